@@ -2,17 +2,29 @@ package main
 
 import (
 	"context"
+	"errors"
 	"fmt"
+	"log"
+	"net/http"
+	"path/filepath"
 	"runtime"
 	"strings"
 	"sync"
 	"time"
 
+	"shell-werk/internal/config"
+	"shell-werk/internal/conversations"
 	"shell-werk/internal/llm"
 	"shell-werk/internal/shell"
 	"shell-werk/internal/tools"
 )
 
+// dialogueTimeout bounds a full tool dialogue, including approval prompts.
+const dialogueTimeout = 120 * time.Second
+
+// toolAuditFileName is the tool audit log inside the data directory.
+const toolAuditFileName = "tool_audit.json"
+
 // App struct
 type App struct {
 	ctx            context.Context
@@ -20,10 +32,26 @@ type App struct {
 	streamer       *llm.Streamer
 	events         *appEventSink
 	prompts        *llm.SystemPromptLoader
+	config         *config.Store
+	traces         *llm.TraceRecorder
+	toolAudit      *llm.ToolAuditLog
+	toolSupport    *llm.ToolSupport
+	conversations  *conversations.Store
+	balancer       *llm.Balancer
+	models         *llm.ModelCache
 	cancelMu       sync.Mutex
-	cancelSessions map[string]cancelEntry
+	cancelRequests map[string]cancelEntry
 	continuationMu sync.Mutex
 	continuations  map[string]continuationWaiter
+	streamsMu      sync.Mutex
+	streams        map[string]activeStream
+}
+
+// activeStream is a background stream together with the request that
+// started it, so it can be restarted with different settings.
+type activeStream struct {
+	req    ChatRequest
+	handle *llm.StreamHandle
 }
 
 type cancelEntry struct {
@@ -41,12 +69,23 @@ func NewApp() *App {
 	app := &App{
 		tools:          tools.NewToolRegistry(tools.DefaultTools()),
 		prompts:        llm.DefaultSystemPromptLoader(),
-		cancelSessions: map[string]cancelEntry{},
+		config:         config.NewMemoryStore(),
+		traces:         llm.NewTraceRecorder(),
+		toolAudit:      llm.NewToolAuditLog(""),
+		toolSupport:    llm.NewToolSupport(),
+		conversations:  conversations.NewStore(""),
+		balancer:       llm.NewBalancer(),
+		models:         llm.NewModelCache(llm.DefaultModelCacheTTL),
+		cancelRequests: map[string]cancelEntry{},
 		continuations:  map[string]continuationWaiter{},
+		streams:        map[string]activeStream{},
 	}
 	app.applyShellToolHint(runtime.GOOS)
 	app.events = &appEventSink{app: app}
 	app.streamer = llm.NewStreamer(app.events)
+	app.streamer.UseToolAudit(app.toolAudit)
+	app.streamer.UseToolSupport(app.toolSupport)
+	app.streamer.UseDialogueDependencies(app.dialogueDependencies())
 	return app
 }
 
@@ -74,6 +113,24 @@ func (a *App) applyShellToolHint(hostOS string) {
 // so we can call the runtime methods
 func (a *App) startup(ctx context.Context) {
 	a.ctx = ctx
+
+	if dataDir, err := config.DataDir(); err != nil {
+		log.Printf("conversation storage unavailable: %v", err)
+	} else {
+		a.conversations = conversations.NewStore(conversations.Dir(dataDir))
+		a.toolAudit = llm.NewToolAuditLog(filepath.Join(dataDir, toolAuditFileName))
+		a.streamer.UseToolAudit(a.toolAudit)
+	}
+
+	path, err := config.DefaultPath()
+	if err != nil {
+		log.Printf("configuration path unavailable, keeping settings in memory: %v", err)
+		return
+	}
+	a.config = config.Open(path)
+	if warning := a.config.Status().Warning; warning != "" {
+		log.Printf("configuration warning: %s", warning)
+	}
 }
 
 // Greet returns a greeting for the given name
@@ -85,14 +142,29 @@ func (a *App) Greet(name string) string {
 func (a *App) Chat(req ChatRequest) (ChatResponse, error) {
 	start := time.Now()
 
+	if err := llm.ValidateModelOverride(req.Model); err != nil {
+		return ChatResponse{}, err
+	}
+
 	ctx := a.ctx
 	if ctx == nil {
 		ctx = context.Background()
 	}
 
+	group := a.applyConfiguration(&req)
+	if strings.TrimSpace(req.SessionID) == "" {
+		req.SessionID = llm.WithNamespace(req.IDPrefix, llm.NewID(req.IDStyle, "session"))
+	}
+
+	req, err := llm.AttachFiles(req)
+	if err != nil {
+		return ChatResponse{}, err
+	}
+
 	ctx, cancel := context.WithCancel(ctx)
-	token := a.trackSessionCancel(req.SessionID, cancel)
-	defer a.releaseSessionCancel(req.SessionID, token, cancel)
+	token := a.trackCancel(req.SessionID, cancel)
+	defer a.releaseCancel(req.SessionID, token, cancel)
+	defer a.events.bindConversation(req.SessionID, req.ConversationID)()
 
 	req.History = llm.ConversationFromRequest(req)
 	req.Message = ""
@@ -102,11 +174,7 @@ func (a *App) Chat(req ChatRequest) (ChatResponse, error) {
 		req.ToolDefs = nil
 	}
 
-	for _, id := range req.Tools {
-		if tool, ok := a.tools.Get(id); ok {
-			req.ToolDefs = append(req.ToolDefs, tool.Definition)
-		}
-	}
+	req.ToolDefs = append(req.ToolDefs, a.toolDefinitions(req.Tools)...)
 
 	a.events.ThinkingStart(req.SessionID)
 	defer a.events.ThinkingEnd(req.SessionID)
@@ -114,6 +182,7 @@ func (a *App) Chat(req ChatRequest) (ChatResponse, error) {
 	if len(req.Tools) == 0 {
 		msg, err := a.streamer.StreamChat(ctx, req)
 		if err != nil {
+			a.reportEndpointFailure(group, req.Endpoint, err)
 			return ChatResponse{}, wrapProviderError(req.Provider, req.Endpoint, err)
 		}
 		return ChatResponse{
@@ -122,14 +191,12 @@ func (a *App) Chat(req ChatRequest) (ChatResponse, error) {
 		}, nil
 	}
 
-	ctx, timeoutCancel := context.WithTimeout(ctx, 120*time.Second)
+	ctx, timeoutCancel := context.WithTimeout(ctx, dialogueTimeout)
 	defer timeoutCancel()
 
-	loop := llm.NewDialogueLoop(req, a.events, llm.DialogueDependencies{
-		PromptLoader:          a.prompts,
-		ContinuationPrompter: a,
-	})
+	loop := llm.NewDialogueLoop(req, a.events, a.dialogueDependencies())
 	msg, trace, err := loop.Run(ctx, req)
+	a.reportEndpointFailure(group, req.Endpoint, err)
 	return ChatResponse{
 		Message:   msg,
 		LatencyMs: time.Since(start).Milliseconds(),
@@ -137,17 +204,261 @@ func (a *App) Chat(req ChatRequest) (ChatResponse, error) {
 	}, wrapProviderError(req.Provider, req.Endpoint, err)
 }
 
+// RunDialogue runs the tool dialogue and returns once it has finished, with
+// every message it appended. It is the blocking alternative to streaming.
+func (a *App) RunDialogue(req ChatRequest) (DialogueResponse, error) {
+	start := time.Now()
+
+	if err := llm.ValidateModelOverride(req.Model); err != nil {
+		return DialogueResponse{}, err
+	}
+
+	group := a.applyConfiguration(&req)
+	if strings.TrimSpace(req.SessionID) == "" {
+		req.SessionID = llm.WithNamespace(req.IDPrefix, llm.NewID(req.IDStyle, "session"))
+	}
+
+	req, err := llm.AttachFiles(req)
+	if err != nil {
+		return DialogueResponse{}, err
+	}
+
+	ctx, cancel := context.WithTimeout(a.baseContext(), dialogueTimeout)
+	token := a.trackCancel(req.SessionID, cancel)
+	defer a.releaseCancel(req.SessionID, token, cancel)
+	defer a.events.bindConversation(req.SessionID, req.ConversationID)()
+
+	req.History = llm.ConversationFromRequest(req)
+	req.Message = ""
+	req.ToolDefs = append(req.ToolDefs, a.toolDefinitions(req.Tools)...)
+
+	loop := llm.NewDialogueLoop(req, a.events, a.dialogueDependencies())
+	msg, trace, err := loop.Run(ctx, req)
+	a.reportEndpointFailure(group, req.Endpoint, err)
+	return DialogueResponse{
+		Message:   msg,
+		Messages:  loop.Messages(),
+		LatencyMs: time.Since(start).Milliseconds(),
+		Trace:     trace,
+		Usage:     loop.Usage(),
+	}, wrapProviderError(req.Provider, req.Endpoint, err)
+}
+
+// BatchComplete answers each prompt in a fresh conversation with the
+// configured system prompt, for prompt regression testing. An empty model
+// uses the selected one; a blank one fails every prompt. Failures are
+// reported per prompt.
+func (a *App) BatchComplete(prompts []string, model string) []BatchResult {
+	if err := llm.ValidateModelOverride(model); err != nil {
+		results := make([]BatchResult, len(prompts))
+		for i, prompt := range prompts {
+			results[i] = BatchResult{Prompt: prompt, Error: err.Error()}
+		}
+		return results
+	}
+	cfg := a.config.Get().Effective()
+	req := ChatRequest{Provider: cfg.Provider, Endpoint: cfg.Endpoint, APIKey: cfg.APIKey, Model: strings.TrimSpace(model)}
+	if req.Model == "" {
+		req.Model = cfg.ActiveModel()
+	}
+	a.applyConfiguration(&req)
+
+	ctx, cancel := context.WithTimeout(a.baseContext(), dialogueTimeout)
+	defer cancel()
+	return llm.BatchComplete(ctx, req, prompts, llm.DialogueDependencies{PromptLoader: a.prompts})
+}
+
+// Regenerate answers the last user message of req.History again. The
+// trailing assistant reply and its tool turns are dropped first; req.Message
+// must be empty. The answer runs through the tool dialogue and the response
+// carries every message it appended, as RunDialogue returns them.
+func (a *App) Regenerate(req ChatRequest) (DialogueResponse, error) {
+	if strings.TrimSpace(req.Message) != "" {
+		return DialogueResponse{}, fmt.Errorf("%w: regenerate does not take a new message", llm.ErrInvalidRequest)
+	}
+	history, err := llm.TrimForRegeneration(req.History)
+	if err != nil {
+		return DialogueResponse{}, err
+	}
+	req.History = history
+	return a.RunDialogue(req)
+}
+
+func (a *App) dialogueDependencies() llm.DialogueDependencies {
+	return llm.DialogueDependencies{
+		PromptLoader:         a.prompts,
+		ContinuationPrompter: a,
+		TraceRecorder:        a.traces,
+		ToolAudit:            a.toolAudit,
+		ToolSupport:          a.toolSupport,
+	}
+}
+
+// toolDefinitions resolves tool IDs to their definitions. The IDs are the
+// exact subset offered for one request, regardless of the enabled flags;
+// unknown IDs are skipped with a warning.
+func (a *App) toolDefinitions(ids []string) []tools.ToolDefinition {
+	a.syncCustomTools()
+	var defs []tools.ToolDefinition
+	for _, id := range ids {
+		tool, ok := a.tools.Get(id)
+		if !ok {
+			log.Printf("ignoring unknown tool %q in request", id)
+			continue
+		}
+		defs = append(defs, tool.Definition)
+	}
+	return defs
+}
+
+// ResumeChat continues an interrupted streamed answer from its saved partial content.
+func (a *App) ResumeChat(req ResumeChatRequest) (ChatResponse, error) {
+	start := time.Now()
+	chatReq := req.Request
+	if err := llm.ValidateModelOverride(chatReq.Model); err != nil {
+		return ChatResponse{}, err
+	}
+	group := a.applyConfiguration(&chatReq)
+
+	ctx, cancel := context.WithCancel(a.baseContext())
+	token := a.trackCancel(chatReq.SessionID, cancel)
+	defer a.releaseCancel(chatReq.SessionID, token, cancel)
+	defer a.events.bindConversation(chatReq.SessionID, chatReq.ConversationID)()
+
+	chatReq.History = llm.ConversationFromRequest(chatReq)
+	chatReq.Message = ""
+	chatReq.Tools = nil
+	chatReq.ToolDefs = nil
+
+	a.events.ThinkingStart(chatReq.SessionID)
+	defer a.events.ThinkingEnd(chatReq.SessionID)
+
+	msg, err := a.streamer.ResumeChat(ctx, chatReq, req.Partial)
+	if err != nil {
+		a.reportEndpointFailure(group, chatReq.Endpoint, err)
+		return ChatResponse{}, wrapProviderError(chatReq.Provider, chatReq.Endpoint, err)
+	}
+	return ChatResponse{
+		Message:   msg,
+		LatencyMs: time.Since(start).Milliseconds(),
+	}, nil
+}
+
+// StreamToFile streams a completion for req straight to a file without UI
+// events, returning the number of bytes written.
+func (a *App) StreamToFile(req ChatRequest, path string) (int, error) {
+	if err := llm.ValidateModelOverride(req.Model); err != nil {
+		return 0, err
+	}
+	a.applyConfiguration(&req)
+	req.History = llm.ConversationFromRequest(req)
+	req.Message = ""
+	req.Tools = nil
+	req.ToolDefs = nil
+
+	written, err := llm.StreamToFile(a.baseContext(), req, path)
+	if err != nil {
+		return 0, wrapProviderError(req.Provider, req.Endpoint, err)
+	}
+	return written, nil
+}
+
+// StartStream streams a reply in the background and returns the request ID
+// to pass to CancelStream. Output arrives through the answer events; failures
+// other than cancellation are reported as an answer:error event.
+func (a *App) StartStream(req ChatRequest) (string, error) {
+	if err := llm.ValidateModelOverride(req.Model); err != nil {
+		return "", err
+	}
+	original := req
+	group := a.applyConfiguration(&req)
+	if strings.TrimSpace(req.SessionID) == "" {
+		req.SessionID = llm.WithNamespace(req.IDPrefix, llm.NewID(req.IDStyle, "stream"))
+	}
+
+	req, err := llm.AttachFiles(req)
+	if err != nil {
+		return "", err
+	}
+	req.History = llm.ConversationFromRequest(req)
+	req.Message = ""
+	if req.ChatOnly {
+		req.Tools = nil
+		req.ToolDefs = nil
+	}
+	req.ToolDefs = append(req.ToolDefs, a.toolDefinitions(req.Tools)...)
+
+	unbind := a.events.bindConversation(req.SessionID, req.ConversationID)
+	a.events.ThinkingStart(req.SessionID)
+	handle := a.streamer.StartStream(a.baseContext(), req)
+	token := a.trackCancel(req.SessionID, handle.Cancel)
+	a.streamsMu.Lock()
+	a.streams[req.SessionID] = activeStream{req: original, handle: handle}
+	a.streamsMu.Unlock()
+
+	go func() {
+		defer unbind()
+		defer a.events.ThinkingEnd(req.SessionID)
+		_, err := handle.Wait()
+		a.releaseCancel(req.SessionID, token, handle.Cancel)
+		a.streamsMu.Lock()
+		delete(a.streams, req.SessionID)
+		a.streamsMu.Unlock()
+		if err == nil || errors.Is(err, context.Canceled) {
+			return
+		}
+		a.reportEndpointFailure(group, req.Endpoint, err)
+		a.events.emit(answerErrorEvent, answerErrorPayload{
+			eventRoute: a.events.route(req.SessionID),
+			Error:      wrapProviderError(req.Provider, req.Endpoint, err).Error(),
+		})
+	}()
+	return req.SessionID, nil
+}
+
+// RestartStream cancels a running stream and starts it again for the same
+// conversation with overrides merged over its sampling parameters, e.g. to
+// regenerate at a higher temperature. It returns the new request ID.
+func (a *App) RestartStream(requestID string, overrides llm.SamplingParams) (string, error) {
+	a.streamsMu.Lock()
+	stream, ok := a.streams[requestID]
+	a.streamsMu.Unlock()
+	if !ok {
+		return "", fmt.Errorf("no active stream %q", requestID)
+	}
+
+	// Wait for the old stream so its final events precede the new ones.
+	stream.handle.Cancel()
+	_, _ = stream.handle.Wait()
+
+	next := stream.req
+	next.SessionID = ""
+	next.Sampling = next.Sampling.Merge(overrides)
+	return a.StartStream(next)
+}
+
+// CancelStream stops a stream started with StartStream.
+func (a *App) CancelStream(requestID string) bool {
+	return a.CancelRequest(requestID)
+}
+
 // CancelChat cancels an in-flight chat session if one exists.
 func (a *App) CancelChat(sessionID string) bool {
-	sessionID = strings.TrimSpace(sessionID)
-	if sessionID == "" {
+	return a.CancelRequest(sessionID)
+}
+
+// CancelRequest cancels any in-flight request registered under id: a chat
+// session, or a model listing or provider probe started with a request ID.
+func (a *App) CancelRequest(id string) bool {
+	id = strings.TrimSpace(id)
+	if id == "" {
 		return false
 	}
 
 	a.cancelMu.Lock()
-	entry, ok := a.cancelSessions[sessionID]
+	entry, ok := a.cancelRequests[id]
 	if ok {
-		delete(a.cancelSessions, sessionID)
+		delete(a.cancelRequests, id)
 	}
 	a.cancelMu.Unlock()
 
@@ -165,7 +476,7 @@ func (a *App) RequestContinuation(ctx context.Context, sessionID string, req llm
 		ctx = a.ctx
 	}
 
-	requestID := fmt.Sprintf("cont-%d", time.Now().UnixNano())
+	requestID := llm.NewID(a.idStyle(), "cont")
 	waiter := continuationWaiter{
 		sessionID: strings.TrimSpace(sessionID),
 		decision:  make(chan llm.ContinuationDecision, 1),
@@ -179,7 +490,7 @@ func (a *App) RequestContinuation(ctx context.Context, sessionID string, req llm
 	a.continuationMu.Unlock()
 
 	a.events.emit(continuationRequestEvent, continuationRequestPayload{
-		SessionID:    waiter.sessionID,
+		eventRoute:   a.events.route(waiter.sessionID),
 		RequestID:    requestID,
 		Reason:       req.Reason,
 		Iteration:    req.Iteration,
@@ -194,19 +505,19 @@ func (a *App) RequestContinuation(ctx context.Context, sessionID string, req llm
 	case decision := <-waiter.decision:
 		a.clearContinuation(requestID)
 		a.events.emit(continuationResolvedEvent, continuationResolvedPayload{
-			SessionID: waiter.sessionID,
-			RequestID: requestID,
-			Decision:  string(decision),
-			Reason:    req.Reason,
+			eventRoute: a.events.route(waiter.sessionID),
+			RequestID:  requestID,
+			Decision:   string(decision),
+			Reason:     req.Reason,
 		})
 		return decision, nil
 	case <-ctx.Done():
 		a.clearContinuation(requestID)
 		a.events.emit(continuationResolvedEvent, continuationResolvedPayload{
-			SessionID: waiter.sessionID,
-			RequestID: requestID,
-			Decision:  "timeout",
-			Reason:    req.Reason,
+			eventRoute: a.events.route(waiter.sessionID),
+			RequestID:  requestID,
+			Decision:   "timeout",
+			Reason:     req.Reason,
 		})
 		return llm.ContinuationDecisionCancel, ctx.Err()
 	}
@@ -235,34 +546,104 @@ func (a *App) ResolveContinuation(req llm.ContinuationDecisionRequest) bool {
 	}
 }
 
-func (a *App) trackSessionCancel(sessionID string, cancel context.CancelFunc) string {
-	if cancel == nil || strings.TrimSpace(sessionID) == "" {
+// applyConfiguration copies backend settings onto a chat request. When a
+// provider group matches the request's provider, the endpoint is chosen by the
+// load balancer and the group is returned so failures can be reported.
+func (a *App) applyConfiguration(req *ChatRequest) *llm.ProviderGroup {
+	cfg := a.config.Get()
+	// A request model overrides the selection for this request only.
+	req.Model = strings.TrimSpace(req.Model)
+	if effective := cfg.Effective(); req.Model == "" && strings.EqualFold(effective.Provider, req.Provider) {
+		req.Model = effective.ActiveModel()
+	}
+	req.IDStyle = llm.ParseIDStyle(cfg.RequestIDStyle)
+	req.StreamFallback = cfg.StreamFallback
+	req.ToolFallback = cfg.ToolFallbackEnabled()
+	req.ParallelTools = cfg.ParallelTools
+	if req.ParallelToolCalls == nil {
+		req.ParallelToolCalls = cfg.ParallelToolCalls
+	}
+	req.RepetitionGuard = cfg.RepetitionGuard
+	req.Sampling = cfg.SamplingFor(req.Provider).Merge(req.Sampling).Clamp().Params
+	req.ToolRoot = cfg.ToolRoot
+	req.MinDeltaChars = cfg.MinDeltaChars
+	req.StripANSI = cfg.StripANSI
+	req.HardMaxCompletionTokens = cfg.HardMaxCompletionTokens
+	if strings.TrimSpace(req.SystemPrompt) == "" {
+		req.SystemPrompt = cfg.SystemPrompt
+	}
+	req.RequestTimeout = cfg.TimeoutFor(req.Provider)
+	req.RequestAttempts = cfg.RequestAttempts
+	req.MaxToolIterations = cfg.MaxToolIterations
+	req.FailFastOnToolError = cfg.FailFastOnToolError
+	req.LogLevel = llm.ParseLogLevel(cfg.LogLevel)
+	req.ExtractCitations = cfg.ExtractCitations
+	req.ToolShell = shell.Options{Env: cfg.ToolEnv, Dir: cfg.ToolCwd, SanitizeEnv: cfg.SanitizeToolEnv}
+	req.CustomTools = cfg.Tools
+
+	group := cfg.ProviderGroup
+	if group == nil || len(group.Endpoints) == 0 || !strings.EqualFold(group.Provider, req.Provider) {
+		return nil
+	}
+	if endpoint, err := a.balancer.Pick(*group); err == nil {
+		req.Endpoint = endpoint
+	}
+	return group
+}
+
+// reportEndpointFailure puts a balanced endpoint on cooldown after a provider error.
+func (a *App) reportEndpointFailure(group *llm.ProviderGroup, endpoint string, err error) {
+	if group == nil || err == nil || errors.Is(err, context.Canceled) {
+		return
+	}
+	a.balancer.MarkFailed(*group, endpoint)
+}
+
+// clientFor builds an HTTP client with the provider's configured timeout and retries.
+func (a *App) clientFor(provider string) *http.Client {
+	cfg := a.config.Get()
+	return llm.WithRetries(llm.MakeClientWithTimeout(cfg.TimeoutFor(provider)), cfg.RequestAttempts)
+}
+
+func (a *App) idStyle() llm.IDStyle {
+	return llm.ParseIDStyle(a.config.Get().RequestIDStyle)
+}
+
+func (a *App) baseContext() context.Context {
+	if a.ctx == nil {
+		return context.Background()
+	}
+	return a.ctx
+}
+
+func (a *App) trackCancel(id string, cancel context.CancelFunc) string {
+	if cancel == nil || strings.TrimSpace(id) == "" {
 		return ""
 	}
 	token := fmt.Sprintf("%d", time.Now().UnixNano())
 
 	a.cancelMu.Lock()
-	if a.cancelSessions == nil {
-		a.cancelSessions = map[string]cancelEntry{}
+	if a.cancelRequests == nil {
+		a.cancelRequests = map[string]cancelEntry{}
 	}
-	a.cancelSessions[sessionID] = cancelEntry{cancel: cancel, token: token}
+	a.cancelRequests[id] = cancelEntry{cancel: cancel, token: token}
 	a.cancelMu.Unlock()
 
 	return token
 }
 
-func (a *App) releaseSessionCancel(sessionID, token string, cancel context.CancelFunc) {
+func (a *App) releaseCancel(id, token string, cancel context.CancelFunc) {
 	if cancel != nil {
 		cancel()
 	}
-	sessionID = strings.TrimSpace(sessionID)
-	if sessionID == "" {
+	id = strings.TrimSpace(id)
+	if id == "" {
 		return
 	}
 	a.cancelMu.Lock()
-	entry, ok := a.cancelSessions[sessionID]
+	entry, ok := a.cancelRequests[id]
 	if ok && entry.token == token {
-		delete(a.cancelSessions, sessionID)
+		delete(a.cancelRequests, id)
 	}
 	a.cancelMu.Unlock()
 }
@@ -280,12 +661,175 @@ func (a *App) clearContinuation(requestID string) {
 
 // Models returns the provider's available models for the configured endpoint.
 func (a *App) Models(req ModelsRequest) (ModelsResponse, error) {
-	models, err := llm.ListModels(a.ctx, req.Provider, req.Endpoint, req.APIKey, nil)
+	ctx, cancel := context.WithCancel(a.baseContext())
+	token := a.trackCancel(req.RequestID, cancel)
+	defer a.releaseCancel(req.RequestID, token, cancel)
+
+	models, ok := a.models.Get(req.Provider, req.Endpoint, req.APIKey)
+	if !ok {
+		var err error
+		models, err = llm.ListModels(ctx, req.Provider, req.Endpoint, req.APIKey, a.clientFor(req.Provider))
+		if err != nil {
+			return ModelsResponse{}, wrapProviderError(req.Provider, req.Endpoint, err)
+		}
+		a.models.Put(req.Provider, req.Endpoint, req.APIKey, models)
+	}
+
+	resp := ModelsResponse{Models: models}
+	if req.DetailedLabels {
+		resp.Labels = llm.ModelLabels(ctx, req, models, a.clientFor(req.Provider))
+	}
+	// Ollama names are readable already; gateway ids often are not.
+	if a.config.Get().PrettifyModelLabels && !strings.EqualFold(req.Provider, "ollama") {
+		resp.Labels = llm.PrettyModelLabels(models)
+	}
+	return resp, nil
+}
+
+// ClearModelCache drops every cached model listing.
+func (a *App) ClearModelCache() {
+	a.models.Clear()
+}
+
+// ListCacheEntries describes each cached model listing for debugging.
+func (a *App) ListCacheEntries() []CacheEntryMeta {
+	return a.models.Entries()
+}
+
+// EvictCacheEntry removes one cached model listing by its key.
+func (a *App) EvictCacheEntry(key string) bool {
+	return a.models.Evict(key)
+}
+
+// ProviderVersion returns the server's reported version, or an empty string
+// when it does not expose one. Like Models, it can be aborted by RequestID.
+func (a *App) ProviderVersion(req ModelsRequest) (string, error) {
+	ctx, cancel := context.WithCancel(a.baseContext())
+	token := a.trackCancel(req.RequestID, cancel)
+	defer a.releaseCancel(req.RequestID, token, cancel)
+
+	version, err := llm.ProviderVersion(ctx, req, a.clientFor(req.Provider))
+	if err != nil {
+		return "", wrapProviderError(req.Provider, req.Endpoint, err)
+	}
+	return version, nil
+}
+
+// ModelWarmState reports which models of the configured provider are loaded
+// in memory, so the picker can point out the ones that will answer quickly.
+// Models come from the model cache; loaded models are included regardless.
+// A non-empty requestID lets the caller abort the probe via CancelRequest.
+func (a *App) ModelWarmState(requestID string) ([]ModelWarmInfo, error) {
+	ctx, cancel := context.WithCancel(a.baseContext())
+	token := a.trackCancel(requestID, cancel)
+	defer a.releaseCancel(requestID, token, cancel)
+
+	cfg := a.config.Get().Effective()
+	models, _ := a.models.Get(cfg.Provider, cfg.Endpoint, cfg.APIKey)
+	target := ModelsRequest{Provider: cfg.Provider, Endpoint: cfg.Endpoint, APIKey: cfg.APIKey}
+	infos, err := llm.ModelWarmState(ctx, target, models, a.clientFor(cfg.Provider))
+	if err != nil {
+		return nil, wrapProviderError(cfg.Provider, cfg.Endpoint, err)
+	}
+	return infos, nil
+}
+
+// ProviderAcceleration reports whether the configured Ollama server runs its
+// loaded models on the GPU, the CPU, or split across both, to explain slow
+// answers. It is empty for other providers and when nothing is loaded.
+// Like ModelWarmState, it can be aborted by requestID.
+func (a *App) ProviderAcceleration(requestID string) (string, error) {
+	ctx, cancel := context.WithCancel(a.baseContext())
+	token := a.trackCancel(requestID, cancel)
+	defer a.releaseCancel(requestID, token, cancel)
+
+	cfg := a.config.Get().Effective()
+	target := ModelsRequest{Provider: cfg.Provider, Endpoint: cfg.Endpoint, APIKey: cfg.APIKey}
+	acceleration, err := llm.ProviderAcceleration(ctx, target, a.clientFor(cfg.Provider))
 	if err != nil {
-		return ModelsResponse{}, wrapProviderError(req.Provider, req.Endpoint, err)
+		return "", wrapProviderError(cfg.Provider, cfg.Endpoint, err)
 	}
+	return acceleration, nil
+}
 
-	return ModelsResponse{Models: models}, nil
+// ListModelsAll fetches models from every given provider concurrently and
+// reports per-provider errors instead of failing the whole call. Without
+// targets it lists every configured profile, keyed by profile name.
+// Successful listings of identified targets also refresh the model cache.
+func (a *App) ListModelsAll(targets []ModelsRequest) AllModels {
+	if len(targets) == 0 {
+		targets = a.config.Get().Effective().ModelsTargets()
+	}
+	result := llm.ListModelsAll(a.baseContext(), targets, nil)
+	for _, target := range targets {
+		if models, ok := result.Models[target.ID]; ok {
+			a.models.Put(target.Provider, target.Endpoint, target.APIKey, models)
+		}
+	}
+	return result
+}
+
+// ToolAuditLog returns the recorded tool invocations, oldest first.
+func (a *App) ToolAuditLog() []ToolAuditEntry {
+	return a.toolAudit.Entries()
+}
+
+// LastTrace returns the redacted provider exchanges of the most recent tool
+// dialogue, or nil when none has run yet.
+func (a *App) LastTrace() *ExchangeTrace {
+	return a.traces.Last()
+}
+
+// WillFit reports whether the history plus the next message fits the model's
+// context, for a warning before sending. A configured num_ctx takes precedence
+// over the probed context length since Ollama truncates to it.
+func (a *App) WillFit(req FitRequest) (FitResult, error) {
+	if req.ContextLength <= 0 {
+		if numCtx := a.config.Get().SamplingFor(req.Provider).NumCtx; numCtx != nil && strings.EqualFold(req.Provider, "ollama") {
+			req.ContextLength = *numCtx
+		}
+	}
+	result, err := llm.WillFit(a.baseContext(), req, a.clientFor(req.Provider))
+	if err != nil {
+		return FitResult{}, wrapProviderError(req.Provider, req.Endpoint, err)
+	}
+	return result, nil
+}
+
+// SupportedSamplingParams lists the sampling parameters the provider accepts.
+func (a *App) SupportedSamplingParams(provider string) []string {
+	return llm.SupportedSamplingParams(provider)
+}
+
+// SamplingPreview returns the sampling values that would be sent after
+// clamping, with a note for each adjustment so the UI can explain it.
+func (a *App) SamplingPreview(params llm.SamplingParams) SamplingPreview {
+	return params.Clamp()
+}
+
+// NormalizeToolArguments exposes how raw tool-call arguments are interpreted.
+func (a *App) NormalizeToolArguments(raw any) any {
+	return llm.NormalizeToolArguments(raw)
+}
+
+// TranscriptPlaintext renders a conversation as labeled plain text for the clipboard.
+func (a *App) TranscriptPlaintext(history []ChatMessage) string {
+	return llm.TranscriptPlaintext(history)
+}
+
+// ExportConversationJSONL renders a conversation as a fine-tuning JSONL line.
+func (a *App) ExportConversationJSONL(history []ChatMessage) (string, error) {
+	return llm.ExportConversationJSONL(history)
+}
+
+// ConversationStats returns message, character, word, and token counts for a conversation.
+func (a *App) ConversationStats(history []ChatMessage) ConversationStats {
+	return llm.ComputeConversationStats(history)
+}
+
+// ConversationHash returns a stable SHA-256 of a conversation for caching.
+func (a *App) ConversationHash(history []ChatMessage) string {
+	return llm.ConversationHash(history)
 }
 
 // RunShellCommand executes a shell command.
@@ -297,13 +841,205 @@ func (a *App) RunShellCommand(command string, args []string, chatOnly bool) (str
 	return executor.Execute(a.ctx, command, args)
 }
 
+// SupportsStreaming probes whether the configured endpoint can stream replies
+// from model, defaulting to the selected model. Results are cached per model.
+// A non-empty requestID lets the caller abort the probe via CancelRequest.
+func (a *App) SupportsStreaming(model, requestID string) (bool, error) {
+	cfg := a.config.Get().Effective()
+	if strings.TrimSpace(model) == "" {
+		model = cfg.SelectedModel
+	}
+	if strings.TrimSpace(model) == "" {
+		return false, fmt.Errorf("no model selected")
+	}
+	ctx, cancel := context.WithCancel(a.baseContext())
+	token := a.trackCancel(requestID, cancel)
+	defer a.releaseCancel(requestID, token, cancel)
+
+	return a.streamer.SupportsStreaming(ctx, ChatRequest{
+		Provider: cfg.Provider,
+		Endpoint: cfg.Endpoint,
+		APIKey:   cfg.APIKey,
+		Model:    model,
+	})
+}
+
+// CheckProviderHealth tests whether the provider's server answers. An empty
+// provider checks the configured connection. It never fails; an unreachable
+// server is reported in the result.
+func (a *App) CheckProviderHealth(req ModelsRequest) HealthStatus {
+	if strings.TrimSpace(req.Provider) == "" {
+		cfg := a.config.Get().Effective()
+		req.Provider, req.Endpoint, req.APIKey = cfg.Provider, cfg.Endpoint, cfg.APIKey
+	}
+	return llm.CheckHealth(a.baseContext(), req, llm.MakeClientWithTimeout(a.config.Get().TimeoutFor(req.Provider)))
+}
+
+// ValidateAll runs health checks, model listings and selected-model checks for
+// every configured profile at once. Successful listings also warm the model cache.
+func (a *App) ValidateAll() ValidationReport {
+	targets := a.config.Get().Effective().DiagnosticsTargets()
+	report := llm.RunDiagnostics(a.baseContext(), targets, nil)
+	for i, result := range report.Providers {
+		if len(result.Models) > 0 {
+			a.models.Put(result.Provider, result.Endpoint, targets[i].Target.APIKey, result.Models)
+		}
+	}
+	return report
+}
+
+// ValidateProviders statically checks each provider config without network calls.
+func (a *App) ValidateProviders(targets []ModelsRequest) []ProviderValidation {
+	return llm.ValidateProviders(targets)
+}
+
+// DetectDuplicateEndpoints groups provider configs that point at the same
+// server so the settings UI can warn about them.
+func (a *App) DetectDuplicateEndpoints(targets []ModelsRequest) [][]string {
+	return llm.DetectDuplicateEndpoints(targets)
+}
+
+// ProviderRequiresAuth reports whether the settings form should require an API key.
+func (a *App) ProviderRequiresAuth(provider string) bool {
+	return llm.ProviderRequiresAuth(provider)
+}
+
+// NormalizeBaseURL validates and cleans an endpoint without saving it.
+func (a *App) NormalizeBaseURL(endpoint, provider string) (string, error) {
+	return llm.NormalizeBaseURL(provider, endpoint)
+}
+
+// GetLLMConfiguration returns the persisted provider configuration.
+func (a *App) GetLLMConfiguration() LLMConfiguration {
+	return a.config.Get()
+}
+
+// GetLLMConfigurationRedacted returns the persisted configuration with every
+// API key masked, for views that only need to show whether a key is set.
+func (a *App) GetLLMConfigurationRedacted() LLMConfiguration {
+	return a.config.Get().Masked()
+}
+
+// GetEffectiveConfiguration returns the configuration after env interpolation
+// and overrides, with the API key masked.
+func (a *App) GetEffectiveConfiguration() LLMConfiguration {
+	return a.config.Get().Effective().Masked()
+}
+
+// SaveLLMConfiguration normalizes and persists the provider configuration.
+func (a *App) SaveLLMConfiguration(cfg LLMConfiguration) (LLMConfiguration, error) {
+	return a.config.Save(cfg)
+}
+
+// SaveLLMConfigurationWithChanges saves like SaveLLMConfiguration and also
+// lists the fields normalization rewrote, e.g. a stripped trailing slash.
+func (a *App) SaveLLMConfigurationWithChanges(cfg LLMConfiguration) (ConfigSaveResult, error) {
+	return a.config.SaveWithChanges(cfg)
+}
+
+// UpdateSelectedModel persists only the selected model.
+func (a *App) UpdateSelectedModel(model string) (LLMConfiguration, error) {
+	return a.config.UpdateSelectedModel(model)
+}
+
+// ListProfiles lists the saved provider profiles without their API keys.
+func (a *App) ListProfiles() []ProfileSummary {
+	return a.config.Get().ProfileSummaries()
+}
+
+// SelectProfile makes the named profile the active provider connection.
+func (a *App) SelectProfile(name string) (LLMConfiguration, error) {
+	return a.config.SelectProfile(name)
+}
+
+// GetSystemPrompt returns the configured system prompt; empty means none is set.
+func (a *App) GetSystemPrompt() string {
+	return a.config.Get().SystemPrompt
+}
+
+// SetSystemPrompt persists only the system prompt; a blank prompt clears it.
+func (a *App) SetSystemPrompt(prompt string) (LLMConfiguration, error) {
+	return a.config.UpdateSystemPrompt(prompt)
+}
+
+// ActiveModel returns the model resolved from the configuration; empty means none is selected.
+func (a *App) ActiveModel() string {
+	return a.config.Get().Effective().ActiveModel()
+}
+
+// ConfigStatus reports where configuration is stored and whether it is read-only.
+func (a *App) ConfigStatus() ConfigStatus {
+	return a.config.Status()
+}
+
+// FeatureFlags reports which optional providers and capabilities this build includes.
+func (a *App) FeatureFlags() FeatureFlags {
+	return llm.Features()
+}
+
+// SaveConversation persists a conversation and returns its metadata.
+func (a *App) SaveConversation(conv Conversation) (ConversationMeta, error) {
+	cfg := a.config.Get()
+	if cfg.MergeConsecutiveAssistant {
+		conv.Messages = conversations.MergeConsecutiveAssistant(conv.Messages)
+	}
+	if max := cfg.MaxConversationMessages; max > 0 {
+		conv.Messages, _ = llm.CompactHistory(conv.Messages, max)
+	}
+	return a.conversations.Save(conv)
+}
+
+// LoadConversation reads a stored conversation.
+func (a *App) LoadConversation(id string) (Conversation, error) {
+	return a.conversations.Load(id)
+}
+
+// ResumeConversation loads a conversation with the provider and model it is
+// pinned to. Conversations saved without a pin resume with the current
+// configuration.
+func (a *App) ResumeConversation(id string) (Conversation, error) {
+	conv, err := a.conversations.Load(id)
+	if err != nil {
+		return Conversation{}, err
+	}
+	if strings.TrimSpace(conv.Provider) == "" {
+		cfg := a.config.Get().Effective()
+		conv.Provider = cfg.Provider
+		conv.Model = cfg.ActiveModel()
+	}
+	return conv, nil
+}
+
+// ListConversations returns metadata for all stored conversations, newest first.
+func (a *App) ListConversations() ([]ConversationMeta, error) {
+	return a.conversations.List()
+}
+
+// DeleteConversation removes a stored conversation; missing ones are ignored.
+func (a *App) DeleteConversation(id string) error {
+	return a.conversations.Delete(id)
+}
+
+// ClearAllConversations removes every stored conversation.
+func (a *App) ClearAllConversations() error {
+	return a.conversations.Clear()
+}
+
+// ValidateConversationFile parses a stored conversation and reports what is
+// wrong with it, if anything, so the UI can offer a repair.
+func (a *App) ValidateConversationFile(id string) (ConversationMeta, error) {
+	return a.conversations.Validate(id)
+}
+
 // GetTools returns the tool metadata for UI rendering and configuration.
 func (a *App) GetTools() []ToolMetadata {
+	a.syncCustomTools()
 	return a.tools.List()
 }
 
 // SetToolEnabled flips the enabled flag for a tool and returns the updated list.
 func (a *App) SetToolEnabled(req SetToolEnabledRequest) ([]ToolMetadata, error) {
+	a.syncCustomTools()
 	if _, err := a.tools.SetEnabled(req.ID, req.Enabled); err != nil {
 		return nil, err
 	}
@@ -311,6 +1047,29 @@ func (a *App) SetToolEnabled(req SetToolEnabledRequest) ([]ToolMetadata, error)
 	return a.tools.List(), nil
 }
 
+// ExportToolsSchema returns the enabled tools, custom ones included, as an
+// OpenAI-compatible tools array for reuse in other systems.
+func (a *App) ExportToolsSchema() []ToolDefinition {
+	a.syncCustomTools()
+	defs := []ToolDefinition{}
+	for _, tool := range a.tools.List() {
+		if tool.Enabled {
+			defs = append(defs, tool.Definition)
+		}
+	}
+	return defs
+}
+
+// syncCustomTools brings the registry in line with the configured custom tools.
+func (a *App) syncCustomTools() {
+	custom := a.config.Get().Tools
+	metadata := make([]tools.ToolMetadata, 0, len(custom))
+	for _, tool := range custom {
+		metadata = append(metadata, tool.Metadata())
+	}
+	a.tools.SetCustom(metadata)
+}
+
 func wrapProviderError(provider, endpoint string, err error) error {
 	if err == nil {
 		return nil
@@ -322,8 +1081,8 @@ func wrapProviderError(provider, endpoint string, err error) error {
 
 	trimmedEndpoint := strings.TrimSpace(endpoint)
 	if trimmedEndpoint != "" {
-		target = fmt.Sprintf("%s @ %s", target, trimmedEndpoint)
+		target = fmt.Sprintf("%s @ %s", target, llm.RedactURL(trimmedEndpoint))
 	}
 
-	return fmt.Errorf("%s: %w", target, err)
+	return fmt.Errorf("%s: %w", target, llm.RedactError(err))
 }
@@ -0,0 +1,254 @@
+package main
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"sync"
+	"testing"
+	"time"
+
+	"shell-werk/internal/llm"
+)
+
+func TestCancelRequestAbortsModelListing(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		select {
+		case <-r.Context().Done():
+		case <-time.After(5 * time.Second):
+		}
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	done := make(chan error, 1)
+	start := time.Now()
+	go func() {
+		_, err := app.Models(ModelsRequest{Provider: "ollama", Endpoint: server.URL, RequestID: "models-1"})
+		done <- err
+	}()
+
+	deadline := time.Now().Add(2 * time.Second)
+	for !app.CancelRequest("models-1") {
+		if time.Now().After(deadline) {
+			t.Fatalf("model listing was never registered for cancellation")
+		}
+		time.Sleep(5 * time.Millisecond)
+	}
+
+	select {
+	case err := <-done:
+		if !errors.Is(err, context.Canceled) {
+			t.Fatalf("expected cancellation error, got %v", err)
+		}
+	case <-time.After(2 * time.Second):
+		t.Fatalf("model listing did not return after cancel")
+	}
+	if elapsed := time.Since(start); elapsed >= 5*time.Second {
+		t.Fatalf("expected prompt return, took %s", elapsed)
+	}
+}
+
+func TestCancelStreamStopsBackgroundStream(t *testing.T) {
+	started, stopped := make(chan struct{}), make(chan struct{})
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		close(started)
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"partial\"}}]}\n\n")
+		if flusher, ok := w.(http.Flusher); ok {
+			flusher.Flush()
+		}
+		select {
+		case <-r.Context().Done():
+			close(stopped)
+		case <-time.After(5 * time.Second):
+		}
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	requestID, err := app.StartStream(ChatRequest{Provider: "vllm", Endpoint: server.URL, Model: "x", Message: "hi"})
+	if err != nil {
+		t.Fatalf("StartStream returned error: %v", err)
+	}
+	if requestID == "" {
+		t.Fatalf("expected a request ID")
+	}
+	select {
+	case <-started:
+	case <-time.After(2 * time.Second):
+		t.Fatalf("stream request never reached the server")
+	}
+
+	if !app.CancelStream(requestID) {
+		t.Fatalf("expected the stream to be registered for cancellation")
+	}
+	select {
+	case <-stopped:
+	case <-time.After(2 * time.Second):
+		t.Fatalf("stream was not aborted after cancel")
+	}
+	if app.CancelStream(requestID) {
+		t.Fatalf("expected a second cancel to find nothing")
+	}
+}
+
+func TestCancelRequestAbortsVersionProbe(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		select {
+		case <-r.Context().Done():
+		case <-time.After(5 * time.Second):
+		}
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	done := make(chan error, 1)
+	go func() {
+		_, err := app.ProviderVersion(ModelsRequest{Provider: "vllm", Endpoint: server.URL, RequestID: "probe-1"})
+		done <- err
+	}()
+
+	deadline := time.Now().Add(2 * time.Second)
+	for !app.CancelRequest("probe-1") {
+		if time.Now().After(deadline) {
+			t.Fatalf("probe was never registered for cancellation")
+		}
+		time.Sleep(5 * time.Millisecond)
+	}
+
+	select {
+	case err := <-done:
+		if !errors.Is(err, context.Canceled) {
+			t.Fatalf("expected cancellation error, got %v", err)
+		}
+	case <-time.After(2 * time.Second):
+		t.Fatalf("probe did not return after cancel")
+	}
+}
+
+func TestCancelRequestAbortsWarmStateProbe(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		select {
+		case <-r.Context().Done():
+		case <-time.After(5 * time.Second):
+		}
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	if _, err := app.SaveLLMConfiguration(LLMConfiguration{Provider: "ollama", Endpoint: server.URL}); err != nil {
+		t.Fatalf("SaveLLMConfiguration returned error: %v", err)
+	}
+	done := make(chan error, 1)
+	go func() {
+		_, err := app.ModelWarmState("warm-1")
+		done <- err
+	}()
+
+	deadline := time.Now().Add(2 * time.Second)
+	for !app.CancelRequest("warm-1") {
+		if time.Now().After(deadline) {
+			t.Fatalf("probe was never registered for cancellation")
+		}
+		time.Sleep(5 * time.Millisecond)
+	}
+
+	select {
+	case err := <-done:
+		if !errors.Is(err, context.Canceled) {
+			t.Fatalf("expected cancellation error, got %v", err)
+		}
+	case <-time.After(2 * time.Second):
+		t.Fatalf("probe did not return after cancel")
+	}
+}
+
+func TestRestartStreamCancelsAndResendsWithOverrides(t *testing.T) {
+	var mu sync.Mutex
+	var temperatures []*float64
+	firstStarted, firstStopped := make(chan struct{}), make(chan struct{})
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var payload struct {
+			Temperature *float64 `json:"temperature"`
+			Messages    []struct {
+				Content string `json:"content"`
+			} `json:"messages"`
+		}
+		if err := json.NewDecoder(r.Body).Decode(&payload); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		if last := payload.Messages[len(payload.Messages)-1].Content; last != "write a poem" {
+			t.Fatalf("expected the user message to be resent, got %q", last)
+		}
+		mu.Lock()
+		temperatures = append(temperatures, payload.Temperature)
+		first := len(temperatures) == 1
+		mu.Unlock()
+
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"roses\"}}]}\n\n")
+		if flusher, ok := w.(http.Flusher); ok {
+			flusher.Flush()
+		}
+		if !first {
+			fmt.Fprint(w, "data: [DONE]\n\n")
+			return
+		}
+		close(firstStarted)
+		select {
+		case <-r.Context().Done():
+			close(firstStopped)
+		case <-time.After(5 * time.Second):
+		}
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	firstID, err := app.StartStream(ChatRequest{Provider: "vllm", Endpoint: server.URL, Model: "x", Message: "write a poem"})
+	if err != nil {
+		t.Fatalf("StartStream returned error: %v", err)
+	}
+	select {
+	case <-firstStarted:
+	case <-time.After(2 * time.Second):
+		t.Fatalf("first stream never reached the server")
+	}
+
+	temperature := 0.9
+	secondID, err := app.RestartStream(firstID, llm.SamplingParams{Temperature: &temperature})
+	if err != nil {
+		t.Fatalf("RestartStream returned error: %v", err)
+	}
+	if secondID == "" || secondID == firstID {
+		t.Fatalf("expected a new request ID, got %q", secondID)
+	}
+	select {
+	case <-firstStopped:
+	case <-time.After(2 * time.Second):
+		t.Fatalf("first stream was not cancelled")
+	}
+
+	deadline := time.Now().Add(2 * time.Second)
+	for {
+		mu.Lock()
+		count := len(temperatures)
+		mu.Unlock()
+		if count == 2 {
+			break
+		}
+		if time.Now().After(deadline) {
+			t.Fatalf("restarted stream never reached the server")
+		}
+		time.Sleep(5 * time.Millisecond)
+	}
+	mu.Lock()
+	defer mu.Unlock()
+	if temperatures[0] != nil {
+		t.Fatalf("expected no temperature on the first request, got %v", *temperatures[0])
+	}
+	if temperatures[1] == nil || *temperatures[1] != 0.9 {
+		t.Fatalf("expected the override temperature on the restarted request, got %v", temperatures[1])
+	}
+}
@@ -1,8 +1,20 @@
 package main
 
 import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"path/filepath"
+	"slices"
 	"strings"
+	"sync"
 	"testing"
+	"time"
+
+	"shell-werk/internal/config"
+	"shell-werk/internal/llm"
 )
 
 func TestChatRespectsChatOnly(t *testing.T) {
@@ -27,3 +39,406 @@ func TestChatRespectsChatOnly(t *testing.T) {
 		t.Fatalf("unexpected chat response: %q", resp.Message.Content)
 	}
 }
+
+func TestChatAppliesPerProviderSampling(t *testing.T) {
+	var ollamaTemp, vllmTemp, overrideTemp *float64
+
+	ollama := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var payload struct {
+			Options struct {
+				Temperature *float64 `json:"temperature"`
+			} `json:"options"`
+		}
+		if err := json.NewDecoder(r.Body).Decode(&payload); err != nil {
+			t.Fatalf("failed to decode ollama payload: %v", err)
+		}
+		ollamaTemp = payload.Options.Temperature
+		fmt.Fprintln(w, `{"message":{"role":"assistant","content":"ok"},"done":true}`)
+	}))
+	defer ollama.Close()
+
+	vllm := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var payload struct {
+			Temperature *float64 `json:"temperature"`
+		}
+		if err := json.NewDecoder(r.Body).Decode(&payload); err != nil {
+			t.Fatalf("failed to decode vllm payload: %v", err)
+		}
+		if vllmTemp == nil {
+			vllmTemp = payload.Temperature
+		} else {
+			overrideTemp = payload.Temperature
+		}
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"ok\"}}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer vllm.Close()
+
+	zero, warm, hot, global := 0.0, 0.8, 1.2, 0.5
+	app := NewApp()
+	if _, err := app.SaveLLMConfiguration(LLMConfiguration{
+		Provider: "ollama",
+		Sampling: llm.SamplingParams{Temperature: &global},
+		ProviderSampling: map[string]llm.SamplingParams{
+			"ollama": {Temperature: &zero},
+			"vllm":   {Temperature: &warm},
+		},
+	}); err != nil {
+		t.Fatalf("SaveLLMConfiguration returned error: %v", err)
+	}
+
+	requests := []ChatRequest{
+		{Provider: "ollama", Endpoint: ollama.URL, Model: "coder", Message: "hi"},
+		{Provider: "vllm", Endpoint: vllm.URL, Model: "chat", Message: "hi"},
+		{Provider: "vllm", Endpoint: vllm.URL, Model: "chat", Message: "hi", Sampling: llm.SamplingParams{Temperature: &hot}},
+	}
+	for _, req := range requests {
+		if _, err := app.Chat(req); err != nil {
+			t.Fatalf("Chat(%s) returned error: %v", req.Provider, err)
+		}
+	}
+
+	if ollamaTemp == nil || *ollamaTemp != zero {
+		t.Fatalf("expected ollama temperature %v, got %v", zero, ollamaTemp)
+	}
+	if vllmTemp == nil || *vllmTemp != warm {
+		t.Fatalf("expected vllm temperature %v, got %v", warm, vllmTemp)
+	}
+	if overrideTemp == nil || *overrideTemp != hot {
+		t.Fatalf("expected request-level temperature %v, got %v", hot, overrideTemp)
+	}
+}
+
+func TestChatRoundRobinsAcrossProviderGroup(t *testing.T) {
+	hits := map[string]int{}
+	newHost := func(name string) *httptest.Server {
+		return httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			hits[name]++
+			fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"ok\"}}]}\n\n")
+			fmt.Fprint(w, "data: [DONE]\n\n")
+		}))
+	}
+	first, second := newHost("first"), newHost("second")
+	defer first.Close()
+	defer second.Close()
+
+	app := NewApp()
+	if _, err := app.SaveLLMConfiguration(LLMConfiguration{
+		Provider:      "vllm",
+		ProviderGroup: &llm.ProviderGroup{Provider: "vllm", Endpoints: []string{first.URL, second.URL}},
+	}); err != nil {
+		t.Fatalf("SaveLLMConfiguration returned error: %v", err)
+	}
+
+	for i := 0; i < 4; i++ {
+		if _, err := app.Chat(ChatRequest{Provider: "vllm", Model: "x", Message: "hi"}); err != nil {
+			t.Fatalf("Chat returned error: %v", err)
+		}
+	}
+	if hits["first"] != 2 || hits["second"] != 2 {
+		t.Fatalf("expected requests to alternate between hosts, got %v", hits)
+	}
+}
+
+func TestChatSendsConfiguredSystemPromptFirst(t *testing.T) {
+	var received struct {
+		Messages []struct {
+			Role    string `json:"role"`
+			Content string `json:"content"`
+		} `json:"messages"`
+	}
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if err := json.NewDecoder(r.Body).Decode(&received); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"ok\"}}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	if _, err := app.SaveLLMConfiguration(LLMConfiguration{Provider: "vllm", SystemPrompt: "  You are a shell assistant on Arch Linux.  "}); err != nil {
+		t.Fatalf("SaveLLMConfiguration returned error: %v", err)
+	}
+
+	_, err := app.Chat(ChatRequest{
+		Provider: "vllm",
+		Endpoint: server.URL,
+		Model:    "x",
+		History:  []ChatMessage{{Role: "system", Content: "stale"}, {Role: "user", Content: "hi"}},
+		Message:  "list my disks",
+	})
+	if err != nil {
+		t.Fatalf("Chat returned error: %v", err)
+	}
+
+	if len(received.Messages) == 0 {
+		t.Fatalf("expected messages in the request body")
+	}
+	first := received.Messages[0]
+	if first.Role != "system" || first.Content != "You are a shell assistant on Arch Linux." {
+		t.Fatalf("expected configured system prompt first, got %+v", received.Messages)
+	}
+	for _, msg := range received.Messages[1:] {
+		if msg.Role == "system" {
+			t.Fatalf("expected a single system message, got %+v", received.Messages)
+		}
+	}
+}
+
+func TestConcurrentStreamsRouteEventsToTheirConversation(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var payload struct {
+			Messages []struct {
+				Content string `json:"content"`
+			} `json:"messages"`
+		}
+		if err := json.NewDecoder(r.Body).Decode(&payload); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		word := payload.Messages[len(payload.Messages)-1].Content
+		for i := 0; i < 5; i++ {
+			fmt.Fprintf(w, "data: {\"choices\":[{\"delta\":{\"content\":\"%s \"}}]}\n\n", word)
+			if flusher, ok := w.(http.Flusher); ok {
+				flusher.Flush()
+			}
+			time.Sleep(5 * time.Millisecond)
+		}
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	var mu sync.Mutex
+	answers := map[string]string{}
+	sessions := map[string]string{}
+	done := make(chan struct{}, 2)
+
+	app := NewApp()
+	app.events.publish = func(name string, payload any) {
+		mu.Lock()
+		defer mu.Unlock()
+		switch event := payload.(type) {
+		case answerEvent:
+			answers[event.ConversationID] += event.Chunk
+			if previous, ok := sessions[event.ConversationID]; ok && previous != event.SessionID {
+				t.Errorf("conversation %s received events from sessions %s and %s", event.ConversationID, previous, event.SessionID)
+			}
+			sessions[event.ConversationID] = event.SessionID
+		case answerDonePayload:
+			done <- struct{}{}
+		}
+	}
+
+	for _, conversation := range []string{"alpha", "beta"} {
+		if _, err := app.StartStream(ChatRequest{
+			Provider:       "vllm",
+			Endpoint:       server.URL,
+			Model:          "x",
+			Message:        conversation,
+			ConversationID: conversation,
+		}); err != nil {
+			t.Fatalf("StartStream returned error: %v", err)
+		}
+	}
+	for i := 0; i < 2; i++ {
+		select {
+		case <-done:
+		case <-time.After(2 * time.Second):
+			t.Fatalf("streams did not finish")
+		}
+	}
+
+	mu.Lock()
+	defer mu.Unlock()
+	for _, conversation := range []string{"alpha", "beta"} {
+		want := strings.Repeat(conversation+" ", 5)
+		if answers[conversation] != want {
+			t.Fatalf("conversation %s received %q, want %q", conversation, answers[conversation], want)
+		}
+	}
+	if len(answers) != 2 {
+		t.Fatalf("expected events only for the two conversations, got %+v", answers)
+	}
+}
+
+func TestChatSendsConfiguredCustomTools(t *testing.T) {
+	var received struct {
+		Tools []struct {
+			Function struct {
+				Name string `json:"name"`
+			} `json:"function"`
+		} `json:"tools"`
+	}
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if err := json.NewDecoder(r.Body).Decode(&received); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		fmt.Fprint(w, `{"choices":[{"message":{"role":"assistant","content":"ok"}}]}`)
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	if _, err := app.SaveLLMConfiguration(LLMConfiguration{
+		Provider: "vllm",
+		Tools: []CustomTool{{
+			Name:        "git_log",
+			Description: "Show recent commits.",
+			Parameters:  map[string]any{"type": "object", "properties": map[string]any{"count": map[string]any{"type": "number"}}},
+			Command:     "git log -n {count}",
+		}},
+	}); err != nil {
+		t.Fatalf("SaveLLMConfiguration returned error: %v", err)
+	}
+
+	var ids []string
+	for _, tool := range app.GetTools() {
+		if tool.Enabled {
+			ids = append(ids, tool.ID)
+		}
+	}
+	if _, err := app.Chat(ChatRequest{Provider: "vllm", Endpoint: server.URL, Model: "x", Message: "what changed?", Tools: ids}); err != nil {
+		t.Fatalf("Chat returned error: %v", err)
+	}
+
+	var names []string
+	for _, tool := range received.Tools {
+		names = append(names, tool.Function.Name)
+	}
+	if !slices.Contains(names, "git_log") || !slices.Contains(names, "shell") {
+		t.Fatalf("expected the custom tool next to the built-ins, got %v", names)
+	}
+
+	duplicate := []CustomTool{{Name: "shell", Command: "echo hi"}}
+	if _, err := app.SaveLLMConfiguration(LLMConfiguration{Provider: "vllm", Tools: duplicate}); !errors.Is(err, config.ErrInvalidConfig) {
+		t.Fatalf("expected a duplicate tool name to be rejected, got %v", err)
+	}
+}
+
+func TestChatSendsOnlyTheRequestedToolSubset(t *testing.T) {
+	var received struct {
+		Tools []struct {
+			Function struct {
+				Name string `json:"name"`
+			} `json:"function"`
+		} `json:"tools"`
+	}
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if err := json.NewDecoder(r.Body).Decode(&received); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		fmt.Fprint(w, `{"choices":[{"message":{"role":"assistant","content":"ok"}}]}`)
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	if _, err := app.SetToolEnabled(SetToolEnabledRequest{ID: "shell", Enabled: false}); err != nil {
+		t.Fatalf("SetToolEnabled returned error: %v", err)
+	}
+	req := ChatRequest{Provider: "vllm", Endpoint: server.URL, Model: "x", Message: "list files", Tools: []string{"shell", "no_such_tool"}}
+	if _, err := app.Chat(req); err != nil {
+		t.Fatalf("Chat returned error: %v", err)
+	}
+
+	if len(received.Tools) != 1 || received.Tools[0].Function.Name != "shell" {
+		t.Fatalf("expected only the shell tool schema, got %+v", received.Tools)
+	}
+}
+
+func TestRegenerateRequestsAFreshAnswerToTheLastUserMessage(t *testing.T) {
+	var received struct {
+		Messages []struct {
+			Role    string `json:"role"`
+			Content string `json:"content"`
+		} `json:"messages"`
+	}
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if err := json.NewDecoder(r.Body).Decode(&received); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		fmt.Fprint(w, `{"choices":[{"message":{"role":"assistant","content":"fresh answer"}}]}`)
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	req := ChatRequest{
+		Provider: "vllm",
+		Endpoint: server.URL,
+		Model:    "x",
+		History: []ChatMessage{
+			{Role: "user", Content: "list my disks"},
+			{Role: "assistant", Content: "stale answer"},
+		},
+	}
+	resp, err := app.Regenerate(req)
+	if err != nil {
+		t.Fatalf("Regenerate returned error: %v", err)
+	}
+	if resp.Message.Content != "fresh answer" || len(resp.Messages) != 1 || resp.Messages[0].Content != "fresh answer" {
+		t.Fatalf("expected the fresh reply to be appended, got %+v", resp)
+	}
+	last := received.Messages[len(received.Messages)-1]
+	if last.Role != "user" || last.Content != "list my disks" {
+		t.Fatalf("expected the last user message to be sent again, got %+v", received.Messages)
+	}
+	for _, msg := range received.Messages {
+		if msg.Content == "stale answer" {
+			t.Fatalf("expected the old reply to be dropped, got %+v", received.Messages)
+		}
+	}
+
+	req.History = req.History[:1]
+	if _, err := app.Regenerate(req); !errors.Is(err, llm.ErrNothingToRegenerate) {
+		t.Fatalf("expected ErrNothingToRegenerate for a history ending with the user, got %v", err)
+	}
+	req.Message = "something new"
+	if _, err := app.Regenerate(req); !errors.Is(err, llm.ErrInvalidRequest) {
+		t.Fatalf("expected ErrInvalidRequest for a new message, got %v", err)
+	}
+}
+
+func TestChatModelOverridesTheSelectionForOneRequest(t *testing.T) {
+	var models []string
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var payload struct {
+			Model string `json:"model"`
+		}
+		if err := json.NewDecoder(r.Body).Decode(&payload); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		models = append(models, payload.Model)
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"ok\"}}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	if _, err := app.SaveLLMConfiguration(LLMConfiguration{Provider: "vllm", Endpoint: server.URL, SelectedModel: "qwen-7b"}); err != nil {
+		t.Fatalf("SaveLLMConfiguration returned error: %v", err)
+	}
+
+	for _, model := range []string{" llama-70b ", ""} {
+		if _, err := app.Chat(ChatRequest{Provider: "vllm", Endpoint: server.URL, Model: model, Message: "hi"}); err != nil {
+			t.Fatalf("Chat returned error: %v", err)
+		}
+	}
+	if !slices.Equal(models, []string{"llama-70b", "qwen-7b"}) {
+		t.Fatalf("expected the override, then the selection, got %v", models)
+	}
+
+	if _, err := app.Chat(ChatRequest{Provider: "vllm", Endpoint: server.URL, Model: "   ", Message: "hi"}); !errors.Is(err, llm.ErrInvalidRequest) {
+		t.Fatalf("expected a blank override to be rejected, got %v", err)
+	}
+	blank := ChatRequest{Provider: "vllm", Endpoint: server.URL, Model: "   ", Message: "hi"}
+	if _, err := app.StreamToFile(blank, filepath.Join(t.TempDir(), "out.txt")); !errors.Is(err, llm.ErrInvalidRequest) {
+		t.Fatalf("expected StreamToFile to reject a blank override, got %v", err)
+	}
+	if _, err := app.ResumeChat(ResumeChatRequest{Request: blank, Partial: "ro"}); !errors.Is(err, llm.ErrInvalidRequest) {
+		t.Fatalf("expected ResumeChat to reject a blank override, got %v", err)
+	}
+	if results := app.BatchComplete([]string{"hi"}, "   "); len(results) != 1 || results[0].Error == "" {
+		t.Fatalf("expected BatchComplete to reject a blank override, got %+v", results)
+	}
+	if len(models) != 2 {
+		t.Fatalf("expected no request for a blank override, got %v", models)
+	}
+}
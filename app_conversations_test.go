@@ -0,0 +1,96 @@
+package main
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"shell-werk/internal/config"
+	"shell-werk/internal/conversations"
+)
+
+func TestResumeConversationReturnsPinnedModel(t *testing.T) {
+	app := NewApp()
+	app.conversations = conversations.NewStore(t.TempDir())
+	if _, err := app.UpdateSelectedModel("current-model"); err != nil {
+		t.Fatalf("UpdateSelectedModel returned error: %v", err)
+	}
+
+	if _, err := app.SaveConversation(Conversation{
+		ID:       "pinned",
+		Title:    "Old chat",
+		Provider: "ollama",
+		Model:    "qwen3:4b",
+		Messages: []ChatMessage{{Role: "user", Content: "hi"}},
+	}); err != nil {
+		t.Fatalf("SaveConversation returned error: %v", err)
+	}
+
+	conv, err := app.ResumeConversation("pinned")
+	if err != nil {
+		t.Fatalf("ResumeConversation returned error: %v", err)
+	}
+	if conv.Provider != "ollama" || conv.Model != "qwen3:4b" || len(conv.Messages) != 1 {
+		t.Fatalf("expected pinned provider and model, got %+v", conv)
+	}
+}
+
+func TestStartupSeparatesDataAndConfigDirs(t *testing.T) {
+	dataDir := t.TempDir()
+	configPath := filepath.Join(t.TempDir(), "llm-config.json")
+	t.Setenv(config.DataPathEnv, dataDir)
+	t.Setenv(config.ConfigPathEnv, configPath)
+
+	app := NewApp()
+	app.startup(context.Background())
+
+	if _, err := app.SaveConversation(Conversation{ID: "chat-1", Title: "x"}); err != nil {
+		t.Fatalf("SaveConversation returned error: %v", err)
+	}
+	if _, err := os.Stat(filepath.Join(dataDir, "conversations", "chat-1.json")); err != nil {
+		t.Fatalf("expected conversation in the data dir: %v", err)
+	}
+	if _, err := os.Stat(configPath); err != nil {
+		t.Fatalf("expected config in its own dir: %v", err)
+	}
+	if _, err := os.Stat(filepath.Join(filepath.Dir(configPath), "conversations")); !os.IsNotExist(err) {
+		t.Fatalf("conversations must not be written next to the config, got %v", err)
+	}
+}
+
+func TestSaveConversationCompactsPastLimit(t *testing.T) {
+	app := NewApp()
+	app.conversations = conversations.NewStore(t.TempDir())
+	if _, err := app.SaveLLMConfiguration(LLMConfiguration{Provider: "mock", MaxConversationMessages: 4}); err != nil {
+		t.Fatalf("SaveLLMConfiguration returned error: %v", err)
+	}
+
+	var messages []ChatMessage
+	for i := 0; i < 5; i++ {
+		messages = append(messages,
+			ChatMessage{Role: "user", Content: fmt.Sprintf("question %d", i)},
+			ChatMessage{Role: "assistant", Content: fmt.Sprintf("answer %d", i)},
+		)
+	}
+	meta, err := app.SaveConversation(Conversation{ID: "long", Messages: messages})
+	if err != nil {
+		t.Fatalf("SaveConversation returned error: %v", err)
+	}
+	if meta.MessageCount != 4 {
+		t.Fatalf("expected the stored conversation to be compacted to 4 messages, got %d", meta.MessageCount)
+	}
+
+	stored, err := app.LoadConversation("long")
+	if err != nil {
+		t.Fatalf("LoadConversation returned error: %v", err)
+	}
+	if stored.Messages[0].Role != "system" || !strings.Contains(stored.Messages[0].Content, "question 0") {
+		t.Fatalf("expected a summary message first, got %+v", stored.Messages[0])
+	}
+	if stored.Messages[3].Content != "answer 4" {
+		t.Fatalf("expected the latest turn to be kept, got %+v", stored.Messages[3])
+	}
+}
@@ -0,0 +1,107 @@
+package main
+
+import (
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+)
+
+func TestRunDialogueReturnsAppendedMessages(t *testing.T) {
+	calls := 0
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.Path != "/v1/chat/completions" {
+			t.Fatalf("unexpected path: %s", r.URL.Path)
+		}
+		calls++
+		message := map[string]any{"role": "assistant", "content": "The directory has 3 files."}
+		if calls == 1 {
+			message = map[string]any{
+				"role":    "assistant",
+				"content": "",
+				"tool_calls": []map[string]any{{
+					"id":       "call-1",
+					"type":     "function",
+					"function": map[string]any{"name": "lookup", "arguments": `{}`},
+				}},
+			}
+		}
+		_ = json.NewEncoder(w).Encode(map[string]any{
+			"choices": []map[string]any{{"message": message}},
+		})
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	resp, err := app.RunDialogue(ChatRequest{
+		Provider: "vllm",
+		Endpoint: server.URL,
+		Model:    "x",
+		Message:  "how many files?",
+		Tools:    []string{"shell"},
+	})
+	if err != nil {
+		t.Fatalf("RunDialogue returned error: %v", err)
+	}
+
+	if len(resp.Messages) != 3 {
+		t.Fatalf("expected tool request, tool result and answer, got %+v", resp.Messages)
+	}
+	if resp.Messages[0].Role != "assistant" || len(resp.Messages[0].ToolCalls) != 1 {
+		t.Fatalf("expected the tool-calling turn first, got %+v", resp.Messages[0])
+	}
+	if resp.Messages[1].Role != "tool" {
+		t.Fatalf("expected a tool turn, got %+v", resp.Messages[1])
+	}
+	last := resp.Messages[2]
+	if last.Role != "assistant" || last.Content != "The directory has 3 files." {
+		t.Fatalf("expected the assistant answer last, got %+v", last)
+	}
+	if resp.Message.Content != last.Content {
+		t.Fatalf("expected the final message to match the answer, got %+v", resp.Message)
+	}
+}
+
+func TestRunDialogueKeepsTheBuiltInPromptAfterTheConfiguredOne(t *testing.T) {
+	var system string
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var payload struct {
+			Messages []struct {
+				Role    string `json:"role"`
+				Content string `json:"content"`
+			} `json:"messages"`
+		}
+		if err := json.NewDecoder(r.Body).Decode(&payload); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		if len(payload.Messages) > 0 && payload.Messages[0].Role == "system" {
+			system = payload.Messages[0].Content
+		}
+		_ = json.NewEncoder(w).Encode(map[string]any{
+			"choices": []map[string]any{{"message": map[string]any{"role": "assistant", "content": "ok"}}},
+		})
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	if _, err := app.SaveLLMConfiguration(LLMConfiguration{Provider: "vllm", SystemPrompt: "You are a shell assistant on Arch Linux."}); err != nil {
+		t.Fatalf("SaveLLMConfiguration returned error: %v", err)
+	}
+	if _, err := app.RunDialogue(ChatRequest{
+		Provider: "vllm",
+		Endpoint: server.URL,
+		Model:    "x",
+		Message:  "list my disks",
+		Tools:    []string{"shell"},
+	}); err != nil {
+		t.Fatalf("RunDialogue returned error: %v", err)
+	}
+
+	if !strings.HasPrefix(system, "You are a shell assistant on Arch Linux.") {
+		t.Fatalf("expected the configured prompt first, got %q", system)
+	}
+	if !strings.Contains(system, "request_fullfilled") {
+		t.Fatalf("expected the built-in prompt to follow, got %q", system)
+	}
+}
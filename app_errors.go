@@ -0,0 +1,85 @@
+package main
+
+import (
+	"context"
+	"errors"
+	"io/fs"
+	"net"
+	"net/url"
+
+	"shell-werk/internal/config"
+	"shell-werk/internal/conversations"
+	"shell-werk/internal/llm"
+)
+
+// Error kinds the frontend can branch on. They are part of the binding
+// contract, so existing values must not change.
+const (
+	errorKindMissingProviderConfig = "missing_provider_config"
+	errorKindCancelled             = "cancelled"
+	errorKindTimeout               = "timeout"
+	errorKindHTTP                  = "http"
+	errorKindStreamingUnsupported  = "streaming_unsupported"
+	errorKindReadOnly              = "read_only"
+	errorKindInvalidConfig         = "invalid_config"
+	errorKindToolFailed            = "tool_failed"
+	errorKindInvalidRequest        = "invalid_request"
+	errorKindStorageUnavailable    = "storage_unavailable"
+	errorKindIO                    = "io"
+	errorKindInternal              = "internal"
+)
+
+// CommandError is what a bound method's error is rejected with in the
+// frontend: a stable kind plus the human-readable message.
+type CommandError struct {
+	Kind    string `json:"kind"`
+	Message string `json:"message"`
+}
+
+func (e CommandError) Error() string {
+	return e.Message
+}
+
+// formatError is the Wails error formatter for every bound method.
+func formatError(err error) any {
+	return newCommandError(err)
+}
+
+func newCommandError(err error) CommandError {
+	return CommandError{Kind: errorKind(err), Message: err.Error()}
+}
+
+func errorKind(err error) string {
+	var (
+		urlErr    *url.Error
+		netErr    net.Error
+		statusErr *llm.StatusError
+		pathErr   *fs.PathError
+	)
+	switch {
+	case errors.Is(err, llm.ErrMissingProviderConfig):
+		return errorKindMissingProviderConfig
+	case errors.Is(err, context.Canceled):
+		return errorKindCancelled
+	case errors.Is(err, context.DeadlineExceeded):
+		return errorKindTimeout
+	case errors.Is(err, llm.ErrStreamingUnsupported):
+		return errorKindStreamingUnsupported
+	case errors.Is(err, config.ErrReadOnly):
+		return errorKindReadOnly
+	case errors.Is(err, config.ErrInvalidConfig):
+		return errorKindInvalidConfig
+	case errors.Is(err, llm.ErrToolFailed):
+		return errorKindToolFailed
+	case errors.Is(err, llm.ErrInvalidRequest):
+		return errorKindInvalidRequest
+	case errors.Is(err, conversations.ErrUnavailable):
+		return errorKindStorageUnavailable
+	case errors.As(err, &urlErr), errors.As(err, &netErr), errors.As(err, &statusErr):
+		return errorKindHTTP
+	case errors.As(err, &pathErr):
+		return errorKindIO
+	default:
+		return errorKindInternal
+	}
+}
@@ -0,0 +1,45 @@
+package main
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"io/fs"
+	"net/url"
+	"testing"
+
+	"shell-werk/internal/config"
+	"shell-werk/internal/conversations"
+	"shell-werk/internal/llm"
+)
+
+func TestCommandErrorKinds(t *testing.T) {
+	cases := []struct {
+		kind string
+		err  error
+	}{
+		{errorKindMissingProviderConfig, fmt.Errorf("%w: openai requires an API key", llm.ErrMissingProviderConfig)},
+		{errorKindCancelled, context.Canceled},
+		{errorKindTimeout, context.DeadlineExceeded},
+		{errorKindHTTP, &url.Error{Op: "Get", URL: "http://localhost:1", Err: errors.New("connection refused")}},
+		{errorKindHTTP, fmt.Errorf("vllm returned %w: model not found", &llm.StatusError{StatusCode: 404, Status: "404 Not Found"})},
+		{errorKindStreamingUnsupported, llm.ErrStreamingUnsupported},
+		{errorKindReadOnly, fmt.Errorf("persist configuration: %w", config.ErrReadOnly)},
+		{errorKindInvalidConfig, fmt.Errorf("%w: ollama endpoint is missing a scheme", config.ErrInvalidConfig)},
+		{errorKindToolFailed, fmt.Errorf("%w: shell: command failed", llm.ErrToolFailed)},
+		{errorKindInvalidRequest, llm.ErrNothingToRegenerate},
+		{errorKindStorageUnavailable, conversations.ErrUnavailable},
+		{errorKindIO, &fs.PathError{Op: "open", Path: "missing.txt", Err: fs.ErrNotExist}},
+		{errorKindInternal, errors.New("something else")},
+	}
+	for _, tc := range cases {
+		wrapped := wrapProviderError("vllm", "http://localhost:8000", tc.err)
+		got := newCommandError(wrapped)
+		if got.Kind != tc.kind {
+			t.Errorf("kind for %v = %q, want %q", tc.err, got.Kind, tc.kind)
+		}
+		if got.Message != wrapped.Error() {
+			t.Errorf("message for %v = %q, want %q", tc.err, got.Message, wrapped.Error())
+		}
+	}
+}
@@ -2,6 +2,9 @@ package main
 
 import (
 	"strings"
+	"sync"
+
+	"shell-werk/internal/llm"
 
 	"github.com/wailsapp/wails/v2/pkg/runtime"
 )
@@ -11,22 +14,69 @@ const (
 	thinkingUpdateEvent = "thinking:update"
 	thinkingEndEvent    = "thinking:end"
 	answerUpdateEvent   = "answer:update"
+	answerDoneEvent     = "answer:done"
+	progressEvent       = "dialogue:progress"
+	usageEvent          = "answer:usage"
+	answerErrorEvent    = "answer:error"
+	toolCallEvent       = "tool:call"
+	heartbeatEvent      = "answer:heartbeat"
+	toolCallDeltaEvent  = "tool:call_delta"
+	toolOutputEvent     = "tool:output"
 	continuationRequestEvent  = "dialogue:continuation_request"
 	continuationResolvedEvent = "dialogue:continuation_resolved"
 )
 
+// eventRoute tells the frontend which stream, and which conversation tab, an
+// event belongs to.
+type eventRoute struct {
+	SessionID      string `json:"sessionId"`
+	ConversationID string `json:"conversationId,omitempty"`
+}
+
 type thinkingEvent struct {
-	SessionID string `json:"sessionId"`
-	Chunk     string `json:"chunk,omitempty"`
+	eventRoute
+	Chunk string `json:"chunk,omitempty"`
 }
 
 type answerEvent struct {
-	SessionID string `json:"sessionId"`
-	Chunk     string `json:"chunk"`
+	eventRoute
+	Chunk string `json:"chunk"`
+}
+
+type answerDonePayload struct {
+	eventRoute
+	FinishReason string `json:"finishReason"`
+}
+
+type progressPayload struct {
+	eventRoute
+	Iteration int    `json:"iteration"`
+	Message   string `json:"message"`
+}
+
+type toolCallPayload struct {
+	eventRoute
+	Name string `json:"name"`
+}
+
+type toolCallDeltaPayload struct {
+	eventRoute
+	Index             int    `json:"index"`
+	ArgumentsFragment string `json:"argumentsFragment"`
+}
+
+type answerErrorPayload struct {
+	eventRoute
+	Error string `json:"error"`
+}
+
+type usagePayload struct {
+	eventRoute
+	Usage llm.Usage `json:"usage"`
 }
 
 type continuationRequestPayload struct {
-	SessionID    string `json:"sessionId"`
+	eventRoute
 	RequestID    string `json:"requestId"`
 	Reason       string `json:"reason"`
 	Iteration    int    `json:"iteration,omitempty"`
@@ -38,7 +88,7 @@ type continuationRequestPayload struct {
 }
 
 type continuationResolvedPayload struct {
-	SessionID string `json:"sessionId"`
+	eventRoute
 	RequestID string `json:"requestId"`
 	Reason    string `json:"reason,omitempty"`
 	Decision  string `json:"decision"`
@@ -47,31 +97,97 @@ type continuationResolvedPayload struct {
 // appEventSink bridges streaming events to the Wails event bus.
 type appEventSink struct {
 	app *App
+	// publish replaces the Wails runtime as the event destination when set.
+	publish func(name string, payload any)
+
+	mu            sync.RWMutex
+	conversations map[string]string
+}
+
+// bindConversation tags events for sessionID with conversationID until the
+// returned function is called.
+func (e *appEventSink) bindConversation(sessionID, conversationID string) func() {
+	conversationID = strings.TrimSpace(conversationID)
+	if conversationID == "" {
+		return func() {}
+	}
+	e.mu.Lock()
+	if e.conversations == nil {
+		e.conversations = map[string]string{}
+	}
+	e.conversations[sessionID] = conversationID
+	e.mu.Unlock()
+
+	return func() {
+		e.mu.Lock()
+		if e.conversations[sessionID] == conversationID {
+			delete(e.conversations, sessionID)
+		}
+		e.mu.Unlock()
+	}
+}
+
+func (e *appEventSink) route(sessionID string) eventRoute {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+	return eventRoute{SessionID: sessionID, ConversationID: e.conversations[sessionID]}
 }
 
 func (e *appEventSink) ThinkingStart(sessionID string) {
-	e.emit(thinkingStartEvent, thinkingEvent{SessionID: sessionID})
+	e.emit(thinkingStartEvent, thinkingEvent{eventRoute: e.route(sessionID)})
 }
 
 func (e *appEventSink) ThinkingUpdate(sessionID, chunk string) {
 	if strings.TrimSpace(chunk) == "" {
 		return
 	}
-	e.emit(thinkingUpdateEvent, thinkingEvent{SessionID: sessionID, Chunk: chunk})
+	e.emit(thinkingUpdateEvent, thinkingEvent{eventRoute: e.route(sessionID), Chunk: chunk})
 }
 
 func (e *appEventSink) ThinkingEnd(sessionID string) {
-	e.emit(thinkingEndEvent, thinkingEvent{SessionID: sessionID})
+	e.emit(thinkingEndEvent, thinkingEvent{eventRoute: e.route(sessionID)})
 }
 
 func (e *appEventSink) AnswerUpdate(sessionID, chunk string) {
 	if chunk == "" {
 		return
 	}
-	e.emit(answerUpdateEvent, answerEvent{SessionID: sessionID, Chunk: chunk})
+	e.emit(answerUpdateEvent, answerEvent{eventRoute: e.route(sessionID), Chunk: chunk})
+}
+
+func (e *appEventSink) AnswerDone(sessionID, finishReason string) {
+	e.emit(answerDoneEvent, answerDonePayload{eventRoute: e.route(sessionID), FinishReason: finishReason})
+}
+
+func (e *appEventSink) Progress(sessionID string, iteration int, message string) {
+	e.emit(progressEvent, progressPayload{eventRoute: e.route(sessionID), Iteration: iteration, Message: message})
+}
+
+func (e *appEventSink) Heartbeat(sessionID string) {
+	e.emit(heartbeatEvent, thinkingEvent{eventRoute: e.route(sessionID)})
+}
+
+func (e *appEventSink) ToolCall(sessionID, name string) {
+	e.emit(toolCallEvent, toolCallPayload{eventRoute: e.route(sessionID), Name: name})
+}
+
+func (e *appEventSink) ToolCallDelta(sessionID string, index int, fragment string) {
+	e.emit(toolCallDeltaEvent, toolCallDeltaPayload{eventRoute: e.route(sessionID), Index: index, ArgumentsFragment: fragment})
+}
+
+func (e *appEventSink) ToolOutput(sessionID, chunk string) {
+	e.emit(toolOutputEvent, answerEvent{eventRoute: e.route(sessionID), Chunk: chunk})
+}
+
+func (e *appEventSink) Usage(sessionID string, usage llm.Usage) {
+	e.emit(usageEvent, usagePayload{eventRoute: e.route(sessionID), Usage: usage})
 }
 
 func (e *appEventSink) emit(name string, payload any) {
+	if e.publish != nil {
+		e.publish(name, payload)
+		return
+	}
 	if e.app == nil || e.app.ctx == nil {
 		return
 	}
@@ -0,0 +1,67 @@
+package main
+
+import (
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+
+	"shell-werk/internal/config"
+)
+
+func TestModelsPrettifiesOpaqueIDsButKeepsRawSelection(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		fmt.Fprint(w, `{"data":[{"id":"models/3f9a2c1e8b7d4f60a9e1c2d3"},{"id":"meta-llama/llama-3.1-8b-instruct"}]}`)
+	}))
+	defer server.Close()
+
+	app := NewApp()
+	if _, err := app.SaveLLMConfiguration(LLMConfiguration{Provider: "vllm", PrettifyModelLabels: true}); err != nil {
+		t.Fatalf("SaveLLMConfiguration returned error: %v", err)
+	}
+
+	resp, err := app.Models(ModelsRequest{Provider: "vllm", Endpoint: server.URL})
+	if err != nil {
+		t.Fatalf("Models returned error: %v", err)
+	}
+	if len(resp.Models) != 2 || resp.Models[0] != "models/3f9a2c1e8b7d4f60a9e1c2d3" {
+		t.Fatalf("expected raw ids for selection, got %+v", resp.Models)
+	}
+	if got := resp.Labels["models/3f9a2c1e8b7d4f60a9e1c2d3"]; got != "Model 3f9a2c1e" {
+		t.Fatalf("expected shortened label for the opaque id, got %q", got)
+	}
+	if got := resp.Labels["meta-llama/llama-3.1-8b-instruct"]; got != "Llama 3.1 8b Instruct" {
+		t.Fatalf("expected title-cased label, got %q", got)
+	}
+}
+
+func TestListModelsAllDefaultsToConfiguredProfiles(t *testing.T) {
+	healthy := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		fmt.Fprint(w, `{"models":[{"name":"llama3"}]}`)
+	}))
+	defer healthy.Close()
+	refused := httptest.NewServer(http.HandlerFunc(func(http.ResponseWriter, *http.Request) {}))
+	refusedURL := refused.URL
+	refused.Close()
+
+	app := NewApp()
+	if _, err := app.SaveLLMConfiguration(LLMConfiguration{
+		Provider:      "ollama",
+		Endpoint:      healthy.URL,
+		ActiveProfile: "local",
+		Profiles:      map[string]config.ProviderProfile{"gpu-box": {Provider: "vllm", Endpoint: refusedURL}},
+	}); err != nil {
+		t.Fatalf("SaveLLMConfiguration returned error: %v", err)
+	}
+
+	result := app.ListModelsAll(nil)
+	if models := result.Models["local"]; len(models) != 1 || models[0] != "llama3" {
+		t.Fatalf("expected the local profile's models, got %+v", result.Models)
+	}
+	if _, ok := result.Errors["gpu-box"]; !ok || len(result.Errors) != 1 {
+		t.Fatalf("expected one error for the unreachable profile, got %+v", result.Errors)
+	}
+	if cached, ok := app.models.Get("ollama", healthy.URL, ""); !ok || len(cached) != 1 {
+		t.Fatalf("expected the listing to warm the model cache, got %v", cached)
+	}
+}
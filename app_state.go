@@ -0,0 +1,48 @@
+package main
+
+import (
+	"errors"
+
+	"shell-werk/internal/llm"
+)
+
+// errDevToolsDisabled is returned by the state commands in release builds.
+var errDevToolsDisabled = errors.New("state dumps are only available in dev builds")
+
+// StateSnapshot is the in-memory state captured by DumpState: the
+// configuration and the cached model listings.
+type StateSnapshot struct {
+	Config     LLMConfiguration         `json:"config"`
+	ModelCache []llm.ModelCacheSnapshot `json:"modelCache"`
+}
+
+// DumpState captures the in-memory state so a developer can restore it later
+// with LoadState instead of restarting. Only dev builds allow it.
+func (a *App) DumpState() (StateSnapshot, error) {
+	if !llm.Features().DevTools {
+		return StateSnapshot{}, errDevToolsDisabled
+	}
+	return a.dumpState(), nil
+}
+
+// LoadState restores a snapshot taken by DumpState. The configuration is
+// saved as if the user had changed it. Only dev builds allow it.
+func (a *App) LoadState(snapshot StateSnapshot) error {
+	if !llm.Features().DevTools {
+		return errDevToolsDisabled
+	}
+	return a.loadState(snapshot)
+}
+
+func (a *App) dumpState() StateSnapshot {
+	return StateSnapshot{
+		Config:     a.config.Get(),
+		ModelCache: a.models.Snapshot(),
+	}
+}
+
+func (a *App) loadState(snapshot StateSnapshot) error {
+	a.models.Restore(snapshot.ModelCache)
+	_, err := a.config.Save(snapshot.Config)
+	return err
+}
@@ -0,0 +1,56 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"reflect"
+	"testing"
+
+	"shell-werk/internal/llm"
+)
+
+func TestDumpAndLoadStateRoundTrips(t *testing.T) {
+	source := NewApp()
+	if _, err := source.SaveLLMConfiguration(LLMConfiguration{
+		Provider:      "ollama",
+		Endpoint:      "http://localhost:11434",
+		SelectedModel: "qwen3:4b",
+		SystemPrompt:  "Be terse.",
+	}); err != nil {
+		t.Fatalf("SaveLLMConfiguration returned error: %v", err)
+	}
+	source.models.Put("ollama", "http://localhost:11434", "sk-local", []string{"qwen3:4b", "llama3"})
+
+	data, err := json.Marshal(source.dumpState())
+	if err != nil {
+		t.Fatalf("failed to encode state: %v", err)
+	}
+	var snapshot StateSnapshot
+	if err := json.Unmarshal(data, &snapshot); err != nil {
+		t.Fatalf("failed to decode state: %v", err)
+	}
+
+	target := NewApp()
+	if err := target.loadState(snapshot); err != nil {
+		t.Fatalf("loadState returned error: %v", err)
+	}
+	if got, want := target.GetLLMConfiguration(), source.GetLLMConfiguration(); !reflect.DeepEqual(got, want) {
+		t.Fatalf("expected config %+v, got %+v", want, got)
+	}
+	if models, ok := target.models.Get("ollama", "http://localhost:11434", "sk-local"); !ok || len(models) != 2 {
+		t.Fatalf("expected cached models to be restored, got %v (ok=%v)", models, ok)
+	}
+}
+
+func TestStateCommandsRequireDevBuild(t *testing.T) {
+	if llm.Features().DevTools {
+		t.Skip("dev build")
+	}
+	app := NewApp()
+	if _, err := app.DumpState(); !errors.Is(err, errDevToolsDisabled) {
+		t.Fatalf("expected DumpState to be disabled, got %v", err)
+	}
+	if err := app.LoadState(StateSnapshot{}); !errors.Is(err, errDevToolsDisabled) {
+		t.Fatalf("expected LoadState to be disabled, got %v", err)
+	}
+}
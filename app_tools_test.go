@@ -1,6 +1,10 @@
 package main
 
-import "testing"
+import (
+	"encoding/json"
+	"strings"
+	"testing"
+)
 
 func TestAppToolAPIs(t *testing.T) {
 	app := NewApp()
@@ -20,3 +24,28 @@ func TestAppToolAPIs(t *testing.T) {
 		}
 	}
 }
+
+func TestExportToolsSchemaIncludesEnabledCustomTools(t *testing.T) {
+	app := NewApp()
+	if _, err := app.SaveLLMConfiguration(LLMConfiguration{
+		Provider: "mock",
+		Tools:    []CustomTool{{Name: "mock_echo", Description: "Echo the text back.", Command: "echo {text}"}},
+	}); err != nil {
+		t.Fatalf("SaveLLMConfiguration returned error: %v", err)
+	}
+	if _, err := app.SetToolEnabled(SetToolEnabledRequest{ID: "browser", Enabled: false}); err != nil {
+		t.Fatalf("SetToolEnabled returned error: %v", err)
+	}
+
+	data, err := json.Marshal(app.ExportToolsSchema())
+	if err != nil {
+		t.Fatalf("failed to encode schema: %v", err)
+	}
+	schema := string(data)
+	if !strings.Contains(schema, `"type":"function","function":{"name":"mock_echo"`) {
+		t.Fatalf("expected the mock_echo function definition, got %s", schema)
+	}
+	if strings.Contains(schema, `"name":"browser"`) {
+		t.Fatalf("expected disabled tools to be left out, got %s", schema)
+	}
+}
@@ -51,11 +51,13 @@ const CONTINUATION_RESOLVED_EVENT = "dialogue:continuation_resolved";
 
 interface ThinkingEventPayload {
   sessionId: string;
+  conversationId?: string;
   chunk?: string;
 }
 
 interface AnswerEventPayload {
   sessionId: string;
+  conversationId?: string;
   chunk: string;
 }
 
@@ -497,7 +499,7 @@ function App() {
         updatedAt: new Date().toISOString(),
       }));
     } catch (err) {
-      alert("Error executing command: " + err);
+      alert("Error executing command: " + describeError(err));
     }
   };
 
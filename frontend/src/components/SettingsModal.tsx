@@ -141,6 +141,7 @@ export const SettingsModal: React.FC<SettingsModalProps> = ({
                         >
                           <option value="ollama">Ollama</option>
                           <option value="vllm">vLLM</option>
+                          <option value="openai">OpenAI</option>
                           <option value="mock">Mock</option>
                         </select>
                       </label>
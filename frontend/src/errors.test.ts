@@ -1,5 +1,10 @@
 import { describe, expect, it } from "vitest";
-import { describeError, formatProviderTarget } from "./errors";
+import {
+  describeError,
+  errorKind,
+  formatProviderTarget,
+  MISSING_PROVIDER_CONFIG,
+} from "./errors";
 
 describe("describeError", () => {
   it("returns message from Error instances", () => {
@@ -26,6 +31,19 @@ describe("describeError", () => {
   });
 });
 
+describe("errorKind", () => {
+  it("reads the kind of a backend command error", () => {
+    const err = { kind: MISSING_PROVIDER_CONFIG, message: "openai requires an API key" };
+    expect(errorKind(err)).toBe(MISSING_PROVIDER_CONFIG);
+    expect(describeError(err)).toBe("openai requires an API key");
+  });
+
+  it("returns undefined for plain errors", () => {
+    expect(errorKind("boom")).toBeUndefined();
+    expect(errorKind(new Error("boom"))).toBeUndefined();
+  });
+});
+
 describe("formatProviderTarget", () => {
   it("includes provider and endpoint when both are present", () => {
     expect(formatProviderTarget("ollama", "http://localhost:11434")).toBe(
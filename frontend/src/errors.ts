@@ -27,6 +27,17 @@ export function describeError(err: unknown, fallback = "Unknown error"): string
   return fallback;
 }
 
+// Kinds reported by the backend's CommandError; see app_errors.go.
+export const MISSING_PROVIDER_CONFIG = "missing_provider_config";
+
+export function errorKind(err: unknown): string | undefined {
+  if (err && typeof err === "object" && "kind" in err) {
+    const kind = (err as { kind?: unknown }).kind;
+    return typeof kind === "string" ? kind : undefined;
+  }
+  return undefined;
+}
+
 export function formatProviderTarget(
   provider?: string,
   endpoint?: string
@@ -3,21 +3,136 @@
 import {llm} from '../models';
 import {tools} from '../models';
 import {context} from '../models';
+import {config} from '../models';
+import {conversations} from '../models';
+import {main} from '../models';
+
+export function ActiveModel():Promise<string>;
+
+export function BatchComplete(arg1:Array<string>,arg2:string):Promise<Array<llm.BatchResult>>;
 
 export function CancelChat(arg1:string):Promise<boolean>;
 
+export function CancelRequest(arg1:string):Promise<boolean>;
+
+export function CancelStream(arg1:string):Promise<boolean>;
+
 export function Chat(arg1:llm.ChatRequest):Promise<llm.ChatResponse>;
 
+export function CheckProviderHealth(arg1:llm.ModelsRequest):Promise<llm.HealthStatus>;
+
+export function ClearAllConversations():Promise<void>;
+
+export function ClearModelCache():Promise<void>;
+
+export function ConfigStatus():Promise<config.Status>;
+
+export function ConversationHash(arg1:Array<llm.ChatMessage>):Promise<string>;
+
+export function ConversationStats(arg1:Array<llm.ChatMessage>):Promise<llm.ConversationStats>;
+
+export function DeleteConversation(arg1:string):Promise<void>;
+
+export function DetectDuplicateEndpoints(arg1:Array<llm.ModelsRequest>):Promise<Array<Array<string>>>;
+
+export function DumpState():Promise<main.StateSnapshot>;
+
+export function EvictCacheEntry(arg1:string):Promise<boolean>;
+
+export function ExportConversationJSONL(arg1:Array<llm.ChatMessage>):Promise<string>;
+
+export function ExportToolsSchema():Promise<Array<tools.ToolDefinition>>;
+
+export function FeatureFlags():Promise<llm.FeatureFlags>;
+
+export function GetEffectiveConfiguration():Promise<config.LLMConfiguration>;
+
+export function GetLLMConfiguration():Promise<config.LLMConfiguration>;
+
+export function GetLLMConfigurationRedacted():Promise<config.LLMConfiguration>;
+
+export function GetSystemPrompt():Promise<string>;
+
 export function GetTools():Promise<Array<tools.ToolMetadata>>;
 
 export function Greet(arg1:string):Promise<string>;
 
+export function LastTrace():Promise<llm.ExchangeTrace>;
+
+export function ListCacheEntries():Promise<Array<llm.CacheEntryMeta>>;
+
+export function ListConversations():Promise<Array<conversations.Meta>>;
+
+export function ListModelsAll(arg1:Array<llm.ModelsRequest>):Promise<llm.AllModels>;
+
+export function ListProfiles():Promise<Array<config.ProfileSummary>>;
+
+export function LoadConversation(arg1:string):Promise<conversations.Conversation>;
+
+export function LoadState(arg1:main.StateSnapshot):Promise<void>;
+
+export function ModelWarmState(arg1:string):Promise<Array<llm.ModelWarmInfo>>;
+
 export function Models(arg1:llm.ModelsRequest):Promise<llm.ModelsResponse>;
 
+export function NormalizeBaseURL(arg1:string,arg2:string):Promise<string>;
+
+export function NormalizeToolArguments(arg1:any):Promise<any>;
+
+export function ProviderAcceleration(arg1:string):Promise<string>;
+
+export function ProviderRequiresAuth(arg1:string):Promise<boolean>;
+
+export function ProviderVersion(arg1:llm.ModelsRequest):Promise<string>;
+
+export function Regenerate(arg1:llm.ChatRequest):Promise<llm.DialogueResponse>;
+
 export function RequestContinuation(arg1:context.Context,arg2:string,arg3:llm.ContinuationRequest):Promise<llm.ContinuationDecision>;
 
 export function ResolveContinuation(arg1:llm.ContinuationDecisionRequest):Promise<boolean>;
 
+export function RestartStream(arg1:string,arg2:llm.SamplingParams):Promise<string>;
+
+export function ResumeChat(arg1:llm.ResumeChatRequest):Promise<llm.ChatResponse>;
+
+export function ResumeConversation(arg1:string):Promise<conversations.Conversation>;
+
+export function RunDialogue(arg1:llm.ChatRequest):Promise<llm.DialogueResponse>;
+
 export function RunShellCommand(arg1:string,arg2:Array<string>,arg3:boolean):Promise<string>;
 
+export function SamplingPreview(arg1:llm.SamplingParams):Promise<llm.SamplingPreview>;
+
+export function SaveConversation(arg1:conversations.Conversation):Promise<conversations.Meta>;
+
+export function SaveLLMConfiguration(arg1:config.LLMConfiguration):Promise<config.LLMConfiguration>;
+
+export function SaveLLMConfigurationWithChanges(arg1:config.LLMConfiguration):Promise<config.SaveResult>;
+
+export function SelectProfile(arg1:string):Promise<config.LLMConfiguration>;
+
+export function SetSystemPrompt(arg1:string):Promise<config.LLMConfiguration>;
+
 export function SetToolEnabled(arg1:tools.SetToolEnabledRequest):Promise<Array<tools.ToolMetadata>>;
+
+export function StartStream(arg1:llm.ChatRequest):Promise<string>;
+
+export function StreamToFile(arg1:llm.ChatRequest,arg2:string):Promise<number>;
+
+export function SupportedSamplingParams(arg1:string):Promise<Array<string>>;
+
+export function SupportsStreaming(arg1:string,arg2:string):Promise<boolean>;
+
+export function ToolAuditLog():Promise<Array<llm.ToolAuditEntry>>;
+
+export function TranscriptPlaintext(arg1:Array<llm.ChatMessage>):Promise<string>;
+
+export function UpdateSelectedModel(arg1:string):Promise<config.LLMConfiguration>;
+
+export function ValidateAll():Promise<llm.ValidationReport>;
+
+export function ValidateConversationFile(arg1:string):Promise<conversations.Meta>;
+
+export function ValidateProviders(arg1:Array<llm.ModelsRequest>):Promise<Array<llm.ProviderValidation>>;
+
+export function WillFit(arg1:llm.FitRequest):Promise<llm.FitResult>;
@@ -2,14 +2,98 @@
 // Cynhyrchwyd y ffeil hon yn awtomatig. PEIDIWCH Â MODIWL
 // This file is automatically generated. DO NOT EDIT
 
+export function ActiveModel() {
+  return window['go']['main']['App']['ActiveModel']();
+}
+
+export function BatchComplete(arg1, arg2) {
+  return window['go']['main']['App']['BatchComplete'](arg1, arg2);
+}
+
 export function CancelChat(arg1) {
   return window['go']['main']['App']['CancelChat'](arg1);
 }
 
+export function CancelRequest(arg1) {
+  return window['go']['main']['App']['CancelRequest'](arg1);
+}
+
+export function CancelStream(arg1) {
+  return window['go']['main']['App']['CancelStream'](arg1);
+}
+
 export function Chat(arg1) {
   return window['go']['main']['App']['Chat'](arg1);
 }
 
+export function CheckProviderHealth(arg1) {
+  return window['go']['main']['App']['CheckProviderHealth'](arg1);
+}
+
+export function ClearAllConversations() {
+  return window['go']['main']['App']['ClearAllConversations']();
+}
+
+export function ClearModelCache() {
+  return window['go']['main']['App']['ClearModelCache']();
+}
+
+export function ConfigStatus() {
+  return window['go']['main']['App']['ConfigStatus']();
+}
+
+export function ConversationHash(arg1) {
+  return window['go']['main']['App']['ConversationHash'](arg1);
+}
+
+export function ConversationStats(arg1) {
+  return window['go']['main']['App']['ConversationStats'](arg1);
+}
+
+export function DeleteConversation(arg1) {
+  return window['go']['main']['App']['DeleteConversation'](arg1);
+}
+
+export function DetectDuplicateEndpoints(arg1) {
+  return window['go']['main']['App']['DetectDuplicateEndpoints'](arg1);
+}
+
+export function DumpState() {
+  return window['go']['main']['App']['DumpState']();
+}
+
+export function EvictCacheEntry(arg1) {
+  return window['go']['main']['App']['EvictCacheEntry'](arg1);
+}
+
+export function ExportConversationJSONL(arg1) {
+  return window['go']['main']['App']['ExportConversationJSONL'](arg1);
+}
+
+export function ExportToolsSchema() {
+  return window['go']['main']['App']['ExportToolsSchema']();
+}
+
+export function FeatureFlags() {
+  return window['go']['main']['App']['FeatureFlags']();
+}
+
+export function GetEffectiveConfiguration() {
+  return window['go']['main']['App']['GetEffectiveConfiguration']();
+}
+
+export function GetLLMConfiguration() {
+  return window['go']['main']['App']['GetLLMConfiguration']();
+}
+
+export function GetLLMConfigurationRedacted() {
+  return window['go']['main']['App']['GetLLMConfigurationRedacted']();
+}
+
+export function GetSystemPrompt() {
+  return window['go']['main']['App']['GetSystemPrompt']();
+}
+
 export function GetTools() {
   return window['go']['main']['App']['GetTools']();
 }
@@ -18,10 +102,66 @@ export function Greet(arg1) {
   return window['go']['main']['App']['Greet'](arg1);
 }
 
+export function LastTrace() {
+  return window['go']['main']['App']['LastTrace']();
+}
+
+export function ListCacheEntries() {
+  return window['go']['main']['App']['ListCacheEntries']();
+}
+
+export function ListConversations() {
+  return window['go']['main']['App']['ListConversations']();
+}
+
+export function ListModelsAll(arg1) {
+  return window['go']['main']['App']['ListModelsAll'](arg1);
+}
+
+export function ListProfiles() {
+  return window['go']['main']['App']['ListProfiles']();
+}
+
+export function LoadConversation(arg1) {
+  return window['go']['main']['App']['LoadConversation'](arg1);
+}
+
+export function LoadState(arg1) {
+  return window['go']['main']['App']['LoadState'](arg1);
+}
+
+export function ModelWarmState(arg1) {
+  return window['go']['main']['App']['ModelWarmState'](arg1);
+}
+
 export function Models(arg1) {
   return window['go']['main']['App']['Models'](arg1);
 }
 
+export function NormalizeBaseURL(arg1, arg2) {
+  return window['go']['main']['App']['NormalizeBaseURL'](arg1, arg2);
+}
+
+export function NormalizeToolArguments(arg1) {
+  return window['go']['main']['App']['NormalizeToolArguments'](arg1);
+}
+
+export function ProviderAcceleration(arg1) {
+  return window['go']['main']['App']['ProviderAcceleration'](arg1);
+}
+
+export function ProviderRequiresAuth(arg1) {
+  return window['go']['main']['App']['ProviderRequiresAuth'](arg1);
+}
+
+export function ProviderVersion(arg1) {
+  return window['go']['main']['App']['ProviderVersion'](arg1);
+}
+
+export function Regenerate(arg1) {
+  return window['go']['main']['App']['Regenerate'](arg1);
+}
+
 export function RequestContinuation(arg1, arg2, arg3) {
   return window['go']['main']['App']['RequestContinuation'](arg1, arg2, arg3);
 }
@@ -30,10 +170,94 @@ export function ResolveContinuation(arg1) {
   return window['go']['main']['App']['ResolveContinuation'](arg1);
 }
 
+export function RestartStream(arg1, arg2) {
+  return window['go']['main']['App']['RestartStream'](arg1, arg2);
+}
+
+export function ResumeChat(arg1) {
+  return window['go']['main']['App']['ResumeChat'](arg1);
+}
+
+export function ResumeConversation(arg1) {
+  return window['go']['main']['App']['ResumeConversation'](arg1);
+}
+
+export function RunDialogue(arg1) {
+  return window['go']['main']['App']['RunDialogue'](arg1);
+}
+
 export function RunShellCommand(arg1, arg2, arg3) {
   return window['go']['main']['App']['RunShellCommand'](arg1, arg2, arg3);
 }
 
+export function SamplingPreview(arg1) {
+  return window['go']['main']['App']['SamplingPreview'](arg1);
+}
+
+export function SaveConversation(arg1) {
+  return window['go']['main']['App']['SaveConversation'](arg1);
+}
+
+export function SaveLLMConfiguration(arg1) {
+  return window['go']['main']['App']['SaveLLMConfiguration'](arg1);
+}
+
+export function SaveLLMConfigurationWithChanges(arg1) {
+  return window['go']['main']['App']['SaveLLMConfigurationWithChanges'](arg1);
+}
+
+export function SelectProfile(arg1) {
+  return window['go']['main']['App']['SelectProfile'](arg1);
+}
+
+export function SetSystemPrompt(arg1) {
+  return window['go']['main']['App']['SetSystemPrompt'](arg1);
+}
+
 export function SetToolEnabled(arg1) {
   return window['go']['main']['App']['SetToolEnabled'](arg1);
 }
+
+export function StartStream(arg1) {
+  return window['go']['main']['App']['StartStream'](arg1);
+}
+
+export function StreamToFile(arg1, arg2) {
+  return window['go']['main']['App']['StreamToFile'](arg1, arg2);
+}
+
+export function SupportedSamplingParams(arg1) {
+  return window['go']['main']['App']['SupportedSamplingParams'](arg1);
+}
+
+export function SupportsStreaming(arg1, arg2) {
+  return window['go']['main']['App']['SupportsStreaming'](arg1, arg2);
+}
+
+export function ToolAuditLog() {
+  return window['go']['main']['App']['ToolAuditLog']();
+}
+
+export function TranscriptPlaintext(arg1) {
+  return window['go']['main']['App']['TranscriptPlaintext'](arg1);
+}
+
+export function UpdateSelectedModel(arg1) {
+  return window['go']['main']['App']['UpdateSelectedModel'](arg1);
+}
+
+export function ValidateAll() {
+  return window['go']['main']['App']['ValidateAll']();
+}
+
+export function ValidateConversationFile(arg1) {
+  return window['go']['main']['App']['ValidateConversationFile'](arg1);
+}
+
+export function ValidateProviders(arg1) {
+  return window['go']['main']['App']['ValidateProviders'](arg1);
+}
+
+export function WillFit(arg1) {
+  return window['go']['main']['App']['WillFit'](arg1);
+}
@@ -48,10 +48,34 @@ export namespace llm {
 		    return a;
 		}
 	}
+	export class Citation {
+	    marker: string;
+	    index: number;
+	    toolCallId?: string;
+	    tool?: string;
+	    source?: string;
+	
+	    static createFrom(source: any = {}) {
+	        return new Citation(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.marker = source["marker"];
+	        this.index = source["index"];
+	        this.toolCallId = source["toolCallId"];
+	        this.tool = source["tool"];
+	        this.source = source["source"];
+	    }
+	}
 	export class ChatMessage {
 	    role: string;
 	    content: string;
 	    tool_calls?: ToolCall[];
+	    tool_call_id?: string;
+	    finishReason?: string;
+	    usage?: Usage;
+	    citations?: Citation[];
 	
 	    static createFrom(source: any = {}) {
 	        return new ChatMessage(source);
@@ -62,6 +86,10 @@ export namespace llm {
 	        this.role = source["role"];
 	        this.content = source["content"];
 	        this.tool_calls = this.convertValues(source["tool_calls"], ToolCall);
+	        this.tool_call_id = source["tool_call_id"];
+	        this.finishReason = source["finishReason"];
+	        this.usage = this.convertValues(source["usage"], Usage);
+	        this.citations = this.convertValues(source["citations"], Citation);
 	    }
 	
 		convertValues(a: any, classs: any, asMap: boolean = false): any {
@@ -94,6 +122,14 @@ export namespace llm {
 	    chatOnly: boolean;
 	    webSearchApiKey: string;
 	    webSearchEndpoint: string;
+	    sampling: SamplingParams;
+	    systemPrompt?: string;
+	    includeShellContext?: boolean;
+	    attachments?: string[];
+	    parallelToolCalls?: boolean;
+	    conversationId?: string;
+	    idPrefix?: string;
+	    maxToolIterations?: number;
 	
 	    static createFrom(source: any = {}) {
 	        return new ChatRequest(source);
@@ -112,6 +148,14 @@ export namespace llm {
 	        this.chatOnly = source["chatOnly"];
 	        this.webSearchApiKey = source["webSearchApiKey"];
 	        this.webSearchEndpoint = source["webSearchEndpoint"];
+	        this.sampling = this.convertValues(source["sampling"], SamplingParams);
+	        this.systemPrompt = source["systemPrompt"];
+	        this.includeShellContext = source["includeShellContext"];
+	        this.attachments = source["attachments"];
+	        this.parallelToolCalls = source["parallelToolCalls"];
+	        this.conversationId = source["conversationId"];
+	        this.idPrefix = source["idPrefix"];
+	        this.maxToolIterations = source["maxToolIterations"];
 	    }
 	
 		convertValues(a: any, classs: any, asMap: boolean = false): any {
@@ -254,6 +298,9 @@ export namespace llm {
 	    provider: string;
 	    endpoint: string;
 	    apiKey: string;
+	    id?: string;
+	    requestId?: string;
+	    detailedLabels?: boolean;
 	
 	    static createFrom(source: any = {}) {
 	        return new ModelsRequest(source);
@@ -264,10 +311,14 @@ export namespace llm {
 	        this.provider = source["provider"];
 	        this.endpoint = source["endpoint"];
 	        this.apiKey = source["apiKey"];
+	        this.id = source["id"];
+	        this.requestId = source["requestId"];
+	        this.detailedLabels = source["detailedLabels"];
 	    }
 	}
 	export class ModelsResponse {
 	    models: string[];
+	    labels?: Record<string, string>;
 	
 	    static createFrom(source: any = {}) {
 	        return new ModelsResponse(source);
@@ -276,58 +327,199 @@ export namespace llm {
 	    constructor(source: any = {}) {
 	        if ('string' === typeof source) source = JSON.parse(source);
 	        this.models = source["models"];
+	        this.labels = source["labels"];
 	    }
 	}
+	export class SamplingParams {
+	    temperature?: number;
+	    topP?: number;
+	    seed?: number;
+	    numCtx?: number;
+	    stop?: string[];
 	
-
-}
-
-export namespace tools {
+	    static createFrom(source: any = {}) {
+	        return new SamplingParams(source);
+	    }
 	
-	export class SetToolEnabledRequest {
-	    id: string;
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.temperature = source["temperature"];
+	        this.topP = source["topP"];
+	        this.seed = source["seed"];
+	        this.numCtx = source["numCtx"];
+	        this.stop = source["stop"];
+	    }
+	}
+	export class FeatureFlags {
+	    mockProvider: boolean;
+	    ollamaProvider: boolean;
+	    vllmProvider: boolean;
+	    streaming: boolean;
+	    toolCalling: boolean;
+	    keyring: boolean;
+	    devTools: boolean;
+	
+	    static createFrom(source: any = {}) {
+	        return new FeatureFlags(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.mockProvider = source["mockProvider"];
+	        this.ollamaProvider = source["ollamaProvider"];
+	        this.vllmProvider = source["vllmProvider"];
+	        this.streaming = source["streaming"];
+	        this.toolCalling = source["toolCalling"];
+	        this.keyring = source["keyring"];
+	        this.devTools = source["devTools"];
+	    }
+	}
+	export class AllModels {
+	    models: Record<string, Array<string>>;
+	    errors: Record<string, string>;
+	
+	    static createFrom(source: any = {}) {
+	        return new AllModels(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.models = source["models"];
+	        this.errors = source["errors"];
+	    }
+	}
+	export class ConversationStats {
+	    messages: number;
+	    userMessages: number;
+	    assistantMessages: number;
+	    systemMessages: number;
+	    toolMessages: number;
+	    characters: number;
+	    words: number;
+	    estimatedTokens: number;
+	
+	    static createFrom(source: any = {}) {
+	        return new ConversationStats(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.messages = source["messages"];
+	        this.userMessages = source["userMessages"];
+	        this.assistantMessages = source["assistantMessages"];
+	        this.systemMessages = source["systemMessages"];
+	        this.toolMessages = source["toolMessages"];
+	        this.characters = source["characters"];
+	        this.words = source["words"];
+	        this.estimatedTokens = source["estimatedTokens"];
+	    }
+	}
+	export class ResumeChatRequest {
+	    request: ChatRequest;
+	    partial: string;
+	
+	    static createFrom(source: any = {}) {
+	        return new ResumeChatRequest(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.request = this.convertValues(source["request"], ChatRequest);
+	        this.partial = source["partial"];
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
+	}
+	export class RepetitionGuard {
 	    enabled: boolean;
+	    minWords?: number;
+	    maxRepeats?: number;
 	
 	    static createFrom(source: any = {}) {
-	        return new SetToolEnabledRequest(source);
+	        return new RepetitionGuard(source);
 	    }
 	
 	    constructor(source: any = {}) {
 	        if ('string' === typeof source) source = JSON.parse(source);
-	        this.id = source["id"];
 	        this.enabled = source["enabled"];
+	        this.minWords = source["minWords"];
+	        this.maxRepeats = source["maxRepeats"];
 	    }
 	}
-	export class ToolFunctionDef {
-	    name: string;
-	    description: string;
-	    parameters: Record<string, any>;
-	    strict?: boolean;
+	export class ExchangeRecord {
+	    provider: string;
+	    model: string;
+	    url: string;
+	    request: Record<string, any>;
+	    status?: number;
+	    response?: string;
+	    error?: string;
+	    startedAt: any;
+	    durationMs: number;
 	
 	    static createFrom(source: any = {}) {
-	        return new ToolFunctionDef(source);
+	        return new ExchangeRecord(source);
 	    }
 	
 	    constructor(source: any = {}) {
 	        if ('string' === typeof source) source = JSON.parse(source);
-	        this.name = source["name"];
-	        this.description = source["description"];
-	        this.parameters = source["parameters"];
-	        this.strict = source["strict"];
+	        this.provider = source["provider"];
+	        this.model = source["model"];
+	        this.url = source["url"];
+	        this.request = source["request"];
+	        this.status = source["status"];
+	        this.response = source["response"];
+	        this.error = source["error"];
+	        this.startedAt = this.convertValues(source["startedAt"], null);
+	        this.durationMs = source["durationMs"];
 	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
 	}
-	export class ToolDefinition {
-	    type: string;
-	    function: ToolFunctionDef;
+	export class ExchangeTrace {
+	    sessionId: string;
+	    exchanges: ExchangeRecord[];
 	
 	    static createFrom(source: any = {}) {
-	        return new ToolDefinition(source);
+	        return new ExchangeTrace(source);
 	    }
 	
 	    constructor(source: any = {}) {
 	        if ('string' === typeof source) source = JSON.parse(source);
-	        this.type = source["type"];
-	        this.function = this.convertValues(source["function"], ToolFunctionDef);
+	        this.sessionId = source["sessionId"];
+	        this.exchanges = this.convertValues(source["exchanges"], ExchangeRecord);
 	    }
 	
 		convertValues(a: any, classs: any, asMap: boolean = false): any {
@@ -348,26 +540,848 @@ export namespace tools {
 		    return a;
 		}
 	}
-	
-	export class ToolMetadata {
+	export class ProviderValidation {
 	    id: string;
-	    name: string;
-	    description: string;
-	    uiVisible: boolean;
-	    enabled: boolean;
+	    provider: string;
+	    endpoint: string;
+	    validUrl: boolean;
+	    hasApiKey: boolean;
+	    warnings?: string[];
+	    error?: string;
 	
 	    static createFrom(source: any = {}) {
-	        return new ToolMetadata(source);
+	        return new ProviderValidation(source);
 	    }
 	
 	    constructor(source: any = {}) {
 	        if ('string' === typeof source) source = JSON.parse(source);
 	        this.id = source["id"];
-	        this.name = source["name"];
-	        this.description = source["description"];
-	        this.uiVisible = source["uiVisible"];
-	        this.enabled = source["enabled"];
+	        this.provider = source["provider"];
+	        this.endpoint = source["endpoint"];
+	        this.validUrl = source["validUrl"];
+	        this.hasApiKey = source["hasApiKey"];
+	        this.warnings = source["warnings"];
+	        this.error = source["error"];
+	    }
+	}
+	export class ProviderGroup {
+	    provider: string;
+	    endpoints: string[];
+	    strategy?: string;
+	    weights?: number[];
+	    cooldownSeconds?: number;
+	
+	    static createFrom(source: any = {}) {
+	        return new ProviderGroup(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.provider = source["provider"];
+	        this.endpoints = source["endpoints"];
+	        this.strategy = source["strategy"];
+	        this.weights = source["weights"];
+	        this.cooldownSeconds = source["cooldownSeconds"];
+	    }
+	}
+	export class SamplingPreview {
+	    params: SamplingParams;
+	    adjustments: string[];
+	
+	    static createFrom(source: any = {}) {
+	        return new SamplingPreview(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.params = this.convertValues(source["params"], SamplingParams);
+	        this.adjustments = source["adjustments"];
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
+	}
+	export class CacheEntryMeta {
+	    key: string;
+	    provider: string;
+	    endpoint: string;
+	    ageSeconds: number;
+	    modelCount: number;
+	
+	    static createFrom(source: any = {}) {
+	        return new CacheEntryMeta(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.key = source["key"];
+	        this.provider = source["provider"];
+	        this.endpoint = source["endpoint"];
+	        this.ageSeconds = source["ageSeconds"];
+	        this.modelCount = source["modelCount"];
+	    }
+	}
+	export class DialogueResponse {
+	    message: ChatMessage;
+	    messages: ChatMessage[];
+	    latencyMs: number;
+	    trace: DialogueTrace[];
+	    usage?: Usage;
+	
+	    static createFrom(source: any = {}) {
+	        return new DialogueResponse(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.message = this.convertValues(source["message"], ChatMessage);
+	        this.messages = this.convertValues(source["messages"], ChatMessage);
+	        this.latencyMs = source["latencyMs"];
+	        this.trace = this.convertValues(source["trace"], DialogueTrace);
+	        this.usage = this.convertValues(source["usage"], Usage);
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
+	}
+	export class FitRequest {
+	    provider: string;
+	    endpoint: string;
+	    apiKey: string;
+	    model: string;
+	    history: ChatMessage[];
+	    message: string;
+	    contextLength?: number;
+	    reserve?: number;
+	
+	    static createFrom(source: any = {}) {
+	        return new FitRequest(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.provider = source["provider"];
+	        this.endpoint = source["endpoint"];
+	        this.apiKey = source["apiKey"];
+	        this.model = source["model"];
+	        this.history = this.convertValues(source["history"], ChatMessage);
+	        this.message = source["message"];
+	        this.contextLength = source["contextLength"];
+	        this.reserve = source["reserve"];
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
+	}
+	export class FitResult {
+	    fits: boolean;
+	    estimatedTokens: number;
+	    reserve: number;
+	    contextLength: number;
+	    margin: number;
+	
+	    static createFrom(source: any = {}) {
+	        return new FitResult(source);
 	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.fits = source["fits"];
+	        this.estimatedTokens = source["estimatedTokens"];
+	        this.reserve = source["reserve"];
+	        this.contextLength = source["contextLength"];
+	        this.margin = source["margin"];
+	    }
+	}
+	export class ModelCacheSnapshot {
+	    provider: string;
+	    endpoint: string;
+	    keyHash?: string;
+	    models: string[];
+	    storedAt: any;
+	
+	    static createFrom(source: any = {}) {
+	        return new ModelCacheSnapshot(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.provider = source["provider"];
+	        this.endpoint = source["endpoint"];
+	        this.keyHash = source["keyHash"];
+	        this.models = source["models"];
+	        this.storedAt = source["storedAt"];
+	    }
+	}
+	export class Usage {
+	    promptTokens: number;
+	    completionTokens: number;
+	    estimated: boolean;
+	
+	    static createFrom(source: any = {}) {
+	        return new Usage(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.promptTokens = source["promptTokens"];
+	        this.completionTokens = source["completionTokens"];
+	        this.estimated = source["estimated"];
+	    }
+	}
+	export class HealthStatus {
+	    reachable: boolean;
+	    latencyMs: number;
+	    detail?: string;
+	
+	    static createFrom(source: any = {}) {
+	        return new HealthStatus(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.reachable = source["reachable"];
+	        this.latencyMs = source["latencyMs"];
+	        this.detail = source["detail"];
+	    }
+	}
+	export class ProviderDiagnostics {
+	    id: string;
+	    provider: string;
+	    endpoint: string;
+	    health: HealthStatus;
+	    models?: string[];
+	    modelsError?: string;
+	    model?: string;
+	    modelError?: string;
+	    ok: boolean;
+	
+	    static createFrom(source: any = {}) {
+	        return new ProviderDiagnostics(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.id = source["id"];
+	        this.provider = source["provider"];
+	        this.endpoint = source["endpoint"];
+	        this.health = this.convertValues(source["health"], HealthStatus);
+	        this.models = source["models"];
+	        this.modelsError = source["modelsError"];
+	        this.model = source["model"];
+	        this.modelError = source["modelError"];
+	        this.ok = source["ok"];
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
+	}
+	export class ValidationReport {
+	    providers: ProviderDiagnostics[];
+	    ok: boolean;
+	
+	    static createFrom(source: any = {}) {
+	        return new ValidationReport(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.providers = this.convertValues(source["providers"], ProviderDiagnostics);
+	        this.ok = source["ok"];
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
+	}
+	export class ModelWarmInfo {
+	    model: string;
+	    state: string;
+	    sizeVram?: number;
+	    expiresAt?: any;
+	
+	    static createFrom(source: any = {}) {
+	        return new ModelWarmInfo(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.model = source["model"];
+	        this.state = source["state"];
+	        this.sizeVram = source["sizeVram"];
+	        this.expiresAt = source["expiresAt"];
+	    }
+	}
+	export class ToolAuditEntry {
+	    tool: string;
+	    arguments: string;
+	    status: string;
+	    result: string;
+	    // Go type: time
+	    time: any;
+	
+	    static createFrom(source: any = {}) {
+	        return new ToolAuditEntry(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.tool = source["tool"];
+	        this.arguments = source["arguments"];
+	        this.status = source["status"];
+	        this.result = source["result"];
+	        this.time = this.convertValues(source["time"], null);
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
+	}
+	export class BatchResult {
+	    prompt: string;
+	    answer?: string;
+	    error?: string;
+	
+	    static createFrom(source: any = {}) {
+	        return new BatchResult(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.prompt = source["prompt"];
+	        this.answer = source["answer"];
+	        this.error = source["error"];
+	    }
+	}
+	
+
+}
+
+export namespace tools {
+	
+	export class SetToolEnabledRequest {
+	    id: string;
+	    enabled: boolean;
+	
+	    static createFrom(source: any = {}) {
+	        return new SetToolEnabledRequest(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.id = source["id"];
+	        this.enabled = source["enabled"];
+	    }
+	}
+	export class ToolFunctionDef {
+	    name: string;
+	    description: string;
+	    parameters: Record<string, any>;
+	    strict?: boolean;
+	
+	    static createFrom(source: any = {}) {
+	        return new ToolFunctionDef(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.name = source["name"];
+	        this.description = source["description"];
+	        this.parameters = source["parameters"];
+	        this.strict = source["strict"];
+	    }
+	}
+	export class ToolDefinition {
+	    type: string;
+	    function: ToolFunctionDef;
+	
+	    static createFrom(source: any = {}) {
+	        return new ToolDefinition(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.type = source["type"];
+	        this.function = this.convertValues(source["function"], ToolFunctionDef);
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
+	}
+	
+	export class ToolMetadata {
+	    id: string;
+	    name: string;
+	    description: string;
+	    uiVisible: boolean;
+	    enabled: boolean;
+	    custom?: boolean;
+	
+	    static createFrom(source: any = {}) {
+	        return new ToolMetadata(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.id = source["id"];
+	        this.name = source["name"];
+	        this.description = source["description"];
+	        this.uiVisible = source["uiVisible"];
+	        this.enabled = source["enabled"];
+	        this.custom = source["custom"];
+	    }
+	}
+	export class CustomTool {
+	    name: string;
+	    description: string;
+	    parameters?: Record<string, any>;
+	    command: string;
+	    allowOptions?: boolean;
+	
+	    static createFrom(source: any = {}) {
+	        return new CustomTool(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.name = source["name"];
+	        this.description = source["description"];
+	        this.parameters = source["parameters"];
+	        this.command = source["command"];
+	        this.allowOptions = source["allowOptions"];
+	    }
+	}
+
+}
+
+export namespace config {
+	
+	export class LLMConfiguration {
+	    provider: string;
+	    endpoint: string;
+	    apiKey?: string;
+	    selectedModel?: string;
+	    requestIdStyle?: string;
+	    streamFallback?: boolean;
+	    parallelTools?: boolean;
+	    toolFallback?: boolean;
+	    repetitionGuard?: llm.RepetitionGuard;
+	    sampling: llm.SamplingParams;
+	    providerSampling?: Record<string, llm.SamplingParams>;
+	    toolRoot?: string;
+	    minDeltaChars?: number;
+	    toolEnv?: Record<string, string>;
+	    toolCwd?: string;
+	    sanitizeToolEnv?: boolean;
+	    parallelToolCalls?: boolean;
+	    providerGroup?: llm.ProviderGroup;
+	    maxConversationMessages?: number;
+	    hardMaxCompletionTokens?: number;
+	    providerTimeouts?: Record<string, number>;
+	    systemPrompt?: string;
+	    requestAttempts?: number;
+	    profiles?: Record<string, ProviderProfile>;
+	    activeProfile?: string;
+	    prettifyModelLabels?: boolean;
+	    keyringBacked?: boolean;
+	    tools?: tools.CustomTool[];
+	    maxToolIterations?: number;
+	    stripAnsi?: boolean;
+	    failFastOnToolError?: boolean;
+	    logLevel?: string;
+	    extractCitations?: boolean;
+	    mergeConsecutiveAssistant?: boolean;
+	
+	    static createFrom(source: any = {}) {
+	        return new LLMConfiguration(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.provider = source["provider"];
+	        this.endpoint = source["endpoint"];
+	        this.apiKey = source["apiKey"];
+	        this.selectedModel = source["selectedModel"];
+	        this.requestIdStyle = source["requestIdStyle"];
+	        this.streamFallback = source["streamFallback"];
+	        this.parallelTools = source["parallelTools"];
+	        this.toolFallback = source["toolFallback"];
+	        this.repetitionGuard = this.convertValues(source["repetitionGuard"], llm.RepetitionGuard);
+	        this.sampling = this.convertValues(source["sampling"], llm.SamplingParams);
+	        this.providerSampling = this.convertValues(source["providerSampling"], llm.SamplingParams, true);
+	        this.toolRoot = source["toolRoot"];
+	        this.minDeltaChars = source["minDeltaChars"];
+	        this.toolEnv = source["toolEnv"];
+	        this.toolCwd = source["toolCwd"];
+	        this.sanitizeToolEnv = source["sanitizeToolEnv"];
+	        this.parallelToolCalls = source["parallelToolCalls"];
+	        this.providerGroup = this.convertValues(source["providerGroup"], llm.ProviderGroup);
+	        this.maxConversationMessages = source["maxConversationMessages"];
+	        this.hardMaxCompletionTokens = source["hardMaxCompletionTokens"];
+	        this.providerTimeouts = source["providerTimeouts"];
+	        this.systemPrompt = source["systemPrompt"];
+	        this.requestAttempts = source["requestAttempts"];
+	        this.profiles = this.convertValues(source["profiles"], ProviderProfile, true);
+	        this.activeProfile = source["activeProfile"];
+	        this.prettifyModelLabels = source["prettifyModelLabels"];
+	        this.keyringBacked = source["keyringBacked"];
+	        this.tools = this.convertValues(source["tools"], tools.CustomTool);
+	        this.maxToolIterations = source["maxToolIterations"];
+	        this.stripAnsi = source["stripAnsi"];
+	        this.failFastOnToolError = source["failFastOnToolError"];
+	        this.logLevel = source["logLevel"];
+	        this.extractCitations = source["extractCitations"];
+	        this.mergeConsecutiveAssistant = source["mergeConsecutiveAssistant"];
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
+	}
+	export class Status {
+	    path: string;
+	    readOnly: boolean;
+	    warning?: string;
+	
+	    static createFrom(source: any = {}) {
+	        return new Status(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.path = source["path"];
+	        this.readOnly = source["readOnly"];
+	        this.warning = source["warning"];
+	    }
+	}
+	export class ProviderProfile {
+	    provider: string;
+	    endpoint: string;
+	    apiKey?: string;
+	
+	    static createFrom(source: any = {}) {
+	        return new ProviderProfile(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.provider = source["provider"];
+	        this.endpoint = source["endpoint"];
+	        this.apiKey = source["apiKey"];
+	    }
+	}
+	export class ProfileSummary {
+	    name: string;
+	    provider: string;
+	    endpoint: string;
+	    hasApiKey: boolean;
+	    active: boolean;
+	
+	    static createFrom(source: any = {}) {
+	        return new ProfileSummary(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.name = source["name"];
+	        this.provider = source["provider"];
+	        this.endpoint = source["endpoint"];
+	        this.hasApiKey = source["hasApiKey"];
+	        this.active = source["active"];
+	    }
+	}
+	export class FieldChange {
+	    field: string;
+	    submitted: string;
+	    normalized: string;
+	
+	    static createFrom(source: any = {}) {
+	        return new FieldChange(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.field = source["field"];
+	        this.submitted = source["submitted"];
+	        this.normalized = source["normalized"];
+	    }
+	}
+	export class SaveResult {
+	    config: LLMConfiguration;
+	    normalized: boolean;
+	    changes?: FieldChange[];
+	
+	    static createFrom(source: any = {}) {
+	        return new SaveResult(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.config = this.convertValues(source["config"], LLMConfiguration);
+	        this.normalized = source["normalized"];
+	        this.changes = this.convertValues(source["changes"], FieldChange);
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
+	}
+
+}
+
+export namespace conversations {
+	
+	export class Conversation {
+	    id: string;
+	    title: string;
+	    createdAt: any;
+	    updatedAt: any;
+	    provider?: string;
+	    model?: string;
+	    messages: llm.ChatMessage[];
+	
+	    static createFrom(source: any = {}) {
+	        return new Conversation(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.id = source["id"];
+	        this.title = source["title"];
+	        this.createdAt = this.convertValues(source["createdAt"], null);
+	        this.updatedAt = this.convertValues(source["updatedAt"], null);
+	        this.provider = source["provider"];
+	        this.model = source["model"];
+	        this.messages = this.convertValues(source["messages"], llm.ChatMessage);
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
+	}
+	export class Meta {
+	    id: string;
+	    title: string;
+	    createdAt: any;
+	    updatedAt: any;
+	    provider?: string;
+	    model?: string;
+	    messageCount: number;
+	
+	    static createFrom(source: any = {}) {
+	        return new Meta(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.id = source["id"];
+	        this.title = source["title"];
+	        this.createdAt = this.convertValues(source["createdAt"], null);
+	        this.updatedAt = this.convertValues(source["updatedAt"], null);
+	        this.provider = source["provider"];
+	        this.model = source["model"];
+	        this.messageCount = source["messageCount"];
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
+	}
+
+}
+
+export namespace main {
+	
+	export class StateSnapshot {
+	    config: config.LLMConfiguration;
+	    modelCache: llm.ModelCacheSnapshot[];
+	
+	    static createFrom(source: any = {}) {
+	        return new StateSnapshot(source);
+	    }
+	
+	    constructor(source: any = {}) {
+	        if ('string' === typeof source) source = JSON.parse(source);
+	        this.config = this.convertValues(source["config"], config.LLMConfiguration);
+	        this.modelCache = this.convertValues(source["modelCache"], llm.ModelCacheSnapshot);
+	    }
+	
+		convertValues(a: any, classs: any, asMap: boolean = false): any {
+		    if (!a) {
+		        return a;
+		    }
+		    if (a.slice && a.map) {
+		        return (a as any[]).map(elem => this.convertValues(elem, classs));
+		    } else if ("object" === typeof a) {
+		        if (asMap) {
+		            for (const key of Object.keys(a)) {
+		                a[key] = new classs(a[key]);
+		            }
+		            return a;
+		        }
+		        return new classs(a);
+		    }
+		    return a;
+		}
 	}
 
 }
@@ -0,0 +1,69 @@
+package config
+
+import (
+	"sort"
+	"strings"
+)
+
+// FieldChange is one value that Normalize rewrote, so the settings form can
+// show what was actually stored.
+type FieldChange struct {
+	Field      string `json:"field"`
+	Submitted  string `json:"submitted"`
+	Normalized string `json:"normalized"`
+}
+
+// SaveResult is the stored configuration together with the changes
+// normalization made to the submitted one.
+type SaveResult struct {
+	Config LLMConfiguration `json:"config"`
+	// Normalized is true when Changes is not empty.
+	Normalized bool          `json:"normalized"`
+	Changes    []FieldChange `json:"changes,omitempty"`
+}
+
+// NormalizationChanges lists the connection and text fields that differ
+// between a submitted configuration and its normalized form, profiles
+// included. API key values are masked.
+func NormalizationChanges(submitted, normalized LLMConfiguration) []FieldChange {
+	var changes []FieldChange
+	add := func(field, before, after string) {
+		if before != after {
+			changes = append(changes, FieldChange{Field: field, Submitted: before, Normalized: after})
+		}
+	}
+
+	add("provider", submitted.Provider, normalized.Provider)
+	add("endpoint", submitted.Endpoint, normalized.Endpoint)
+	if submitted.APIKey != normalized.APIKey {
+		changes = append(changes, FieldChange{Field: "apiKey", Submitted: maskKey(submitted.APIKey), Normalized: maskKey(normalized.APIKey)})
+	}
+	add("selectedModel", submitted.SelectedModel, normalized.SelectedModel)
+	add("systemPrompt", submitted.SystemPrompt, normalized.SystemPrompt)
+	add("requestIdStyle", submitted.RequestIDStyle, normalized.RequestIDStyle)
+	add("toolRoot", submitted.ToolRoot, normalized.ToolRoot)
+	add("toolCwd", submitted.ToolCwd, normalized.ToolCwd)
+
+	names := make([]string, 0, len(submitted.Profiles))
+	for name := range submitted.Profiles {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	for _, name := range names {
+		after, ok := normalized.Profiles[strings.TrimSpace(name)]
+		if !ok {
+			continue
+		}
+		before := submitted.Profiles[name]
+		add("profiles."+name+".provider", before.Provider, after.Provider)
+		add("profiles."+name+".endpoint", before.Endpoint, after.Endpoint)
+	}
+	return changes
+}
+
+func maskKey(key string) string {
+	if key == "" {
+		return ""
+	}
+	return maskedKey
+}
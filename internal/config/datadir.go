@@ -0,0 +1,45 @@
+package config
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"runtime"
+	"strings"
+)
+
+// DataPathEnv overrides the directory for conversations and caches.
+const DataPathEnv = "SHELLWERK_DATA_PATH"
+
+// DataDir resolves where conversations and caches live. It is kept separate
+// from the configuration directory and follows each OS's convention for
+// application data: XDG_DATA_HOME on Linux, Application Support on macOS and
+// LocalAppData on Windows.
+func DataDir() (string, error) {
+	if override := strings.TrimSpace(os.Getenv(DataPathEnv)); override != "" {
+		return override, nil
+	}
+
+	switch runtime.GOOS {
+	case "windows":
+		if dir := os.Getenv("LocalAppData"); dir != "" {
+			return filepath.Join(dir, "shell-werk", "data"), nil
+		}
+	case "darwin":
+		home, err := os.UserHomeDir()
+		if err != nil {
+			return "", fmt.Errorf("resolve data dir: %w", err)
+		}
+		return filepath.Join(home, "Library", "Application Support", "shell-werk"), nil
+	default:
+		if dir := os.Getenv("XDG_DATA_HOME"); dir != "" {
+			return filepath.Join(dir, "shell-werk"), nil
+		}
+	}
+
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return "", fmt.Errorf("resolve data dir: %w", err)
+	}
+	return filepath.Join(home, ".local", "share", "shell-werk"), nil
+}
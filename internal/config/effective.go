@@ -0,0 +1,71 @@
+package config
+
+import (
+	"os"
+	"regexp"
+	"strings"
+)
+
+// Environment variables that override the stored configuration at runtime.
+const (
+	ProviderEnv = "SHELLWERK_PROVIDER"
+	EndpointEnv = "SHELLWERK_ENDPOINT"
+	APIKeyEnv   = "SHELLWERK_API_KEY"
+	ModelEnv    = "SHELLWERK_MODEL"
+)
+
+const maskedKey = "********"
+
+// envReference matches the ${NAME} form of an environment reference.
+var envReference = regexp.MustCompile(`\$\{([A-Za-z_][A-Za-z0-9_]*)\}`)
+
+// expandEnv replaces ${NAME} references with the variable's value. Unlike
+// os.ExpandEnv it leaves bare $NAME and any other "$" untouched, since keys
+// and URLs may contain them.
+func expandEnv(value string) string {
+	return envReference.ReplaceAllStringFunc(value, func(ref string) string {
+		return os.Getenv(ref[2 : len(ref)-1])
+	})
+}
+
+// Effective returns the configuration that is actually used: ${VAR}
+// references in the endpoint and API key are expanded, then any
+// SHELLWERK_* environment overrides are applied.
+func (c LLMConfiguration) Effective() LLMConfiguration {
+	c.Endpoint = expandEnv(c.Endpoint)
+	c.APIKey = expandEnv(c.APIKey)
+
+	overrides := []struct {
+		env    string
+		target *string
+	}{
+		{ProviderEnv, &c.Provider},
+		{EndpointEnv, &c.Endpoint},
+		{APIKeyEnv, &c.APIKey},
+		{ModelEnv, &c.SelectedModel},
+	}
+	for _, override := range overrides {
+		if value := strings.TrimSpace(os.Getenv(override.env)); value != "" {
+			*override.target = value
+		}
+	}
+	return c.Normalize()
+}
+
+// Masked hides the API key while keeping whether one is set visible.
+func (c LLMConfiguration) Masked() LLMConfiguration {
+	if c.APIKey != "" {
+		c.APIKey = maskedKey
+	}
+	if len(c.Profiles) > 0 {
+		masked := make(map[string]ProviderProfile, len(c.Profiles))
+		for name, profile := range c.Profiles {
+			if profile.APIKey != "" {
+				profile.APIKey = maskedKey
+			}
+			masked[name] = profile
+		}
+		c.Profiles = masked
+	}
+	return c
+}
@@ -0,0 +1,118 @@
+package config
+
+import (
+	"errors"
+	"fmt"
+	"log"
+	"strings"
+)
+
+// keyringSentinel replaces an API key in the saved file when the key itself
+// lives in the OS keyring.
+const keyringSentinel = "@keyring"
+
+const keyringAccount = "api-key"
+
+// ErrKeyringUnavailable is returned when this build or platform has no keyring.
+var ErrKeyringUnavailable = errors.New("keyring unavailable")
+
+// Keyring is the platform secret store used for API keys.
+type Keyring interface {
+	Get(service, account string) (string, error)
+	Set(service, account, secret string) error
+	Delete(service, account string) error
+}
+
+// keyringService names the keyring entry for a profile, so every provider
+// connection keeps its own key.
+func keyringService(profile string) string {
+	return "shell-werk/" + profile
+}
+
+// UsesKeyring reports whether API keys should be kept out of the saved file.
+func (c LLMConfiguration) UsesKeyring() bool {
+	return c.KeyringBacked == nil || *c.KeyringBacked
+}
+
+// storeKeys moves API keys into the keyring and returns the configuration to
+// write, with sentinels in their place. Keys that cannot be stored stay in
+// plaintext so saving never loses them; ${VAR} references are left as they are.
+func (s *Store) storeKeys(cfg LLMConfiguration) LLMConfiguration {
+	if s.keyring == nil || !cfg.UsesKeyring() {
+		return cfg
+	}
+	profiles := make(map[string]ProviderProfile, len(cfg.Profiles))
+	for name, profile := range cfg.Profiles {
+		if key := profile.APIKey; key != "" && key != keyringSentinel && !strings.Contains(key, "$") {
+			if err := s.keyring.Set(keyringService(name), keyringAccount, key); err != nil {
+				log.Printf("keeping API key for profile %q in the configuration file: %v", name, err)
+			} else {
+				profile.APIKey = keyringSentinel
+			}
+		}
+		profiles[name] = profile
+	}
+	cfg.Profiles = profiles
+	if profiles[cfg.ActiveProfile].APIKey == keyringSentinel {
+		cfg.APIKey = keyringSentinel
+	}
+	return cfg
+}
+
+// forgetKeys deletes the keyring entries of profiles that were removed or
+// lost their API key, so deleted connections leave no secret behind.
+func (s *Store) forgetKeys(previous, next LLMConfiguration) {
+	if s.keyring == nil {
+		return
+	}
+	for name, profile := range previous.Profiles {
+		if key := profile.APIKey; key == "" || strings.Contains(key, "$") {
+			continue
+		}
+		if current, ok := next.Profiles[name]; ok && current.APIKey != "" {
+			continue
+		}
+		if err := s.keyring.Delete(keyringService(name), keyringAccount); err != nil {
+			log.Printf("API key for profile %q could not be deleted from the keyring: %v", name, err)
+		}
+	}
+}
+
+// loadKeys replaces sentinels with the keys stored in the keyring. Keys that
+// cannot be read are left empty and reported through the store warning.
+func (s *Store) loadKeys(cfg *LLMConfiguration) {
+	for name, profile := range cfg.Profiles {
+		if profile.APIKey == keyringSentinel {
+			profile.APIKey = s.lookupKey(name)
+			cfg.Profiles[name] = profile
+		}
+	}
+	if cfg.APIKey == keyringSentinel {
+		name := strings.TrimSpace(cfg.ActiveProfile)
+		if name == "" {
+			name = strings.ToLower(strings.TrimSpace(cfg.Provider))
+		}
+		cfg.APIKey = s.lookupKey(name)
+	}
+}
+
+func (s *Store) lookupKey(profile string) string {
+	keyring := s.keyring
+	if keyring == nil {
+		keyring = unavailableKeyring{}
+	}
+	key, err := keyring.Get(keyringService(profile), keyringAccount)
+	if err != nil {
+		s.warning = fmt.Sprintf("API key for profile %q could not be read from the keyring: %v", profile, err)
+		return ""
+	}
+	return key
+}
+
+type unavailableKeyring struct{}
+
+func (unavailableKeyring) Get(string, string) (string, error) { return "", ErrKeyringUnavailable }
+
+func (unavailableKeyring) Set(string, string, string) error { return ErrKeyringUnavailable }
+
+func (unavailableKeyring) Delete(string, string) error { return ErrKeyringUnavailable }
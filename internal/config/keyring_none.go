@@ -0,0 +1,9 @@
+//go:build !keyring
+
+package config
+
+// systemKeyring returns nil: builds without the keyring tag keep API keys in
+// the configuration file.
+func systemKeyring() Keyring {
+	return nil
+}
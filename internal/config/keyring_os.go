@@ -0,0 +1,92 @@
+//go:build keyring
+
+package config
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"os/exec"
+	"runtime"
+	"strings"
+)
+
+// systemKeyring returns the platform keyring: the login keychain on macOS and
+// the Secret Service (via secret-tool) on Linux. Other platforms have none.
+func systemKeyring() Keyring {
+	switch runtime.GOOS {
+	case "darwin", "linux":
+		return commandKeyring{}
+	default:
+		return nil
+	}
+}
+
+type commandKeyring struct{}
+
+func (commandKeyring) Get(service, account string) (string, error) {
+	var cmd *exec.Cmd
+	if runtime.GOOS == "darwin" {
+		cmd = exec.Command("security", "find-generic-password", "-s", service, "-a", account, "-w")
+	} else {
+		cmd = exec.Command("secret-tool", "lookup", "service", service, "account", account)
+	}
+	out, err := runKeyringCommand(cmd)
+	if err != nil {
+		return "", err
+	}
+	return strings.TrimRight(out, "\r\n"), nil
+}
+
+func (commandKeyring) Set(service, account, secret string) error {
+	var cmd *exec.Cmd
+	if runtime.GOOS == "darwin" {
+		// security -i reads its commands from stdin, keeping the secret out
+		// of the process arguments.
+		if strings.ContainsAny(secret, "\r\n") {
+			return errors.New("keychain secrets cannot contain line breaks")
+		}
+		cmd = exec.Command("security", "-i")
+		cmd.Stdin = strings.NewReader(fmt.Sprintf("add-generic-password -U -s %s -a %s -w %s\n",
+			securityQuote(service), securityQuote(account), securityQuote(secret)))
+	} else {
+		// secret-tool reads the secret from stdin, keeping it off the command line.
+		cmd = exec.Command("secret-tool", "store", "--label", service, "service", service, "account", account)
+		cmd.Stdin = strings.NewReader(secret)
+	}
+	_, err := runKeyringCommand(cmd)
+	return err
+}
+
+func (commandKeyring) Delete(service, account string) error {
+	var cmd *exec.Cmd
+	if runtime.GOOS == "darwin" {
+		cmd = exec.Command("security", "delete-generic-password", "-s", service, "-a", account)
+	} else {
+		cmd = exec.Command("secret-tool", "clear", "service", service, "account", account)
+	}
+	_, err := runKeyringCommand(cmd)
+	return err
+}
+
+// securityQuote quotes an argument for the command parser of security -i.
+func securityQuote(arg string) string {
+	return `"` + strings.NewReplacer(`\`, `\\`, `"`, `\"`).Replace(arg) + `"`
+}
+
+func runKeyringCommand(cmd *exec.Cmd) (string, error) {
+	var stdout, stderr bytes.Buffer
+	cmd.Stdout = &stdout
+	cmd.Stderr = &stderr
+	if err := cmd.Run(); err != nil {
+		if _, ok := err.(*exec.Error); ok {
+			return "", fmt.Errorf("%w: %v", ErrKeyringUnavailable, err)
+		}
+		return "", fmt.Errorf("%s: %v: %s", cmd.Args[0], err, strings.TrimSpace(stderr.String()))
+	}
+	// security -i exits cleanly even when the command it read failed.
+	if len(cmd.Args) > 1 && cmd.Args[1] == "-i" && strings.TrimSpace(stderr.String()) != "" {
+		return "", fmt.Errorf("%s: %s", cmd.Args[0], strings.TrimSpace(stderr.String()))
+	}
+	return stdout.String(), nil
+}
@@ -0,0 +1,127 @@
+package config
+
+import (
+	"errors"
+	"fmt"
+	"sort"
+	"strings"
+
+	"shell-werk/internal/llm"
+)
+
+// ErrUnknownProfile is returned when selecting a profile that does not exist.
+var ErrUnknownProfile = errors.New("unknown provider profile")
+
+// ProviderProfile is a named provider connection, so several servers of the
+// same provider can be kept side by side.
+type ProviderProfile struct {
+	Provider string `json:"provider"`
+	Endpoint string `json:"endpoint"`
+	APIKey   string `json:"apiKey,omitempty"`
+}
+
+// ProfileSummary describes a profile for the picker without exposing its key.
+type ProfileSummary struct {
+	Name      string `json:"name"`
+	Provider  string `json:"provider"`
+	Endpoint  string `json:"endpoint"`
+	HasAPIKey bool   `json:"hasApiKey"`
+	Active    bool   `json:"active"`
+}
+
+// normalizeProfiles cleans profile entries and keeps the active profile in
+// step with the top-level connection fields, which stay authoritative for the
+// active connection. Configurations saved before profiles existed are
+// migrated into a single profile named after their provider.
+func (c LLMConfiguration) normalizeProfiles() LLMConfiguration {
+	profiles := make(map[string]ProviderProfile, len(c.Profiles)+1)
+	for name, profile := range c.Profiles {
+		name = strings.TrimSpace(name)
+		if name == "" {
+			continue
+		}
+		profile.Provider = strings.ToLower(strings.TrimSpace(profile.Provider))
+		profile.Endpoint = normalizeEndpoint(profile.Endpoint)
+		profile.APIKey = strings.TrimSpace(profile.APIKey)
+		profiles[name] = profile
+	}
+
+	c.ActiveProfile = strings.TrimSpace(c.ActiveProfile)
+	if c.ActiveProfile == "" && len(profiles) == 0 {
+		c.ActiveProfile = c.Provider
+	}
+	if c.ActiveProfile != "" {
+		profiles[c.ActiveProfile] = ProviderProfile{Provider: c.Provider, Endpoint: c.Endpoint, APIKey: c.APIKey}
+	}
+	c.Profiles = profiles
+	return c
+}
+
+// selectProfile makes name the active profile and copies its connection into
+// the top-level fields.
+func (c *LLMConfiguration) selectProfile(name string) error {
+	name = strings.TrimSpace(name)
+	profile, ok := c.Profiles[name]
+	if !ok {
+		return fmt.Errorf("%w: %s", ErrUnknownProfile, name)
+	}
+	c.ActiveProfile = name
+	c.Provider = profile.Provider
+	c.Endpoint = profile.Endpoint
+	c.APIKey = profile.APIKey
+	return nil
+}
+
+// ProfileSummaries lists the profiles sorted by name.
+func (c LLMConfiguration) ProfileSummaries() []ProfileSummary {
+	out := make([]ProfileSummary, 0, len(c.Profiles))
+	for name, profile := range c.Profiles {
+		out = append(out, ProfileSummary{
+			Name:      name,
+			Provider:  profile.Provider,
+			Endpoint:  profile.Endpoint,
+			HasAPIKey: profile.APIKey != "",
+			Active:    name == c.ActiveProfile,
+		})
+	}
+	sort.Slice(out, func(i, j int) bool { return out[i].Name < out[j].Name })
+	return out
+}
+
+// ModelsTargets lists the connection of every profile, sorted by name and
+// identified by the profile name. Call it on the Effective configuration so
+// the active connection includes env overrides.
+func (c LLMConfiguration) ModelsTargets() []llm.ModelsRequest {
+	names := make([]string, 0, len(c.Profiles))
+	for name := range c.Profiles {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+
+	targets := make([]llm.ModelsRequest, 0, len(names))
+	for _, name := range names {
+		profile := c.Profiles[name]
+		targets = append(targets, llm.ModelsRequest{
+			ID:       name,
+			Provider: profile.Provider,
+			Endpoint: expandEnv(profile.Endpoint),
+			APIKey:   expandEnv(profile.APIKey),
+		})
+	}
+	return targets
+}
+
+// DiagnosticsTargets lists every profile for llm.RunDiagnostics like
+// ModelsTargets. Only the active profile carries the selected model.
+func (c LLMConfiguration) DiagnosticsTargets() []llm.DiagnosticsTarget {
+	connections := c.ModelsTargets()
+	targets := make([]llm.DiagnosticsTarget, 0, len(connections))
+	for _, connection := range connections {
+		target := llm.DiagnosticsTarget{Target: connection}
+		if connection.ID == c.ActiveProfile {
+			target.Model = c.ActiveModel()
+		}
+		targets = append(targets, target)
+	}
+	return targets
+}
@@ -0,0 +1,363 @@
+package config
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"strings"
+	"sync"
+	"time"
+
+	"shell-werk/internal/llm"
+	"shell-werk/internal/tools"
+)
+
+// ConfigPathEnv overrides the location of the persisted LLM configuration file.
+const ConfigPathEnv = "SHELLWERK_CONFIG_PATH"
+
+const configFileName = "llm-config.json"
+
+var utf8BOM = []byte("\xef\xbb\xbf")
+
+// ErrReadOnly is returned when a change cannot be persisted because the
+// configuration location is not writable.
+var ErrReadOnly = errors.New("configuration is read-only")
+
+// LLMConfiguration is the provider selection persisted by the backend.
+type LLMConfiguration struct {
+	Provider      string `json:"provider"`
+	Endpoint      string `json:"endpoint"`
+	APIKey        string `json:"apiKey,omitempty"`
+	SelectedModel string `json:"selectedModel,omitempty"`
+	// Profiles holds named connections; the active one mirrors Provider,
+	// Endpoint and APIKey above.
+	Profiles      map[string]ProviderProfile `json:"profiles,omitempty"`
+	ActiveProfile string                     `json:"activeProfile,omitempty"`
+	// KeyringBacked keeps API keys in the OS keyring instead of this file when
+	// the build supports it; nil means enabled.
+	KeyringBacked *bool `json:"keyringBacked,omitempty"`
+	// SystemPrompt starts every conversation unless the request sets its own.
+	SystemPrompt string `json:"systemPrompt,omitempty"`
+	// RequestIDStyle is "timestamp" (default) or "uuid".
+	RequestIDStyle string `json:"requestIdStyle,omitempty"`
+	// StreamFallback switches to non-streaming requests for models that reject streaming.
+	StreamFallback bool `json:"streamFallback,omitempty"`
+	// ParallelTools executes the read-only tool calls of one turn concurrently.
+	ParallelTools bool `json:"parallelTools,omitempty"`
+	// ToolFallback retries requests without tools for models that reject them;
+	// nil means enabled.
+	ToolFallback *bool `json:"toolFallback,omitempty"`
+	// ParallelToolCalls is the default for requests that don't set it.
+	ParallelToolCalls *bool `json:"parallelToolCalls,omitempty"`
+	// ProviderGroup load-balances requests for its provider across endpoints.
+	ProviderGroup *llm.ProviderGroup `json:"providerGroup,omitempty"`
+	// RepetitionGuard stops streamed answers that keep repeating the same phrase.
+	RepetitionGuard *llm.RepetitionGuard `json:"repetitionGuard,omitempty"`
+	// StripANSI removes terminal escape sequences, such as colored shell
+	// output echoed by the model, from streamed answers.
+	StripANSI bool `json:"stripAnsi,omitempty"`
+	// MinDeltaChars buffers streamed answer text before emitting it to the UI.
+	MinDeltaChars int `json:"minDeltaChars,omitempty"`
+	// HardMaxCompletionTokens cuts off streamed answers past this estimated length.
+	HardMaxCompletionTokens int `json:"hardMaxCompletionTokens,omitempty"`
+	// ToolRoot confines attachments and file-based tools to a directory.
+	// Attachments are refused until it is set.
+	ToolRoot string `json:"toolRoot,omitempty"`
+	// ToolEnv adds environment variables to tool commands.
+	ToolEnv map[string]string `json:"toolEnv,omitempty"`
+	// ToolCwd is the working directory for tool commands; it must lie within ToolRoot.
+	ToolCwd string `json:"toolCwd,omitempty"`
+	// Tools adds user-defined tools backed by command templates.
+	Tools []tools.CustomTool `json:"tools,omitempty"`
+	// SanitizeToolEnv stops tools from inheriting anything but a safe set of variables.
+	SanitizeToolEnv bool `json:"sanitizeToolEnv,omitempty"`
+	// PrettifyModelLabels shows cleaned-up labels for OpenAI-compatible model ids.
+	PrettifyModelLabels bool `json:"prettifyModelLabels,omitempty"`
+	// MaxToolIterations is how many tool rounds a dialogue runs before it asks
+	// to continue (or, when streaming, stops); 0 uses the built-in default.
+	MaxToolIterations int `json:"maxToolIterations,omitempty"`
+	// FailFastOnToolError aborts a dialogue on the first failed tool instead
+	// of letting the model see the error and recover.
+	FailFastOnToolError bool `json:"failFastOnToolError,omitempty"`
+	// LogLevel is "info" (default) or "debug", which also logs the redacted
+	// JSON body of every completion request.
+	LogLevel string `json:"logLevel,omitempty"`
+	// ExtractCitations returns the "[n]" markers of tool-backed answers as
+	// structured citations.
+	ExtractCitations bool `json:"extractCitations,omitempty"`
+	// MaxConversationMessages compacts saved conversations that grow past this many messages.
+	MaxConversationMessages int `json:"maxConversationMessages,omitempty"`
+	// MergeConsecutiveAssistant joins back-to-back assistant replies when a
+	// conversation is saved.
+	MergeConsecutiveAssistant bool `json:"mergeConsecutiveAssistant,omitempty"`
+	// Sampling holds global generation defaults.
+	Sampling llm.SamplingParams `json:"sampling"`
+	// ProviderSampling overrides the global defaults per provider name.
+	ProviderSampling map[string]llm.SamplingParams `json:"providerSampling,omitempty"`
+	// ProviderTimeouts sets the request timeout in seconds per provider name;
+	// 0 disables the timeout and missing providers use llm.DefaultRequestTimeout.
+	ProviderTimeouts map[string]int `json:"providerTimeouts,omitempty"`
+	// RequestAttempts is how often model listings and non-streaming completions
+	// are tried on transient failures; 0 uses llm.DefaultRequestAttempts.
+	RequestAttempts int `json:"requestAttempts,omitempty"`
+}
+
+// DefaultConfiguration mirrors the frontend's default model config.
+func DefaultConfiguration() LLMConfiguration {
+	return LLMConfiguration{
+		Provider: "mock",
+		Endpoint: "http://localhost:11434",
+	}
+}
+
+// Normalize trims user input, including trailing slashes of endpoints, and
+// fills required defaults.
+func (c LLMConfiguration) Normalize() LLMConfiguration {
+	c.Provider = strings.ToLower(strings.TrimSpace(c.Provider))
+	if c.Provider == "" {
+		c.Provider = DefaultConfiguration().Provider
+	}
+	c.Endpoint = normalizeEndpoint(c.Endpoint)
+	c.APIKey = strings.TrimSpace(c.APIKey)
+	c.SelectedModel = strings.TrimSpace(c.SelectedModel)
+	c.SystemPrompt = strings.TrimSpace(c.SystemPrompt)
+	c.ToolRoot = strings.TrimSpace(c.ToolRoot)
+	c.ToolCwd = strings.TrimSpace(c.ToolCwd)
+	c.RequestIDStyle = strings.ToLower(strings.TrimSpace(c.RequestIDStyle))
+	c.LogLevel = strings.ToLower(strings.TrimSpace(c.LogLevel))
+	c.Tools = append([]tools.CustomTool(nil), c.Tools...)
+	for i := range c.Tools {
+		c.Tools[i].Name = strings.TrimSpace(c.Tools[i].Name)
+		c.Tools[i].Description = strings.TrimSpace(c.Tools[i].Description)
+		c.Tools[i].Command = strings.TrimSpace(c.Tools[i].Command)
+	}
+	if len(c.ProviderSampling) > 0 {
+		normalized := make(map[string]llm.SamplingParams, len(c.ProviderSampling))
+		for provider, params := range c.ProviderSampling {
+			normalized[strings.ToLower(strings.TrimSpace(provider))] = params
+		}
+		c.ProviderSampling = normalized
+	}
+	if len(c.ProviderTimeouts) > 0 {
+		normalized := make(map[string]int, len(c.ProviderTimeouts))
+		for provider, seconds := range c.ProviderTimeouts {
+			normalized[strings.ToLower(strings.TrimSpace(provider))] = max(seconds, 0)
+		}
+		c.ProviderTimeouts = normalized
+	}
+	return c.normalizeProfiles()
+}
+
+func normalizeEndpoint(endpoint string) string {
+	return strings.TrimRight(strings.TrimSpace(endpoint), "/")
+}
+
+// ToolFallbackEnabled reports whether requests are retried without tools
+// when the model rejects them.
+func (c LLMConfiguration) ToolFallbackEnabled() bool {
+	return c.ToolFallback == nil || *c.ToolFallback
+}
+
+// TimeoutFor returns the request timeout for provider in the form expected by
+// llm.MakeClientWithTimeout: zero for the default and llm.NoTimeout when the
+// configured value is 0.
+func (c LLMConfiguration) TimeoutFor(provider string) time.Duration {
+	seconds, ok := c.ProviderTimeouts[strings.ToLower(strings.TrimSpace(provider))]
+	if !ok {
+		return 0
+	}
+	if seconds <= 0 {
+		return llm.NoTimeout
+	}
+	return time.Duration(seconds) * time.Second
+}
+
+// SamplingFor merges the provider's sampling overrides over the global
+// defaults. Request-level values are merged on top by the caller.
+func (c LLMConfiguration) SamplingFor(provider string) llm.SamplingParams {
+	return c.Sampling.Merge(c.ProviderSampling[strings.ToLower(strings.TrimSpace(provider))])
+}
+
+// ActiveModel returns the effective model for the configured provider, or an
+// empty string when none is selected.
+func (c LLMConfiguration) ActiveModel() string {
+	return strings.TrimSpace(c.SelectedModel)
+}
+
+// Status describes where the configuration lives and whether it can be saved.
+type Status struct {
+	Path     string `json:"path"`
+	ReadOnly bool   `json:"readOnly"`
+	Warning  string `json:"warning,omitempty"`
+}
+
+// Store keeps the configuration in memory and persists changes to disk.
+type Store struct {
+	mu       sync.RWMutex
+	path     string
+	cfg      LLMConfiguration
+	readOnly bool
+	warning  string
+	keyring  Keyring
+}
+
+// DefaultPath resolves the configuration file, preferring ConfigPathEnv.
+func DefaultPath() (string, error) {
+	if override := strings.TrimSpace(os.Getenv(ConfigPathEnv)); override != "" {
+		return override, nil
+	}
+	dir, err := os.UserConfigDir()
+	if err != nil {
+		return "", fmt.Errorf("resolve config dir: %w", err)
+	}
+	return filepath.Join(dir, "shell-werk", configFileName), nil
+}
+
+// NewMemoryStore returns a store that keeps changes in memory only.
+func NewMemoryStore() *Store {
+	return &Store{cfg: DefaultConfiguration()}
+}
+
+// Open loads the configuration at path. Failures never prevent startup: when
+// the file cannot be written the store falls back to read-only mode and
+// records a warning describing why.
+func Open(path string) *Store {
+	return openWithKeyring(path, systemKeyring())
+}
+
+func openWithKeyring(path string, keyring Keyring) *Store {
+	s := &Store{path: path, cfg: DefaultConfiguration(), keyring: keyring}
+	s.load()
+	return s
+}
+
+func (s *Store) load() {
+	data, err := os.ReadFile(s.path)
+	if errors.Is(err, fs.ErrNotExist) {
+		if err := s.write(s.cfg); err != nil {
+			s.readOnly = true
+			s.warning = fmt.Sprintf("configuration cannot be saved, running read-only: %v", err)
+		}
+		return
+	}
+	if err != nil {
+		s.readOnly = true
+		s.warning = fmt.Sprintf("configuration cannot be read, running read-only: %v", err)
+		return
+	}
+
+	// Files saved by some Windows editors start with a UTF-8 BOM.
+	data = bytes.TrimLeft(bytes.TrimPrefix(data, utf8BOM), " \t\r\n")
+
+	cfg := DefaultConfiguration()
+	if err := json.Unmarshal(data, &cfg); err != nil {
+		s.warning = fmt.Sprintf("configuration at %s is invalid, using defaults: %v", s.path, err)
+		return
+	}
+	s.loadKeys(&cfg)
+	s.cfg = cfg.Normalize()
+}
+
+func (s *Store) write(cfg LLMConfiguration) error {
+	if s.path == "" {
+		return nil
+	}
+	data, err := json.MarshalIndent(s.storeKeys(cfg), "", "  ")
+	if err != nil {
+		return err
+	}
+	if err := os.MkdirAll(filepath.Dir(s.path), 0o755); err != nil {
+		return err
+	}
+	return os.WriteFile(s.path, data, 0o600)
+}
+
+// Get returns a copy of the current configuration.
+func (s *Store) Get() LLMConfiguration {
+	s.mu.RLock()
+	defer s.mu.RUnlock()
+	return s.cfg
+}
+
+// Status reports the backing path and read-only state.
+func (s *Store) Status() Status {
+	s.mu.RLock()
+	defer s.mu.RUnlock()
+	return Status{Path: s.path, ReadOnly: s.readOnly, Warning: s.warning}
+}
+
+// Save replaces the configuration and persists it. Invalid configurations are
+// rejected and leave the stored one untouched.
+func (s *Store) Save(cfg LLMConfiguration) (LLMConfiguration, error) {
+	if err := cfg.Normalize().Validate(); err != nil {
+		return s.Get(), err
+	}
+	return s.Update(func(current *LLMConfiguration) {
+		*current = cfg
+	})
+}
+
+// SaveWithChanges saves cfg like Save and reports what normalization changed,
+// so callers can refresh fields the user submitted in another form.
+func (s *Store) SaveWithChanges(cfg LLMConfiguration) (SaveResult, error) {
+	saved, err := s.Save(cfg)
+	result := SaveResult{Config: saved}
+	if err == nil || errors.Is(err, ErrReadOnly) {
+		result.Changes = NormalizationChanges(cfg, saved)
+		result.Normalized = len(result.Changes) > 0
+	}
+	return result, err
+}
+
+// UpdateSelectedModel changes only the selected model and persists it.
+func (s *Store) UpdateSelectedModel(model string) (LLMConfiguration, error) {
+	return s.Update(func(current *LLMConfiguration) {
+		current.SelectedModel = model
+	})
+}
+
+// SelectProfile switches the active connection to the named profile and persists it.
+func (s *Store) SelectProfile(name string) (LLMConfiguration, error) {
+	var selectErr error
+	cfg, err := s.Update(func(current *LLMConfiguration) {
+		selectErr = current.selectProfile(name)
+	})
+	if selectErr != nil {
+		return s.Get(), selectErr
+	}
+	return cfg, err
+}
+
+// UpdateSystemPrompt changes only the system prompt and persists it. A blank
+// prompt clears it.
+func (s *Store) UpdateSystemPrompt(prompt string) (LLMConfiguration, error) {
+	return s.Update(func(current *LLMConfiguration) {
+		current.SystemPrompt = prompt
+	})
+}
+
+// Update applies fn to the current configuration and persists the result. In
+// read-only mode the change is kept for this session and ErrReadOnly is returned.
+func (s *Store) Update(fn func(*LLMConfiguration)) (LLMConfiguration, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	previous := s.cfg
+	next := s.cfg
+	fn(&next)
+	next = next.Normalize()
+	s.cfg = next
+
+	if s.readOnly {
+		return next, fmt.Errorf("persist configuration: %w", ErrReadOnly)
+	}
+	if err := s.write(next); err != nil {
+		return next, fmt.Errorf("persist configuration to %s: %w", s.path, err)
+	}
+	s.forgetKeys(previous, next)
+	return next, nil
+}
@@ -0,0 +1,403 @@
+package config
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+	"time"
+
+	"shell-werk/internal/llm"
+)
+
+func TestOpenCreatesAndPersistsConfiguration(t *testing.T) {
+	keyring := fakeKeyring{}
+	path := filepath.Join(t.TempDir(), "shell-werk", configFileName)
+
+	store := openWithKeyring(path, keyring)
+	if status := store.Status(); status.ReadOnly || status.Warning != "" {
+		t.Fatalf("expected writable store, got %+v", status)
+	}
+	if _, err := os.Stat(path); err != nil {
+		t.Fatalf("expected config file to be created: %v", err)
+	}
+
+	if _, err := store.UpdateSelectedModel("  qwen3:4b "); err != nil {
+		t.Fatalf("UpdateSelectedModel returned error: %v", err)
+	}
+
+	reloaded := openWithKeyring(path, keyring)
+	if got := reloaded.Get().SelectedModel; got != "qwen3:4b" {
+		t.Fatalf("expected persisted model, got %q", got)
+	}
+}
+
+func TestUpdateSystemPromptPersists(t *testing.T) {
+	keyring := fakeKeyring{}
+	path := filepath.Join(t.TempDir(), configFileName)
+	store := openWithKeyring(path, keyring)
+
+	if _, err := store.UpdateSystemPrompt("  You are a shell assistant on Arch Linux.\n"); err != nil {
+		t.Fatalf("UpdateSystemPrompt returned error: %v", err)
+	}
+	if got := openWithKeyring(path, keyring).Get().SystemPrompt; got != "You are a shell assistant on Arch Linux." {
+		t.Fatalf("expected persisted prompt, got %q", got)
+	}
+
+	if _, err := store.UpdateSystemPrompt("   "); err != nil {
+		t.Fatalf("UpdateSystemPrompt returned error: %v", err)
+	}
+	if got := openWithKeyring(path, keyring).Get().SystemPrompt; got != "" {
+		t.Fatalf("expected blank prompt to clear it, got %q", got)
+	}
+}
+
+func TestOpenFallsBackToReadOnly(t *testing.T) {
+	keyring := fakeKeyring{}
+	dir := t.TempDir()
+	blocker := filepath.Join(dir, "blocker")
+	if err := os.WriteFile(blocker, []byte("not a directory"), 0o644); err != nil {
+		t.Fatalf("failed to write blocker file: %v", err)
+	}
+
+	// The parent is a regular file, so the config directory can never be created.
+	store := openWithKeyring(filepath.Join(blocker, "shell-werk", configFileName), keyring)
+
+	status := store.Status()
+	if !status.ReadOnly {
+		t.Fatalf("expected read-only mode, got %+v", status)
+	}
+	if status.Warning == "" {
+		t.Fatalf("expected a warning to be surfaced")
+	}
+	if store.Get().Provider != DefaultConfiguration().Provider {
+		t.Fatalf("expected defaults in read-only mode, got %+v", store.Get())
+	}
+
+	cfg, err := store.UpdateSelectedModel("llama3")
+	if !errors.Is(err, ErrReadOnly) {
+		t.Fatalf("expected ErrReadOnly, got %v", err)
+	}
+	if cfg.SelectedModel != "llama3" || store.Get().SelectedModel != "llama3" {
+		t.Fatalf("expected in-memory update to apply, got %+v", store.Get())
+	}
+}
+
+func TestActiveModel(t *testing.T) {
+	store := NewMemoryStore()
+	if got := store.Get().ActiveModel(); got != "" {
+		t.Fatalf("expected no active model, got %q", got)
+	}
+
+	if _, err := store.UpdateSelectedModel("qwen3:4b"); err != nil {
+		t.Fatalf("UpdateSelectedModel returned error: %v", err)
+	}
+	if got := store.Get().ActiveModel(); got != "qwen3:4b" {
+		t.Fatalf("expected selected model, got %q", got)
+	}
+}
+
+func TestEffectiveAppliesEnvOverrides(t *testing.T) {
+	t.Setenv(EndpointEnv, "http://gpu-box:8000")
+	t.Setenv("SHELLWERK_TEST_KEY", "sk-from-env")
+
+	cfg := LLMConfiguration{Provider: "vllm", Endpoint: "http://localhost:8000", APIKey: "${SHELLWERK_TEST_KEY}"}
+	effective := cfg.Effective()
+	if effective.Endpoint != "http://gpu-box:8000" {
+		t.Fatalf("expected overridden endpoint, got %q", effective.Endpoint)
+	}
+	if effective.APIKey != "sk-from-env" {
+		t.Fatalf("expected interpolated api key, got %q", effective.APIKey)
+	}
+	if masked := effective.Masked(); masked.APIKey == "sk-from-env" || masked.APIKey == "" {
+		t.Fatalf("expected masked api key, got %q", masked.APIKey)
+	}
+	if cfg.Endpoint != "http://localhost:8000" {
+		t.Fatalf("stored configuration must stay untouched, got %q", cfg.Endpoint)
+	}
+}
+
+func TestEffectiveKeepsBareDollarSigns(t *testing.T) {
+	t.Setenv("abc", "expanded")
+	t.Setenv("SHELLWERK_TEST_HOST", "gpu-box")
+
+	cfg := LLMConfiguration{Provider: "vllm", Endpoint: "http://${SHELLWERK_TEST_HOST}:8000/$path", APIKey: "sk-$abc$"}
+	effective := cfg.Effective()
+	if effective.APIKey != "sk-$abc$" {
+		t.Fatalf("expected the key verbatim, got %q", effective.APIKey)
+	}
+	if effective.Endpoint != "http://gpu-box:8000/$path" {
+		t.Fatalf("expected only ${VAR} to be expanded, got %q", effective.Endpoint)
+	}
+}
+
+func TestSaveWithChangesReportsNormalization(t *testing.T) {
+	store := NewMemoryStore()
+	result, err := store.SaveWithChanges(LLMConfiguration{
+		Provider: "Ollama",
+		Endpoint: "http://gpu:11434/",
+		Profiles: map[string]ProviderProfile{"remote": {Provider: "vllm", Endpoint: "http://remote:8000//"}},
+	})
+	if err != nil {
+		t.Fatalf("SaveWithChanges returned error: %v", err)
+	}
+	if result.Config.Endpoint != "http://gpu:11434" || result.Config.Profiles["remote"].Endpoint != "http://remote:8000" {
+		t.Fatalf("expected trailing slashes to be stripped, got %+v", result.Config)
+	}
+
+	want := []FieldChange{
+		{Field: "provider", Submitted: "Ollama", Normalized: "ollama"},
+		{Field: "endpoint", Submitted: "http://gpu:11434/", Normalized: "http://gpu:11434"},
+		{Field: "profiles.remote.endpoint", Submitted: "http://remote:8000//", Normalized: "http://remote:8000"},
+	}
+	if !result.Normalized || !reflect.DeepEqual(result.Changes, want) {
+		t.Fatalf("expected changes %+v, got %+v", want, result.Changes)
+	}
+
+	result, err = store.SaveWithChanges(result.Config)
+	if err != nil || result.Normalized || len(result.Changes) != 0 {
+		t.Fatalf("expected a normalized config to save unchanged, got %+v (%v)", result, err)
+	}
+}
+
+func TestMaskedHidesEveryAPIKey(t *testing.T) {
+	cfg := LLMConfiguration{
+		Provider: "openai",
+		APIKey:   "sk-active",
+		Profiles: map[string]ProviderProfile{
+			"cloud": {Provider: "openai", APIKey: "sk-active"},
+			"local": {Provider: "ollama"},
+		},
+	}
+
+	masked := cfg.Masked()
+	if masked.APIKey != maskedKey || masked.Profiles["cloud"].APIKey != maskedKey {
+		t.Fatalf("expected set keys to be masked, got %+v", masked)
+	}
+	if masked.Profiles["local"].APIKey != "" {
+		t.Fatalf("expected a missing key to stay empty, got %q", masked.Profiles["local"].APIKey)
+	}
+	if cfg.Profiles["cloud"].APIKey != "sk-active" {
+		t.Fatalf("masking must not modify the original profiles")
+	}
+}
+
+func TestOpenAcceptsBOMPrefixedConfig(t *testing.T) {
+	keyring := fakeKeyring{}
+	path := filepath.Join(t.TempDir(), configFileName)
+	content := "\xef\xbb\xbf\r\n  {\"provider\":\"ollama\",\"endpoint\":\"http://gpu:11434\",\"selectedModel\":\"llama3\"}"
+	if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+		t.Fatalf("failed to write config: %v", err)
+	}
+
+	store := openWithKeyring(path, keyring)
+	if warning := store.Status().Warning; warning != "" {
+		t.Fatalf("expected no warning, got %q", warning)
+	}
+	if cfg := store.Get(); cfg.Provider != "ollama" || cfg.SelectedModel != "llama3" {
+		t.Fatalf("expected BOM-prefixed config to load, got %+v", cfg)
+	}
+}
+
+func TestOpenLoadsConfigWrittenBeforeOpenAIProvider(t *testing.T) {
+	keyring := fakeKeyring{}
+	path := filepath.Join(t.TempDir(), configFileName)
+	content := `{"provider":"vllm","endpoint":"http://gpu:8000","apiKey":"secret","sampling":{}}`
+	if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+		t.Fatalf("failed to write config: %v", err)
+	}
+
+	store := openWithKeyring(path, keyring)
+	if warning := store.Status().Warning; warning != "" {
+		t.Fatalf("expected no warning, got %q", warning)
+	}
+	if cfg := store.Get(); cfg.Provider != "vllm" || cfg.Endpoint != "http://gpu:8000" {
+		t.Fatalf("expected older config to load unchanged, got %+v", cfg)
+	}
+
+	saved, err := store.Save(LLMConfiguration{Provider: " OpenAI ", APIKey: "sk-test"})
+	if err != nil {
+		t.Fatalf("Save returned error: %v", err)
+	}
+	if reopened := openWithKeyring(path, keyring).Get(); saved.Provider != "openai" || reopened.Provider != "openai" || reopened.APIKey != "sk-test" {
+		t.Fatalf("expected openai provider to round-trip, got %+v then %+v", saved, reopened)
+	}
+}
+
+func TestTimeoutForBuildsClientWithConfiguredTimeout(t *testing.T) {
+	cfg := LLMConfiguration{ProviderTimeouts: map[string]int{" Ollama ": 300, "vllm": 0, "openai": -5}}.Normalize()
+
+	cases := map[string]time.Duration{
+		"ollama": 300 * time.Second,
+		"vllm":   0,
+		"openai": 0,
+		"mock":   llm.DefaultRequestTimeout,
+	}
+	for provider, want := range cases {
+		if got := llm.MakeClientWithTimeout(cfg.TimeoutFor(provider)).Timeout; got != want {
+			t.Errorf("client timeout for %s = %s, want %s", provider, got, want)
+		}
+	}
+}
+
+func TestOpenMigratesSingleProviderConfigIntoProfile(t *testing.T) {
+	keyring := fakeKeyring{}
+	path := filepath.Join(t.TempDir(), configFileName)
+	content := `{"provider":"vllm","endpoint":"http://small:8000","apiKey":"secret","selectedModel":"qwen3"}`
+	if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+		t.Fatalf("failed to write config: %v", err)
+	}
+
+	store := openWithKeyring(path, keyring)
+	cfg := store.Get()
+	if cfg.ActiveProfile != "vllm" {
+		t.Fatalf("expected migrated profile to be active, got %q", cfg.ActiveProfile)
+	}
+	if got := cfg.Profiles["vllm"]; got != (ProviderProfile{Provider: "vllm", Endpoint: "http://small:8000", APIKey: "secret"}) {
+		t.Fatalf("expected old connection to become a profile, got %+v", got)
+	}
+
+	cfg.Profiles["large"] = ProviderProfile{Provider: "vllm", Endpoint: "http://large:8000"}
+	if _, err := store.Save(cfg); err != nil {
+		t.Fatalf("Save returned error: %v", err)
+	}
+	selected, err := store.SelectProfile("large")
+	if err != nil {
+		t.Fatalf("SelectProfile returned error: %v", err)
+	}
+	if selected.Endpoint != "http://large:8000" || selected.APIKey != "" || selected.ActiveProfile != "large" {
+		t.Fatalf("expected the large profile's connection, got %+v", selected)
+	}
+
+	summaries := openWithKeyring(path, keyring).Get().ProfileSummaries()
+	if len(summaries) != 2 || summaries[0].Name != "large" || !summaries[0].Active || summaries[1].Active || !summaries[1].HasAPIKey {
+		t.Fatalf("unexpected profiles after reload: %+v", summaries)
+	}
+
+	if _, err := store.SelectProfile("missing"); !errors.Is(err, ErrUnknownProfile) {
+		t.Fatalf("expected ErrUnknownProfile, got %v", err)
+	}
+}
+
+func TestSaveRejectsInvalidEndpoints(t *testing.T) {
+	keyring := fakeKeyring{}
+	path := filepath.Join(t.TempDir(), configFileName)
+	store := openWithKeyring(path, keyring)
+	if _, err := store.Save(LLMConfiguration{Provider: "ollama", Endpoint: "http://localhost:11434"}); err != nil {
+		t.Fatalf("expected a valid URL to save, got %v", err)
+	}
+
+	for _, endpoint := range []string{"ftp://localhost:11434", "localhost:11434", "not a url"} {
+		if _, err := store.Save(LLMConfiguration{Provider: "ollama", Endpoint: endpoint}); !errors.Is(err, ErrInvalidConfig) {
+			t.Fatalf("expected ErrInvalidConfig for %q, got %v", endpoint, err)
+		}
+	}
+
+	if got := openWithKeyring(path, keyring).Get().Endpoint; got != "http://localhost:11434" {
+		t.Fatalf("expected the stored endpoint to be untouched, got %q", got)
+	}
+}
+
+type fakeKeyring map[string]string
+
+func (k fakeKeyring) Get(service, account string) (string, error) {
+	secret, ok := k[service+"|"+account]
+	if !ok {
+		return "", errors.New("not found")
+	}
+	return secret, nil
+}
+
+func (k fakeKeyring) Set(service, account, secret string) error {
+	k[service+"|"+account] = secret
+	return nil
+}
+
+func (k fakeKeyring) Delete(service, account string) error {
+	delete(k, service+"|"+account)
+	return nil
+}
+
+func TestKeyringKeepsAPIKeysOutOfTheFile(t *testing.T) {
+	path := filepath.Join(t.TempDir(), configFileName)
+	keyring := fakeKeyring{}
+	store := openWithKeyring(path, keyring)
+	if _, err := store.Save(LLMConfiguration{Provider: "openai", APIKey: "sk-secret"}); err != nil {
+		t.Fatalf("Save returned error: %v", err)
+	}
+
+	data, err := os.ReadFile(path)
+	if err != nil {
+		t.Fatalf("failed to read config: %v", err)
+	}
+	if strings.Contains(string(data), "sk-secret") {
+		t.Fatalf("expected the key to stay out of the file, got %s", data)
+	}
+	if keyring[keyringService("openai")+"|"+keyringAccount] != "sk-secret" {
+		t.Fatalf("expected the key in the keyring, got %+v", keyring)
+	}
+
+	reloaded := openWithKeyring(path, keyring).Get()
+	if reloaded.APIKey != "sk-secret" || reloaded.Profiles["openai"].APIKey != "sk-secret" {
+		t.Fatalf("expected the key to be hydrated from the keyring, got %+v", reloaded)
+	}
+
+	disabled := false
+	reloaded.KeyringBacked = &disabled
+	if _, err := openWithKeyring(path, keyring).Save(reloaded); err != nil {
+		t.Fatalf("Save returned error: %v", err)
+	}
+	if data, _ := os.ReadFile(path); !strings.Contains(string(data), "sk-secret") {
+		t.Fatalf("expected opting out to store the key in the file, got %s", data)
+	}
+}
+
+func TestKeyringForgetsKeysOfRemovedProfiles(t *testing.T) {
+	path := filepath.Join(t.TempDir(), configFileName)
+	keyring := fakeKeyring{}
+	store := openWithKeyring(path, keyring)
+	cfg := LLMConfiguration{
+		Provider:      "openai",
+		APIKey:        "sk-openai",
+		ActiveProfile: "openai",
+		Profiles: map[string]ProviderProfile{
+			"openai": {Provider: "openai", APIKey: "sk-openai"},
+			"groq":   {Provider: "openai", Endpoint: "https://api.groq.com/openai", APIKey: "sk-groq"},
+		},
+	}
+	if _, err := store.Save(cfg); err != nil {
+		t.Fatalf("Save returned error: %v", err)
+	}
+	if len(keyring) != 2 {
+		t.Fatalf("expected both keys in the keyring, got %+v", keyring)
+	}
+
+	delete(cfg.Profiles, "groq")
+	if _, err := store.Save(cfg); err != nil {
+		t.Fatalf("Save returned error: %v", err)
+	}
+	if _, ok := keyring[keyringService("groq")+"|"+keyringAccount]; ok {
+		t.Fatalf("expected the removed profile's key to be deleted, got %+v", keyring)
+	}
+	if keyring[keyringService("openai")+"|"+keyringAccount] != "sk-openai" {
+		t.Fatalf("expected the remaining profile's key to stay, got %+v", keyring)
+	}
+}
+
+func TestKeyringSentinelWithoutKeyringWarns(t *testing.T) {
+	path := filepath.Join(t.TempDir(), configFileName)
+	content := `{"provider":"openai","apiKey":"@keyring","profiles":{"openai":{"provider":"openai","apiKey":"@keyring"}},"activeProfile":"openai"}`
+	if err := os.WriteFile(path, []byte(content), 0o600); err != nil {
+		t.Fatalf("failed to write config: %v", err)
+	}
+
+	store := openWithKeyring(path, nil)
+	if got := store.Get().APIKey; got != "" {
+		t.Fatalf("expected no key without a keyring, got %q", got)
+	}
+	if status := store.Status(); status.Warning == "" {
+		t.Fatalf("expected a warning about the unreadable key")
+	}
+}
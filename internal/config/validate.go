@@ -0,0 +1,79 @@
+package config
+
+import (
+	"errors"
+	"fmt"
+	"net/url"
+	"sort"
+	"strings"
+
+	"shell-werk/internal/tools"
+)
+
+// ErrInvalidConfig is returned when a configuration is rejected before saving.
+var ErrInvalidConfig = errors.New("invalid configuration")
+
+// Validate checks the connection endpoints so a malformed URL is rejected on
+// save instead of failing every later request. Blank endpoints are allowed;
+// they fall back to the provider default.
+func (c LLMConfiguration) Validate() error {
+	if err := validateEndpoint(c.Provider, c.Endpoint); err != nil {
+		return err
+	}
+	names := make([]string, 0, len(c.Profiles))
+	for name := range c.Profiles {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	for _, name := range names {
+		if err := validateEndpoint(fmt.Sprintf("profile %q", name), c.Profiles[name].Endpoint); err != nil {
+			return err
+		}
+	}
+	return validateTools(c.Tools)
+}
+
+// validateTools rejects custom tools that are unnamed, have no command, or
+// reuse the name of another tool.
+func validateTools(custom []tools.CustomTool) error {
+	seen := map[string]bool{}
+	for _, tool := range tools.DefaultTools() {
+		seen[tool.ID] = true
+	}
+	for _, tool := range custom {
+		switch {
+		case tool.Name == "":
+			return fmt.Errorf("%w: custom tools need a name", ErrInvalidConfig)
+		case tool.Command == "":
+			return fmt.Errorf("%w: tool %q has no command", ErrInvalidConfig, tool.Name)
+		case seen[tool.Name]:
+			return fmt.Errorf("%w: tool name %q is already in use", ErrInvalidConfig, tool.Name)
+		}
+		seen[tool.Name] = true
+	}
+	return nil
+}
+
+func validateEndpoint(owner, endpoint string) error {
+	expanded := strings.TrimSpace(expandEnv(endpoint))
+	if expanded == "" {
+		return nil
+	}
+	invalid := func(reason string) error {
+		return fmt.Errorf("%w: %s endpoint %q %s", ErrInvalidConfig, owner, endpoint, reason)
+	}
+	if !strings.Contains(expanded, "://") {
+		return invalid("is missing an http:// or https:// scheme")
+	}
+	parsed, err := url.Parse(expanded)
+	if err != nil {
+		return invalid("is not a valid URL")
+	}
+	if parsed.Scheme != "http" && parsed.Scheme != "https" {
+		return invalid("must use http or https")
+	}
+	if parsed.Host == "" {
+		return invalid("is missing a host")
+	}
+	return nil
+}
@@ -0,0 +1,56 @@
+package conversations
+
+import (
+	"strings"
+
+	"shell-werk/internal/llm"
+)
+
+// MergeConsecutiveAssistant folds back-to-back assistant messages, as left by
+// two replies streamed for quickly sent user messages, into one. Contents are
+// joined by a blank line, usage is summed and the later finish reason wins.
+// Messages with tool calls are never merged so tool chains stay intact.
+func MergeConsecutiveAssistant(messages []llm.ChatMessage) []llm.ChatMessage {
+	merged := make([]llm.ChatMessage, 0, len(messages))
+	for _, msg := range messages {
+		if n := len(merged); n > 0 && mergeable(merged[n-1]) && mergeable(msg) {
+			prev := &merged[n-1]
+			prev.Content = joinContent(prev.Content, msg.Content)
+			prev.FinishReason = msg.FinishReason
+			prev.Usage = sumUsage(prev.Usage, msg.Usage)
+			continue
+		}
+		merged = append(merged, msg)
+	}
+	return merged
+}
+
+func mergeable(msg llm.ChatMessage) bool {
+	return strings.EqualFold(strings.TrimSpace(msg.Role), "assistant") && len(msg.ToolCalls) == 0
+}
+
+func joinContent(first, second string) string {
+	first, second = strings.TrimSpace(first), strings.TrimSpace(second)
+	switch {
+	case first == "":
+		return second
+	case second == "":
+		return first
+	default:
+		return first + "\n\n" + second
+	}
+}
+
+func sumUsage(a, b *llm.Usage) *llm.Usage {
+	if a == nil {
+		return b
+	}
+	if b == nil {
+		return a
+	}
+	return &llm.Usage{
+		PromptTokens:     a.PromptTokens + b.PromptTokens,
+		CompletionTokens: a.CompletionTokens + b.CompletionTokens,
+		Estimated:        a.Estimated || b.Estimated,
+	}
+}
@@ -0,0 +1,274 @@
+package conversations
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"shell-werk/internal/llm"
+)
+
+const dirName = "conversations"
+
+// ErrUnavailable is returned when no storage directory is configured.
+var ErrUnavailable = errors.New("conversation storage is unavailable")
+
+var validID = regexp.MustCompile(`^[A-Za-z0-9][A-Za-z0-9_.-]*$`)
+
+// Conversation is a persisted chat session.
+type Conversation struct {
+	ID        string            `json:"id"`
+	Title     string            `json:"title"`
+	CreatedAt time.Time         `json:"createdAt"`
+	UpdatedAt time.Time         `json:"updatedAt"`
+	// Provider and Model pin the conversation to the backend it was created with.
+	Provider string            `json:"provider,omitempty"`
+	Model    string            `json:"model,omitempty"`
+	Messages []llm.ChatMessage `json:"messages"`
+}
+
+// Meta summarizes a conversation without its messages.
+type Meta struct {
+	ID           string    `json:"id"`
+	Title        string    `json:"title"`
+	CreatedAt    time.Time `json:"createdAt"`
+	UpdatedAt    time.Time `json:"updatedAt"`
+	Provider     string    `json:"provider,omitempty"`
+	Model        string    `json:"model,omitempty"`
+	MessageCount int       `json:"messageCount"`
+}
+
+func (c Conversation) Meta() Meta {
+	return Meta{
+		ID:           c.ID,
+		Title:        c.Title,
+		CreatedAt:    c.CreatedAt,
+		UpdatedAt:    c.UpdatedAt,
+		Provider:     c.Provider,
+		Model:        c.Model,
+		MessageCount: len(c.Messages),
+	}
+}
+
+// Problem categorizes why a conversation file could not be loaded.
+type Problem string
+
+const (
+	ProblemNotFound     Problem = "not_found"
+	ProblemUnreadable   Problem = "unreadable"
+	ProblemInvalidJSON  Problem = "invalid_json"
+	ProblemMissingField Problem = "missing_field"
+	ProblemInvalidID    Problem = "invalid_id"
+)
+
+// FileError describes a conversation file that failed to load.
+type FileError struct {
+	ID      string  `json:"id"`
+	Problem Problem `json:"problem"`
+	Detail  string  `json:"detail"`
+}
+
+func (e *FileError) Error() string {
+	return fmt.Sprintf("conversation %s: %s: %s", e.ID, e.Problem, e.Detail)
+}
+
+// Dir returns the conversation directory inside the data directory.
+func Dir(dataDir string) string {
+	return filepath.Join(dataDir, dirName)
+}
+
+// Store persists one JSON file per conversation.
+type Store struct {
+	mu  sync.Mutex
+	dir string
+}
+
+// NewStore returns a store rooted at dir. An empty dir disables persistence.
+func NewStore(dir string) *Store {
+	return &Store{dir: strings.TrimSpace(dir)}
+}
+
+func (s *Store) path(id string) (string, error) {
+	if s.dir == "" {
+		return "", ErrUnavailable
+	}
+	if !validID.MatchString(id) {
+		return "", &FileError{ID: id, Problem: ProblemInvalidID, Detail: "ids may only contain letters, digits, '.', '_' and '-'"}
+	}
+	return filepath.Join(s.dir, id+".json"), nil
+}
+
+// Save writes the conversation, stamping UpdatedAt.
+func (s *Store) Save(conv Conversation) (Meta, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	path, err := s.path(conv.ID)
+	if err != nil {
+		return Meta{}, err
+	}
+	now := time.Now()
+	if conv.CreatedAt.IsZero() {
+		conv.CreatedAt = now
+	}
+	conv.UpdatedAt = now
+	if conv.Messages == nil {
+		conv.Messages = []llm.ChatMessage{}
+	}
+
+	data, err := json.MarshalIndent(conv, "", "  ")
+	if err != nil {
+		return Meta{}, err
+	}
+	if err := os.MkdirAll(s.dir, 0o755); err != nil {
+		return Meta{}, fmt.Errorf("create conversation dir: %w", err)
+	}
+	if err := os.WriteFile(path, data, 0o600); err != nil {
+		return Meta{}, fmt.Errorf("write conversation %s: %w", conv.ID, err)
+	}
+	return conv.Meta(), nil
+}
+
+// Load reads and validates a stored conversation.
+func (s *Store) Load(id string) (Conversation, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return s.load(id)
+}
+
+func (s *Store) load(id string) (Conversation, error) {
+	path, err := s.path(id)
+	if err != nil {
+		return Conversation{}, err
+	}
+
+	data, err := os.ReadFile(path)
+	if errors.Is(err, fs.ErrNotExist) {
+		return Conversation{}, &FileError{ID: id, Problem: ProblemNotFound, Detail: "no stored conversation with this id"}
+	}
+	if err != nil {
+		return Conversation{}, &FileError{ID: id, Problem: ProblemUnreadable, Detail: err.Error()}
+	}
+	return decode(id, data)
+}
+
+func decode(id string, data []byte) (Conversation, error) {
+	var fields map[string]json.RawMessage
+	if err := json.Unmarshal(data, &fields); err != nil {
+		return Conversation{}, &FileError{ID: id, Problem: ProblemInvalidJSON, Detail: err.Error()}
+	}
+	for _, required := range []string{"id", "messages"} {
+		if _, ok := fields[required]; !ok {
+			return Conversation{}, &FileError{ID: id, Problem: ProblemMissingField, Detail: fmt.Sprintf("missing %q", required)}
+		}
+	}
+
+	var conv Conversation
+	if err := json.Unmarshal(data, &conv); err != nil {
+		return Conversation{}, &FileError{ID: id, Problem: ProblemInvalidJSON, Detail: err.Error()}
+	}
+	if strings.TrimSpace(conv.ID) == "" {
+		return Conversation{}, &FileError{ID: id, Problem: ProblemMissingField, Detail: `"id" is empty`}
+	}
+	return conv, nil
+}
+
+// Validate parses a stored conversation and returns its metadata, or a
+// *FileError naming the problem.
+func (s *Store) Validate(id string) (Meta, error) {
+	conv, err := s.Load(id)
+	if err != nil {
+		return Meta{}, err
+	}
+	return conv.Meta(), nil
+}
+
+// List returns metadata for every readable conversation, newest first.
+// Unreadable files are skipped; use Validate to diagnose them.
+func (s *Store) List() ([]Meta, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	if s.dir == "" {
+		return nil, ErrUnavailable
+	}
+	entries, err := os.ReadDir(s.dir)
+	if errors.Is(err, fs.ErrNotExist) {
+		return []Meta{}, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("list conversations: %w", err)
+	}
+
+	metas := []Meta{}
+	for _, entry := range entries {
+		name := entry.Name()
+		if entry.IsDir() || !strings.HasSuffix(name, ".json") {
+			continue
+		}
+		conv, err := s.load(strings.TrimSuffix(name, ".json"))
+		if err != nil {
+			continue
+		}
+		metas = append(metas, conv.Meta())
+	}
+	sort.Slice(metas, func(i, j int) bool {
+		return metas[i].UpdatedAt.After(metas[j].UpdatedAt)
+	})
+	return metas, nil
+}
+
+// Delete removes a stored conversation. Deleting one that does not exist is
+// not an error.
+func (s *Store) Delete(id string) error {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	path, err := s.path(id)
+	if err != nil {
+		return err
+	}
+	if err := os.Remove(path); err != nil && !errors.Is(err, fs.ErrNotExist) {
+		return fmt.Errorf("delete conversation %s: %w", id, err)
+	}
+	return nil
+}
+
+// Clear removes every stored conversation. Only conversation files directly
+// inside the store directory are removed; subdirectories and other files are
+// left alone and symlinks are removed rather than followed.
+func (s *Store) Clear() error {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	if s.dir == "" {
+		return ErrUnavailable
+	}
+	entries, err := os.ReadDir(s.dir)
+	if errors.Is(err, fs.ErrNotExist) {
+		return nil
+	}
+	if err != nil {
+		return fmt.Errorf("list conversations: %w", err)
+	}
+
+	for _, entry := range entries {
+		name := entry.Name()
+		id := strings.TrimSuffix(name, ".json")
+		if entry.IsDir() || id == name || !validID.MatchString(id) {
+			continue
+		}
+		if err := os.Remove(filepath.Join(s.dir, name)); err != nil && !errors.Is(err, fs.ErrNotExist) {
+			return fmt.Errorf("delete conversation %s: %w", id, err)
+		}
+	}
+	return nil
+}
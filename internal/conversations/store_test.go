@@ -0,0 +1,131 @@
+package conversations
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"shell-werk/internal/llm"
+)
+
+func TestSaveLoadRoundTrip(t *testing.T) {
+	store := NewStore(t.TempDir())
+	meta, err := store.Save(Conversation{
+		ID:       "chat-1",
+		Title:    "Disk usage",
+		Messages: []llm.ChatMessage{{Role: "user", Content: "du -sh?"}},
+	})
+	if err != nil {
+		t.Fatalf("Save returned error: %v", err)
+	}
+	if meta.MessageCount != 1 || meta.CreatedAt.IsZero() {
+		t.Fatalf("unexpected meta: %+v", meta)
+	}
+
+	validated, err := store.Validate("chat-1")
+	if err != nil {
+		t.Fatalf("Validate returned error: %v", err)
+	}
+	if validated.Title != "Disk usage" || validated.MessageCount != 1 {
+		t.Fatalf("unexpected validated meta: %+v", validated)
+	}
+}
+
+func TestValidateReportsCorruptFiles(t *testing.T) {
+	dir := t.TempDir()
+	store := NewStore(dir)
+
+	files := map[string]string{
+		"truncated": `{"id":"truncated","messages":[`,
+		"no-messages": `{"id":"no-messages","title":"x"}`,
+	}
+	for id, content := range files {
+		if err := os.WriteFile(filepath.Join(dir, id+".json"), []byte(content), 0o644); err != nil {
+			t.Fatalf("failed to write fixture: %v", err)
+		}
+	}
+
+	cases := map[string]Problem{
+		"truncated":   ProblemInvalidJSON,
+		"no-messages": ProblemMissingField,
+		"missing":     ProblemNotFound,
+		"../escape":   ProblemInvalidID,
+	}
+	for id, want := range cases {
+		_, err := store.Validate(id)
+		var fileErr *FileError
+		if !errors.As(err, &fileErr) {
+			t.Fatalf("Validate(%q): expected FileError, got %v", id, err)
+		}
+		if fileErr.Problem != want || fileErr.Detail == "" {
+			t.Fatalf("Validate(%q): expected %s with detail, got %+v", id, want, fileErr)
+		}
+	}
+}
+
+func TestDeleteAndClearConversations(t *testing.T) {
+	dir := t.TempDir()
+	store := NewStore(dir)
+	for _, id := range []string{"keep", "drop"} {
+		if _, err := store.Save(Conversation{ID: id, Messages: []llm.ChatMessage{{Role: "user", Content: id}}}); err != nil {
+			t.Fatalf("Save returned error: %v", err)
+		}
+	}
+
+	if err := store.Delete("drop"); err != nil {
+		t.Fatalf("Delete returned error: %v", err)
+	}
+	if err := store.Delete("drop"); err != nil {
+		t.Fatalf("expected deleting a missing conversation to succeed, got %v", err)
+	}
+	if err := store.Delete("../outside"); err == nil {
+		t.Fatalf("expected an invalid id to be rejected")
+	}
+
+	metas, err := store.List()
+	if err != nil {
+		t.Fatalf("List returned error: %v", err)
+	}
+	if len(metas) != 1 || metas[0].ID != "keep" {
+		t.Fatalf("expected only the survivor, got %+v", metas)
+	}
+
+	notes := filepath.Join(dir, "notes.txt")
+	if err := os.WriteFile(notes, []byte("keep me"), 0o600); err != nil {
+		t.Fatalf("failed to write file: %v", err)
+	}
+	if err := store.Clear(); err != nil {
+		t.Fatalf("Clear returned error: %v", err)
+	}
+	if metas, _ := store.List(); len(metas) != 0 {
+		t.Fatalf("expected no conversations after Clear, got %+v", metas)
+	}
+	if _, err := os.Stat(notes); err != nil {
+		t.Fatalf("expected unrelated files to survive Clear: %v", err)
+	}
+}
+
+func TestMergeConsecutiveAssistant(t *testing.T) {
+	messages := []llm.ChatMessage{
+		{Role: "user", Content: "first question"},
+		{Role: "user", Content: "second question"},
+		{Role: "assistant", Content: "first answer", Usage: &llm.Usage{PromptTokens: 3, CompletionTokens: 2}},
+		{Role: "assistant", Content: "second answer", FinishReason: llm.FinishReasonLength, Usage: &llm.Usage{PromptTokens: 4, CompletionTokens: 5}},
+	}
+
+	merged := MergeConsecutiveAssistant(messages)
+	if len(merged) != 3 {
+		t.Fatalf("expected the assistant replies to be merged, got %+v", merged)
+	}
+	last := merged[2]
+	if last.Content != "first answer\n\nsecond answer" || last.FinishReason != llm.FinishReasonLength {
+		t.Fatalf("unexpected merged message: %+v", last)
+	}
+	if last.Usage == nil || last.Usage.PromptTokens != 7 || last.Usage.CompletionTokens != 7 {
+		t.Fatalf("expected summed usage, got %+v", last.Usage)
+	}
+	if messages[2].Content != "first answer" {
+		t.Fatalf("merging must not modify the input")
+	}
+}
@@ -0,0 +1,102 @@
+package llm
+
+import (
+	"bytes"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"unicode/utf8"
+)
+
+// maxAttachmentBytes caps the size of a single attached file.
+const maxAttachmentBytes = 256 * 1024
+
+// AttachFiles inlines each file in req.Attachments into the latest user
+// message as a fenced block labeled with the file name. Relative paths
+// resolve against req.ToolRoot and files outside it are rejected; without a
+// tool root nothing can be attached.
+func AttachFiles(req ChatRequest) (ChatRequest, error) {
+	if len(req.Attachments) == 0 {
+		return req, nil
+	}
+	if strings.TrimSpace(req.ToolRoot) == "" {
+		return req, fmt.Errorf("%w: attachments require a configured tool root", ErrInvalidRequest)
+	}
+
+	var blocks strings.Builder
+	for _, path := range req.Attachments {
+		content, err := readAttachment(req.ToolRoot, path)
+		if err != nil {
+			return req, err
+		}
+		fmt.Fprintf(&blocks, "\n\n```%s\n%s\n```", filepath.Base(path), strings.TrimRight(content, "\n"))
+	}
+
+	if strings.TrimSpace(req.Message) != "" {
+		req.Message += blocks.String()
+		return req, nil
+	}
+
+	history := append([]ChatMessage(nil), req.History...)
+	for i := len(history) - 1; i >= 0; i-- {
+		if history[i].Role == "user" {
+			history[i].Content += blocks.String()
+			req.History = history
+			return req, nil
+		}
+	}
+	req.Message = strings.TrimSpace(blocks.String())
+	return req, nil
+}
+
+func readAttachment(root, path string) (string, error) {
+	path = strings.TrimSpace(path)
+	if path == "" {
+		return "", fmt.Errorf("attachment path is empty")
+	}
+
+	if !filepath.IsAbs(path) {
+		path = filepath.Join(root, path)
+	}
+	if err := ensureWithinRoot(root, path); err != nil {
+		return "", err
+	}
+
+	info, err := os.Stat(path)
+	if err != nil {
+		return "", fmt.Errorf("read attachment %s: %w", path, err)
+	}
+	if info.IsDir() {
+		return "", fmt.Errorf("attachment %s is a directory", path)
+	}
+	if info.Size() > maxAttachmentBytes {
+		return "", fmt.Errorf("attachment %s is %d bytes; the limit is %d", path, info.Size(), maxAttachmentBytes)
+	}
+
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return "", fmt.Errorf("read attachment %s: %w", path, err)
+	}
+	if bytes.IndexByte(data, 0) >= 0 || !utf8.Valid(data) {
+		return "", fmt.Errorf("attachment %s is not a text file", path)
+	}
+	return string(data), nil
+}
+
+// ensureWithinRoot rejects paths that resolve outside root, following symlinks.
+func ensureWithinRoot(root, path string) error {
+	resolvedRoot, err := filepath.EvalSymlinks(root)
+	if err != nil {
+		return fmt.Errorf("resolve tool root %s: %w", root, err)
+	}
+	resolved, err := filepath.EvalSymlinks(path)
+	if err != nil {
+		return fmt.Errorf("read attachment %s: %w", path, err)
+	}
+	rel, err := filepath.Rel(resolvedRoot, resolved)
+	if err != nil || rel == ".." || strings.HasPrefix(rel, ".."+string(filepath.Separator)) {
+		return fmt.Errorf("attachment %s is outside the tool root %s", path, root)
+	}
+	return nil
+}
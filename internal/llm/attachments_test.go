@@ -0,0 +1,48 @@
+package llm
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestAttachFilesInlinesTextIntoUserMessage(t *testing.T) {
+	root := t.TempDir()
+	if err := os.WriteFile(filepath.Join(root, "notes.txt"), []byte("line one\nline two\n"), 0o644); err != nil {
+		t.Fatalf("failed to write attachment: %v", err)
+	}
+
+	req, err := AttachFiles(ChatRequest{Message: "Summarize this", Attachments: []string{"notes.txt"}, ToolRoot: root})
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+
+	conversation := ConversationFromRequest(req)
+	last := conversation[len(conversation)-1]
+	if last.Role != "user" || !strings.Contains(last.Content, "```notes.txt\nline one\nline two\n```") {
+		t.Fatalf("expected fenced attachment in user message, got %q", last.Content)
+	}
+}
+
+func TestAttachFilesRejectsBinaryAndOutsideRoot(t *testing.T) {
+	root := t.TempDir()
+	if err := os.WriteFile(filepath.Join(root, "blob.bin"), []byte{0x00, 0x01, 0x02}, 0o644); err != nil {
+		t.Fatalf("failed to write attachment: %v", err)
+	}
+	outside := filepath.Join(t.TempDir(), "secret.txt")
+	if err := os.WriteFile(outside, []byte("nope"), 0o644); err != nil {
+		t.Fatalf("failed to write attachment: %v", err)
+	}
+
+	if _, err := AttachFiles(ChatRequest{Message: "x", Attachments: []string{"blob.bin"}, ToolRoot: root}); err == nil || !strings.Contains(err.Error(), "not a text file") {
+		t.Fatalf("expected binary attachment to be rejected, got %v", err)
+	}
+	if _, err := AttachFiles(ChatRequest{Message: "x", Attachments: []string{outside}, ToolRoot: root}); err == nil || !strings.Contains(err.Error(), "outside the tool root") {
+		t.Fatalf("expected attachment outside the root to be rejected, got %v", err)
+	}
+	if _, err := AttachFiles(ChatRequest{Message: "x", Attachments: []string{outside}}); !errors.Is(err, ErrInvalidRequest) {
+		t.Fatalf("expected attachments without a tool root to be rejected, got %v", err)
+	}
+}
@@ -0,0 +1,110 @@
+package llm
+
+import (
+	"errors"
+	"strings"
+	"sync"
+	"time"
+)
+
+// Load-balancing strategies for a ProviderGroup.
+const (
+	BalanceRoundRobin = "roundRobin"
+	BalanceWeighted   = "weighted"
+)
+
+const defaultBalanceCooldown = 30 * time.Second
+
+// ProviderGroup spreads requests for one provider across equivalent endpoints.
+type ProviderGroup struct {
+	Provider  string   `json:"provider"`
+	Endpoints []string `json:"endpoints"`
+	// Strategy is BalanceRoundRobin (default) or BalanceWeighted.
+	Strategy string `json:"strategy,omitempty"`
+	// Weights pairs with Endpoints for BalanceWeighted; missing weights count as 1.
+	Weights []int `json:"weights,omitempty"`
+	// CooldownSeconds is how long a failed endpoint is skipped.
+	CooldownSeconds int `json:"cooldownSeconds,omitempty"`
+}
+
+func (g ProviderGroup) key() string {
+	return strings.ToLower(g.Provider) + "|" + strings.Join(g.Endpoints, "|")
+}
+
+func (g ProviderGroup) weight(i int) int {
+	if g.Strategy != BalanceWeighted || i >= len(g.Weights) || g.Weights[i] <= 0 {
+		return 1
+	}
+	return g.Weights[i]
+}
+
+func (g ProviderGroup) cooldown() time.Duration {
+	if g.CooldownSeconds > 0 {
+		return time.Duration(g.CooldownSeconds) * time.Second
+	}
+	return defaultBalanceCooldown
+}
+
+// Balancer keeps the selection state for provider groups. It uses smooth
+// weighted round-robin, which degrades to plain round-robin with equal weights.
+type Balancer struct {
+	mu        sync.Mutex
+	current   map[string][]int
+	coolUntil map[string]time.Time
+	now       func() time.Time
+}
+
+func NewBalancer() *Balancer {
+	return &Balancer{
+		current:   map[string][]int{},
+		coolUntil: map[string]time.Time{},
+		now:       time.Now,
+	}
+}
+
+// Pick returns the next endpoint of the group, skipping endpoints that are
+// cooling down after a failure unless every endpoint is.
+func (b *Balancer) Pick(group ProviderGroup) (string, error) {
+	if len(group.Endpoints) == 0 {
+		return "", errors.New("provider group has no endpoints")
+	}
+
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	key := group.key()
+	current := b.current[key]
+	if len(current) != len(group.Endpoints) {
+		current = make([]int, len(group.Endpoints))
+		b.current[key] = current
+	}
+
+	now := b.now()
+	available := make([]bool, len(group.Endpoints))
+	anyAvailable := false
+	for i, endpoint := range group.Endpoints {
+		available[i] = !now.Before(b.coolUntil[NormalizeBase(endpoint)])
+		anyAvailable = anyAvailable || available[i]
+	}
+
+	best, total := -1, 0
+	for i := range group.Endpoints {
+		if anyAvailable && !available[i] {
+			continue
+		}
+		current[i] += group.weight(i)
+		total += group.weight(i)
+		if best == -1 || current[i] > current[best] {
+			best = i
+		}
+	}
+	current[best] -= total
+	return group.Endpoints[best], nil
+}
+
+// MarkFailed skips endpoint for the group's cooldown.
+func (b *Balancer) MarkFailed(group ProviderGroup, endpoint string) {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	b.coolUntil[NormalizeBase(endpoint)] = b.now().Add(group.cooldown())
+}
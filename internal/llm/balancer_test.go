@@ -0,0 +1,44 @@
+package llm
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestBalancerRoundRobinAndCooldown(t *testing.T) {
+	group := ProviderGroup{Provider: "ollama", Endpoints: []string{"http://a", "http://b"}}
+	balancer := NewBalancer()
+
+	var picks []string
+	for i := 0; i < 4; i++ {
+		endpoint, err := balancer.Pick(group)
+		if err != nil {
+			t.Fatalf("Pick returned error: %v", err)
+		}
+		picks = append(picks, endpoint)
+	}
+	if strings.Join(picks, ",") != "http://a,http://b,http://a,http://b" {
+		t.Fatalf("expected alternating picks, got %v", picks)
+	}
+
+	balancer.MarkFailed(group, "http://a")
+	for i := 0; i < 2; i++ {
+		if endpoint, _ := balancer.Pick(group); endpoint != "http://b" {
+			t.Fatalf("expected cooling endpoint to be skipped, got %s", endpoint)
+		}
+	}
+}
+
+func TestBalancerWeighted(t *testing.T) {
+	group := ProviderGroup{Endpoints: []string{"http://a", "http://b"}, Strategy: BalanceWeighted, Weights: []int{3, 1}}
+	balancer := NewBalancer()
+
+	counts := map[string]int{}
+	for i := 0; i < 8; i++ {
+		endpoint, _ := balancer.Pick(group)
+		counts[endpoint]++
+	}
+	if counts["http://a"] != 6 || counts["http://b"] != 2 {
+		t.Fatalf("expected 3:1 split, got %v", counts)
+	}
+}
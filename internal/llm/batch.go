@@ -0,0 +1,61 @@
+package llm
+
+import (
+	"context"
+	"strings"
+	"sync"
+)
+
+// maxBatchCompletions bounds how many batch prompts run at the same time.
+const maxBatchCompletions = 4
+
+// BatchResult is the outcome of one prompt of BatchComplete.
+type BatchResult struct {
+	Prompt string `json:"prompt"`
+	Answer string `json:"answer,omitempty"`
+	Error  string `json:"error,omitempty"`
+}
+
+// BatchComplete answers every prompt as an independent one-shot completion:
+// each starts a fresh conversation with only req's system prompt, without the
+// built-in tool prompt, and offers no tools. Prompts run concurrently, at most
+// maxBatchCompletions at a time, and results keep the order of prompts.
+func BatchComplete(ctx context.Context, req ChatRequest, prompts []string, deps DialogueDependencies) []BatchResult {
+	results := make([]BatchResult, len(prompts))
+	sem := make(chan struct{}, maxBatchCompletions)
+	var wg sync.WaitGroup
+
+	for i, prompt := range prompts {
+		wg.Add(1)
+		go func(i int, prompt string) {
+			defer wg.Done()
+			sem <- struct{}{}
+			defer func() { <-sem }()
+
+			one := req
+			one.SessionID = ""
+			one.Message = prompt
+			one.History = nil
+			one.Tools = nil
+			one.ToolDefs = nil
+
+			var msg ChatMessage
+			var err error
+			if strings.EqualFold(one.Provider, "mock") {
+				msg, err = MockProvider{}.Chat(ctx, one)
+			} else {
+				msg, _, err = NewDialogueLoop(one, nil, deps).Run(ctx, one)
+			}
+
+			results[i] = BatchResult{Prompt: prompt}
+			if err != nil {
+				results[i].Error = RedactError(err).Error()
+				return
+			}
+			results[i].Answer = msg.Content
+		}(i, prompt)
+	}
+
+	wg.Wait()
+	return results
+}
@@ -0,0 +1,70 @@
+package llm
+
+import (
+	"context"
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+)
+
+func TestBatchCompleteAnswersEachPromptInFreshConversation(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var payload struct {
+			Messages []chatCompletionMessage `json:"messages"`
+		}
+		if err := json.NewDecoder(r.Body).Decode(&payload); err != nil {
+			t.Errorf("failed to decode payload: %v", err)
+		}
+		if len(payload.Messages) != 2 || payload.Messages[0].Role != "system" || payload.Messages[0].Content != "Be brief." {
+			t.Errorf("expected the system prompt and one user turn, got %+v", payload.Messages)
+		}
+		answer := "answer to " + payload.Messages[len(payload.Messages)-1].Content
+		_ = json.NewEncoder(w).Encode(map[string]any{
+			"choices": []map[string]any{{"message": map[string]any{"role": "assistant", "content": answer}}},
+		})
+	}))
+	defer server.Close()
+
+	req := ChatRequest{
+		Provider:     "vllm",
+		Endpoint:     server.URL,
+		Model:        "x",
+		SystemPrompt: "Be brief.",
+		History:      []ChatMessage{{Role: "user", Content: "earlier turn"}},
+	}
+	results := BatchComplete(context.Background(), req, []string{"first", "second"}, DialogueDependencies{})
+
+	if len(results) != 2 {
+		t.Fatalf("expected two results, got %+v", results)
+	}
+	for i, want := range []string{"answer to first", "answer to second"} {
+		if results[i].Error != "" || results[i].Answer != want {
+			t.Fatalf("result %d: expected %q, got %+v", i, want, results[i])
+		}
+	}
+}
+
+func TestBatchCompleteSendsNoToolPromptWithoutTools(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var payload struct {
+			Messages []chatCompletionMessage `json:"messages"`
+		}
+		if err := json.NewDecoder(r.Body).Decode(&payload); err != nil {
+			t.Errorf("failed to decode payload: %v", err)
+		}
+		if len(payload.Messages) != 1 || payload.Messages[0].Role != "user" {
+			t.Errorf("expected only the user turn without a configured prompt, got %+v", payload.Messages)
+		}
+		_ = json.NewEncoder(w).Encode(map[string]any{
+			"choices": []map[string]any{{"message": map[string]any{"role": "assistant", "content": "ok"}}},
+		})
+	}))
+	defer server.Close()
+
+	req := ChatRequest{Provider: "vllm", Endpoint: server.URL, Model: "x", Tools: []string{"shell"}}
+	results := BatchComplete(context.Background(), req, []string{"hi"}, DialogueDependencies{})
+	if len(results) != 1 || results[0].Error != "" || results[0].Answer != "ok" {
+		t.Fatalf("expected one answer, got %+v", results)
+	}
+}
@@ -0,0 +1,59 @@
+package llm
+
+import (
+	"regexp"
+	"strconv"
+	"strings"
+)
+
+// Citation is a numbered marker such as "[1]" found in an answer. Marker n
+// refers to the n-th tool result of the dialogue; when that result exists the
+// citation links to it.
+type Citation struct {
+	Marker string `json:"marker"`
+	Index  int    `json:"index"`
+	// ToolCallID and Tool identify the tool result the marker refers to.
+	ToolCallID string `json:"toolCallId,omitempty"`
+	Tool       string `json:"tool,omitempty"`
+	// Source is the first URL in that tool result, if any.
+	Source string `json:"source,omitempty"`
+}
+
+var citationMarker = regexp.MustCompile(`\[(\d{1,3})\]`)
+
+var citationSource = regexp.MustCompile(`https?://[^\s<>"')\]]+`)
+
+// citationsFor extracts the citation markers of answer in order of first
+// appearance and links them to the tool results in messages, which should
+// hold only the turns of the current dialogue.
+func citationsFor(answer string, messages []chatCompletionMessage) []Citation {
+	var results []chatCompletionMessage
+	for _, msg := range messages {
+		if msg.Role == "tool" {
+			results = append(results, msg)
+		}
+	}
+
+	var citations []Citation
+	seen := map[int]bool{}
+	for _, loc := range citationMarker.FindAllStringSubmatchIndex(answer, -1) {
+		// "[1](url)" is a Markdown link, not a citation.
+		if strings.HasPrefix(answer[loc[1]:], "(") {
+			continue
+		}
+		index, err := strconv.Atoi(answer[loc[2]:loc[3]])
+		if err != nil || index == 0 || seen[index] {
+			continue
+		}
+		seen[index] = true
+		citation := Citation{Marker: answer[loc[0]:loc[1]], Index: index}
+		if index <= len(results) {
+			result := results[index-1]
+			citation.ToolCallID = result.ToolCallID
+			citation.Tool = result.Name
+			citation.Source = strings.TrimRight(citationSource.FindString(result.Content), ".,;:")
+		}
+		citations = append(citations, citation)
+	}
+	return citations
+}
@@ -0,0 +1,59 @@
+package llm
+
+import (
+	"fmt"
+	"strings"
+)
+
+// compactionSummaryPrefix starts the system message that replaces compacted turns.
+const compactionSummaryPrefix = "Summary of earlier conversation"
+
+const compactionLineChars = 160
+
+// CompactHistory folds the oldest messages into one summary message so that
+// at most max messages remain. It returns the history unchanged when it is
+// short enough or when a tool chain is still in progress, and it never
+// separates tool results from the assistant turn that requested them.
+func CompactHistory(history []ChatMessage, max int) ([]ChatMessage, bool) {
+	if max < 2 || len(history) <= max || toolChainPending(history) {
+		return history, false
+	}
+
+	split := len(history) - (max - 1)
+	for split < len(history) && strings.EqualFold(history[split].Role, "tool") {
+		split++
+	}
+	if split >= len(history) {
+		return history, false
+	}
+
+	compacted := make([]ChatMessage, 0, len(history)-split+1)
+	compacted = append(compacted, summarizeMessages(history[:split]))
+	compacted = append(compacted, history[split:]...)
+	return compacted, true
+}
+
+// isCompactionSummary reports whether msg is a summary made by CompactHistory.
+func isCompactionSummary(msg ChatMessage) bool {
+	return strings.EqualFold(strings.TrimSpace(msg.Role), "system") &&
+		strings.HasPrefix(strings.TrimSpace(msg.Content), compactionSummaryPrefix)
+}
+
+// toolChainPending reports whether the last turn is waiting on tool output.
+func toolChainPending(history []ChatMessage) bool {
+	last := history[len(history)-1]
+	return strings.EqualFold(last.Role, "tool") || len(last.ToolCalls) > 0
+}
+
+func summarizeMessages(messages []ChatMessage) ChatMessage {
+	builder := &strings.Builder{}
+	fmt.Fprintf(builder, "%s (%d messages):", compactionSummaryPrefix, len(messages))
+	for _, msg := range messages {
+		content := strings.Join(strings.Fields(msg.Content), " ")
+		if content == "" {
+			continue
+		}
+		fmt.Fprintf(builder, "\n- %s: %s", strings.ToLower(strings.TrimSpace(msg.Role)), truncate(content, compactionLineChars))
+	}
+	return ChatMessage{Role: "system", Content: builder.String()}
+}
@@ -0,0 +1,66 @@
+package llm
+
+import (
+	"fmt"
+	"strings"
+	"testing"
+)
+
+func TestCompactHistoryKeepsRecentTurns(t *testing.T) {
+	var history []ChatMessage
+	for i := 0; i < 6; i++ {
+		history = append(history,
+			ChatMessage{Role: "user", Content: fmt.Sprintf("question %d", i)},
+			ChatMessage{Role: "assistant", Content: fmt.Sprintf("answer %d", i)},
+		)
+	}
+
+	compacted, ok := CompactHistory(history, 5)
+	if !ok || len(compacted) != 5 {
+		t.Fatalf("expected compaction to five messages, got %d (%v)", len(compacted), ok)
+	}
+	if compacted[0].Role != "system" || !strings.Contains(compacted[0].Content, "question 0") {
+		t.Fatalf("expected a summary of the oldest turns, got %+v", compacted[0])
+	}
+	if compacted[4].Content != "answer 5" {
+		t.Fatalf("expected the latest turn to be kept, got %+v", compacted[4])
+	}
+
+	pending := append(append([]ChatMessage{}, history...), ChatMessage{Role: "tool", Content: "ls output"})
+	if _, ok := CompactHistory(pending, 5); ok {
+		t.Fatalf("expected no compaction while a tool chain is in progress")
+	}
+}
+
+func TestCompactionSummaryReachesThePayload(t *testing.T) {
+	var history []ChatMessage
+	for i := 0; i < 6; i++ {
+		history = append(history,
+			ChatMessage{Role: "user", Content: fmt.Sprintf("question %d", i)},
+			ChatMessage{Role: "assistant", Content: fmt.Sprintf("answer %d", i)},
+		)
+	}
+	compacted, ok := CompactHistory(history, 5)
+	if !ok {
+		t.Fatal("expected the history to be compacted")
+	}
+
+	req := ChatRequest{History: compacted, Message: "next question", SystemPrompt: "Be terse."}
+	req.History = ConversationFromRequest(req)
+	if first := req.History[0]; !isCompactionSummary(first) {
+		t.Fatalf("expected the summary to survive normalization, got %+v", req.History)
+	}
+
+	messages := requestMessages(req)
+	if len(messages) != 6 || messages[0].Role != "system" {
+		t.Fatalf("expected one system message and five turns, got %+v", messages)
+	}
+	if !strings.HasPrefix(messages[0].Content, "Be terse.") || !strings.Contains(messages[0].Content, "question 0") {
+		t.Fatalf("expected the summary after the system prompt, got %q", messages[0].Content)
+	}
+	for _, msg := range messages[1:] {
+		if msg.Role == "system" {
+			t.Fatalf("expected a single system message, got %+v", messages)
+		}
+	}
+}
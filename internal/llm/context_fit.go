@@ -0,0 +1,171 @@
+package llm
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"strings"
+)
+
+// DefaultFitReserve is the number of tokens kept free for the reply when
+// checking whether a prompt fits.
+const DefaultFitReserve = 1024
+
+// mockContextLength is what the mock provider reports as its context size.
+const mockContextLength = 8192
+
+// FitRequest asks whether a conversation plus the next message fits a model.
+// ContextLength skips probing when the caller already knows it.
+type FitRequest struct {
+	Provider      string        `json:"provider"`
+	Endpoint      string        `json:"endpoint"`
+	APIKey        string        `json:"apiKey"`
+	Model         string        `json:"model"`
+	History       []ChatMessage `json:"history"`
+	Message       string        `json:"message"`
+	ContextLength int           `json:"contextLength,omitempty"`
+	// Reserve is the number of tokens kept free for the reply; zero uses DefaultFitReserve.
+	Reserve int `json:"reserve,omitempty"`
+}
+
+// FitResult reports the numbers behind a fit check. Margin is negative when
+// the prompt does not fit.
+type FitResult struct {
+	Fits            bool `json:"fits"`
+	EstimatedTokens int  `json:"estimatedTokens"`
+	Reserve         int  `json:"reserve"`
+	ContextLength   int  `json:"contextLength"`
+	Margin          int  `json:"margin"`
+}
+
+// WillFit estimates the prompt size and compares it with the model's context
+// length, probing the provider when req.ContextLength is not set.
+func WillFit(ctx context.Context, req FitRequest, client *http.Client) (FitResult, error) {
+	contextLength := req.ContextLength
+	if contextLength <= 0 {
+		probed, err := ContextLength(ctx, ModelsRequest{Provider: req.Provider, Endpoint: req.Endpoint, APIKey: req.APIKey}, req.Model, client)
+		if err != nil {
+			return FitResult{}, err
+		}
+		contextLength = probed
+	}
+
+	reserve := req.Reserve
+	if reserve <= 0 {
+		reserve = DefaultFitReserve
+	}
+	estimated := EstimateTokens(req.Message)
+	for _, msg := range req.History {
+		estimated += EstimateTokens(msg.Content)
+	}
+
+	margin := contextLength - estimated - reserve
+	return FitResult{
+		Fits:            margin >= 0,
+		EstimatedTokens: estimated,
+		Reserve:         reserve,
+		ContextLength:   contextLength,
+		Margin:          margin,
+	}, nil
+}
+
+// ContextLength asks the provider for a model's context length. Ollama reports
+// it through /api/show and vLLM through max_model_len on /v1/models.
+func ContextLength(ctx context.Context, target ModelsRequest, model string, client *http.Client) (int, error) {
+	if client == nil {
+		client = MakeClient()
+	}
+	base := providerBase(target.Provider, target.Endpoint)
+
+	var (
+		length int
+		err    error
+	)
+	switch strings.ToLower(strings.TrimSpace(target.Provider)) {
+	case "mock":
+		return mockContextLength, nil
+	case "ollama":
+		length, err = ollamaContextLength(ctx, base, model, client)
+	case "vllm", "openai":
+		length, err = vllmContextLength(ctx, base, target.APIKey, model, client)
+	default:
+		return 0, fmt.Errorf("unsupported provider: %s", target.Provider)
+	}
+	if err != nil {
+		return 0, err
+	}
+	if length <= 0 {
+		return 0, fmt.Errorf("%s does not report a context length for %s", target.Provider, model)
+	}
+	return length, nil
+}
+
+func ollamaContextLength(ctx context.Context, base, model string, client *http.Client) (int, error) {
+	body, err := json.Marshal(map[string]string{"model": model})
+	if err != nil {
+		return 0, err
+	}
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, base+"/api/show", bytes.NewReader(body))
+	if err != nil {
+		return 0, err
+	}
+	req.Header.Set("Content-Type", "application/json")
+
+	resp, err := client.Do(req)
+	if err != nil {
+		return 0, err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode >= http.StatusBadRequest {
+		return 0, fmt.Errorf("ollama show %s: %w", model, statusError(resp))
+	}
+
+	var decoded struct {
+		ModelInfo map[string]any `json:"model_info"`
+	}
+	if err := json.NewDecoder(resp.Body).Decode(&decoded); err != nil {
+		return 0, err
+	}
+	// The key is prefixed with the architecture, e.g. "llama.context_length".
+	for key, value := range decoded.ModelInfo {
+		if !strings.HasSuffix(key, ".context_length") {
+			continue
+		}
+		if n, ok := value.(float64); ok {
+			return int(n), nil
+		}
+	}
+	return 0, nil
+}
+
+func vllmContextLength(ctx context.Context, base, apiKey, model string, client *http.Client) (int, error) {
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, base+"/v1/models", nil)
+	if err != nil {
+		return 0, err
+	}
+	if strings.TrimSpace(apiKey) != "" {
+		req.Header.Set("Authorization", "Bearer "+apiKey)
+	}
+
+	resp, err := client.Do(req)
+	if err != nil {
+		return 0, err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode >= http.StatusBadRequest {
+		return 0, fmt.Errorf("vllm list models: %w", statusError(resp))
+	}
+
+	var decoded vllmModelsResponse
+	if err := json.NewDecoder(resp.Body).Decode(&decoded); err != nil {
+		return 0, err
+	}
+	for _, entry := range decoded.Data {
+		if entry.ID == model {
+			return entry.MaxModelLen, nil
+		}
+	}
+	return 0, nil
+}
@@ -0,0 +1,54 @@
+package llm
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+)
+
+func TestWillFitProbesOllamaContextLength(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.Path != "/api/show" {
+			t.Fatalf("unexpected path %s", r.URL.Path)
+		}
+		fmt.Fprint(w, `{"model_info":{"general.architecture":"llama","llama.context_length":32768}}`)
+	}))
+	defer server.Close()
+
+	req := FitRequest{
+		Provider: "ollama",
+		Endpoint: server.URL,
+		Model:    "llama3",
+		History:  []ChatMessage{{Role: "user", Content: "hello there"}},
+		Message:  "how are you?",
+	}
+	result, err := WillFit(context.Background(), req, server.Client())
+	if err != nil {
+		t.Fatalf("WillFit returned error: %v", err)
+	}
+
+	estimated := EstimateTokens("hello there") + EstimateTokens("how are you?")
+	want := FitResult{
+		Fits:            true,
+		EstimatedTokens: estimated,
+		Reserve:         DefaultFitReserve,
+		ContextLength:   32768,
+		Margin:          32768 - estimated - DefaultFitReserve,
+	}
+	if result != want {
+		t.Fatalf("WillFit = %+v, want %+v", result, want)
+	}
+}
+
+func TestWillFitReportsOverflowWithKnownContextLength(t *testing.T) {
+	req := FitRequest{Provider: "vllm", Message: "a long prompt", ContextLength: 100, Reserve: 99}
+	result, err := WillFit(context.Background(), req, nil)
+	if err != nil {
+		t.Fatalf("WillFit returned error: %v", err)
+	}
+	if result.Fits || result.Margin >= 0 {
+		t.Fatalf("expected prompt not to fit, got %+v", result)
+	}
+}
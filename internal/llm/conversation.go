@@ -3,7 +3,8 @@ package llm
 import "strings"
 
 // ConversationFromRequest merges prior user/assistant turns with the latest user
-// message, ignoring tool/system entries to keep provider payloads valid.
+// message, ignoring tool/system entries to keep provider payloads valid. A
+// compaction summary is the one system message that is kept.
 func ConversationFromRequest(req ChatRequest) []ChatMessage {
 	history := NormalizeHistory(req.History)
 
@@ -16,11 +17,12 @@ func ConversationFromRequest(req ChatRequest) []ChatMessage {
 
 // NormalizeHistory filters out empty content and non-dialogue roles. It preserves
 // chronological order for the model while avoiding placeholder messages.
+// Compaction summaries are kept so compacted context still reaches the model.
 func NormalizeHistory(history []ChatMessage) []ChatMessage {
 	cleaned := make([]ChatMessage, 0, len(history))
 	for _, msg := range history {
 		role := strings.ToLower(strings.TrimSpace(msg.Role))
-		if role != "user" && role != "assistant" {
+		if role != "user" && role != "assistant" && !isCompactionSummary(msg) {
 			continue
 		}
 		content := strings.TrimSpace(msg.Content)
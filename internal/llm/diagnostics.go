@@ -0,0 +1,87 @@
+package llm
+
+import (
+	"context"
+	"net/http"
+	"slices"
+	"strings"
+	"sync"
+)
+
+// DiagnosticsTarget is one provider connection to diagnose. Model, when set,
+// is checked against the provider's model list.
+type DiagnosticsTarget struct {
+	Target ModelsRequest
+	Model  string
+}
+
+// ProviderDiagnostics is the result of diagnosing one provider connection.
+type ProviderDiagnostics struct {
+	ID          string       `json:"id"`
+	Provider    string       `json:"provider"`
+	Endpoint    string       `json:"endpoint"`
+	Health      HealthStatus `json:"health"`
+	Models      []string     `json:"models,omitempty"`
+	ModelsError string       `json:"modelsError,omitempty"`
+	Model       string       `json:"model,omitempty"`
+	ModelError  string       `json:"modelError,omitempty"`
+	OK          bool         `json:"ok"`
+}
+
+// ValidationReport collects the diagnostics for every configured provider,
+// in the order they were given.
+type ValidationReport struct {
+	Providers []ProviderDiagnostics `json:"providers"`
+	OK        bool                  `json:"ok"`
+}
+
+// RunDiagnostics health-checks every target, lists its models and verifies
+// the selected model, running the targets concurrently. Failures are recorded
+// in the report rather than returned.
+func RunDiagnostics(ctx context.Context, targets []DiagnosticsTarget, client *http.Client) ValidationReport {
+	report := ValidationReport{Providers: make([]ProviderDiagnostics, len(targets)), OK: true}
+
+	var wg sync.WaitGroup
+	for i, target := range targets {
+		wg.Add(1)
+		go func(i int, target DiagnosticsTarget) {
+			defer wg.Done()
+			report.Providers[i] = diagnoseProvider(ctx, target, client)
+		}(i, target)
+	}
+	wg.Wait()
+
+	for _, result := range report.Providers {
+		report.OK = report.OK && result.OK
+	}
+	return report
+}
+
+func diagnoseProvider(ctx context.Context, target DiagnosticsTarget, client *http.Client) ProviderDiagnostics {
+	result := ProviderDiagnostics{
+		ID:       modelsKey(target.Target),
+		Provider: strings.ToLower(strings.TrimSpace(target.Target.Provider)),
+		Endpoint: providerBase(target.Target.Provider, target.Target.Endpoint),
+		Model:    strings.TrimSpace(target.Model),
+	}
+
+	result.Health = CheckHealth(ctx, target.Target, client)
+	if !result.Health.Reachable {
+		result.ModelsError = "skipped: provider is unreachable"
+		return result
+	}
+
+	models, err := ListModels(ctx, target.Target.Provider, target.Target.Endpoint, target.Target.APIKey, client)
+	if err != nil {
+		result.ModelsError = RedactError(err).Error()
+		return result
+	}
+	result.Models = models
+
+	if result.Model != "" && !slices.Contains(models, result.Model) {
+		result.ModelError = "model " + result.Model + " is not available"
+		return result
+	}
+	result.OK = result.Health.Detail == ""
+	return result
+}
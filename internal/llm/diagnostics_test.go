@@ -0,0 +1,36 @@
+package llm
+
+import (
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+)
+
+func TestRunDiagnosticsReportsEachProvider(t *testing.T) {
+	ollama := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		_, _ = w.Write([]byte(`{"models":[{"name":"llama3"}]}`))
+	}))
+	defer ollama.Close()
+
+	vllm := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		_, _ = w.Write([]byte(`{"data":[{"id":"qwen3"}]}`))
+	}))
+	defer vllm.Close()
+
+	report := RunDiagnostics(context.Background(), []DiagnosticsTarget{
+		{Target: ModelsRequest{ID: "local", Provider: "ollama", Endpoint: ollama.URL}, Model: "llama3"},
+		{Target: ModelsRequest{ID: "gpu-box", Provider: "vllm", Endpoint: vllm.URL}, Model: "mistral"},
+	}, nil)
+
+	if len(report.Providers) != 2 || report.OK {
+		t.Fatalf("expected two results with an overall failure, got %+v", report)
+	}
+	local, gpu := report.Providers[0], report.Providers[1]
+	if local.ID != "local" || !local.OK || !local.Health.Reachable || len(local.Models) != 1 {
+		t.Fatalf("expected the ollama provider to pass, got %+v", local)
+	}
+	if gpu.ID != "gpu-box" || gpu.OK || !gpu.Health.Reachable || gpu.ModelError == "" {
+		t.Fatalf("expected the vllm provider to report the missing model, got %+v", gpu)
+	}
+}
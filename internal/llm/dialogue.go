@@ -18,6 +18,25 @@ import (
 
 const braveSearchEndpointDefault = "https://api.search.brave.com/res/v1/web/search"
 
+// defaultIterationLimit is how many tool rounds run before asking to continue.
+const defaultIterationLimit = 30
+
+// maxIterationOverride caps the per-request MaxToolIterationsOverride.
+const maxIterationOverride = 100
+
+// iterationLimitFor returns the request's tool iteration limit: the
+// per-request override, capped at maxIterationOverride, then the configured
+// limit, then defaultIterationLimit.
+func iterationLimitFor(req ChatRequest) int {
+	if req.MaxToolIterationsOverride > 0 {
+		return min(req.MaxToolIterationsOverride, maxIterationOverride)
+	}
+	if req.MaxToolIterations > 0 {
+		return req.MaxToolIterations
+	}
+	return defaultIterationLimit
+}
+
 type dialogueLoop struct {
 	provider     string
 	endpoint     string
@@ -33,6 +52,20 @@ type dialogueLoop struct {
 	prompter     ContinuationPrompter
 	iterationLimit int
 	failureLimit   int
+	sampling       SamplingParams
+	idStyle        IDStyle
+	idPrefix       string
+	parallelTools  bool
+	failFast       bool
+	logLevel       LogLevel
+	toolFallback   bool
+	citations      bool
+	toolSupport    *ToolSupport
+	parallelToolCalls *bool
+	recorder       *TraceRecorder
+	exchanges      []ExchangeRecord
+	appended       []ChatMessage
+	usage          *Usage
 	completionRequester completionRequester
 }
 
@@ -41,6 +74,11 @@ type DialogueDependencies struct {
 	PromptLoader *SystemPromptLoader
 	ToolExecutor ToolExecutor
 	ContinuationPrompter ContinuationPrompter
+	TraceRecorder        *TraceRecorder
+	// ToolAudit records every tool invocation when set.
+	ToolAudit *ToolAuditLog
+	// ToolSupport remembers models that rejected tools; nil remembers nothing.
+	ToolSupport *ToolSupport
 }
 
 type completionRequester func(ctx context.Context, messages []chatCompletionMessage, tools []tools.ToolDefinition) (completionChoice, error)
@@ -48,7 +86,7 @@ type completionRequester func(ctx context.Context, messages []chatCompletionMess
 func NewDialogueLoop(req ChatRequest, sink StreamEventSink, deps DialogueDependencies) *dialogueLoop {
 	client := deps.Client
 	if client == nil {
-		client = MakeClient()
+		client = WithRetries(MakeClientWithTimeout(req.RequestTimeout), req.RequestAttempts)
 	}
 
 	promptLoader := deps.PromptLoader
@@ -62,8 +100,17 @@ func NewDialogueLoop(req ChatRequest, sink StreamEventSink, deps DialogueDepende
 			Client:            client,
 			WebSearchAPIKey:   req.WebSearchAPIKey,
 			WebSearchEndpoint: req.WebSearchEndpoint,
+			ShellOptions:      req.ToolShell,
+			ToolRoot:          req.ToolRoot,
+			CustomTools:       req.CustomTools,
+			OnOutput: func(line string) {
+				if sink != nil {
+					sink.ToolOutput(req.SessionID, line)
+				}
+			},
 		})
 	}
+	toolExecutor = AuditTools(toolExecutor, deps.ToolAudit)
 	prompter := deps.ContinuationPrompter
 	if prompter == nil {
 		prompter = autoContinuePrompter{}
@@ -82,8 +129,19 @@ func NewDialogueLoop(req ChatRequest, sink StreamEventSink, deps DialogueDepende
 		promptLoader: promptLoader,
 		toolExecutor: toolExecutor,
 		prompter:     prompter,
-		iterationLimit: 30,
+		iterationLimit: iterationLimitFor(req),
 		failureLimit:   5,
+		sampling:       req.Sampling,
+		idStyle:        req.IDStyle,
+		idPrefix:       req.IDPrefix,
+		parallelTools:  req.ParallelTools,
+		failFast:       req.FailFastOnToolError,
+		logLevel:       req.LogLevel,
+		toolFallback:   req.ToolFallback,
+		citations:      req.ExtractCitations,
+		toolSupport:    deps.ToolSupport,
+		parallelToolCalls: req.ParallelToolCalls,
+		recorder:       deps.TraceRecorder,
 	}
 	loop.completionRequester = loop.requestCompletion
 
@@ -95,29 +153,56 @@ func (l *dialogueLoop) Run(ctx context.Context, req ChatRequest) (ChatMessage, [
 	start := time.Now()
 	failures := map[string]int{}
 
-	toolDefs := l.toolDefs
+	l.exchanges = nil
+	l.usage = nil
+	defer l.flushExchanges()
 
-	systemPrompt, promptErr := l.promptLoader.Load(runtime.GOOS)
-	if promptErr != nil {
-		err := fmt.Errorf("load system prompt: %w", promptErr)
-		trace = append(trace, DialogueTrace{
-			ID:        newTraceID(),
-			Role:      "assistant",
-			Kind:      "error",
-			Status:    "failed",
-			Content:   err.Error(),
-			CreatedAt: time.Now(),
-		})
-		return ChatMessage{Role: "assistant", Content: err.Error()}, trace, err
+	toolDefs := l.toolDefs
+	if l.toolFallback && l.toolSupport.Unsupported(l.provider, l.endpoint, l.model) {
+		toolDefs = nil
+	}
+
+	// The built-in prompt carries the host OS and the request_fullfilled
+	// instruction, so a configured or request-level prompt is put before it
+	// rather than replacing it. Without tools attached it only confuses the
+	// model, so the configured prompt is sent alone.
+	systemPrompt := strings.TrimSpace(req.SystemPrompt)
+	if len(req.Tools) > 0 || len(l.toolDefs) > 0 {
+		loaded, promptErr := l.promptLoader.Load(runtime.GOOS)
+		if promptErr != nil {
+			err := fmt.Errorf("load system prompt: %w", promptErr)
+			trace = append(trace, DialogueTrace{
+				ID:        l.newTraceID(),
+				Role:      "assistant",
+				Kind:      "error",
+				Status:    "failed",
+				Content:   err.Error(),
+				CreatedAt: time.Now(),
+			})
+			return ChatMessage{Role: "assistant", Content: err.Error()}, trace, err
+		}
+		if systemPrompt != "" {
+			loaded = systemPrompt + "\n\n" + loaded
+		}
+		systemPrompt = loaded
 	}
 
-	messages := []chatCompletionMessage{
-		{Role: "system", Content: systemPrompt},
+	var messages []chatCompletionMessage
+	if systemPrompt != "" {
+		messages = append(messages, chatCompletionMessage{Role: "system", Content: systemPrompt})
 	}
+	messages = withShellContext(messages, req.IncludeShellContext)
 
 	for _, msg := range ConversationFromRequest(req) {
+		if msg.Role == "system" && len(messages) > 0 && messages[0].Role == "system" {
+			// A compaction summary extends the system prompt.
+			messages[0].Content += "\n\n" + msg.Content
+			continue
+		}
 		messages = append(messages, chatCompletionMessage{Role: msg.Role, Content: msg.Content})
 	}
+	base := len(messages)
+	defer func() { l.appended = chatMessagesFrom(messages[base:]) }()
 
 	iterationLimit := l.iterationLimit
 	failureLimit := l.failureLimit
@@ -135,23 +220,32 @@ func (l *dialogueLoop) Run(ctx context.Context, req ChatRequest) (ChatMessage, [
 			}
 			if !continueLoop {
 				stop := fmt.Sprintf("Stopped after %d tool iterations at your request.", iteration)
+				// Record why the transcript ends on tool results.
+				messages = append(messages, chatCompletionMessage{Role: "assistant", Content: stop})
 				trace = append(trace, DialogueTrace{
-					ID:        newTraceID(),
+					ID:        l.newTraceID(),
 					Role:      "assistant",
 					Kind:      "final",
 					Status:    "cancelled",
 					Content:   stop,
 					CreatedAt: time.Now(),
 				})
-				return ChatMessage{Role: "assistant", Content: stop}, trace, nil
+				return ChatMessage{Role: "assistant", Content: stop, FinishReason: FinishReasonToolLimit}, trace, nil
 			}
 			iterationLimit += 10
 		}
 
+		l.emitProgress(iteration, "Thinking...")
+		reconcileToolCallIDs(messages)
 		choice, err := l.completionRequester(ctx, messages, toolDefs)
+		if err != nil && l.toolFallback && len(toolDefs) > 0 && errors.Is(err, ErrToolsUnsupported) {
+			l.toolSupport.markUnsupported(l.provider, l.endpoint, l.model)
+			toolDefs = nil
+			choice, err = l.completionRequester(ctx, messages, toolDefs)
+		}
 		if err != nil {
 			trace = append(trace, DialogueTrace{
-				ID:        newTraceID(),
+				ID:        l.newTraceID(),
 				Role:      "assistant",
 				Kind:      "error",
 				Status:    "failed",
@@ -160,6 +254,7 @@ func (l *dialogueLoop) Run(ctx context.Context, req ChatRequest) (ChatMessage, [
 			})
 			return ChatMessage{Role: "assistant", Content: fmt.Sprintf("Dialog failed: %s", err.Error())}, trace, err
 		}
+		l.usage = addUsage(l.usage, choice.Usage)
 
 		assistantMsg := chatCompletionMessage{
 			Role:      choice.Message.Role,
@@ -170,25 +265,38 @@ func (l *dialogueLoop) Run(ctx context.Context, req ChatRequest) (ChatMessage, [
 
 		if len(choice.Message.ToolCalls) == 0 {
 			trace = append(trace, DialogueTrace{
-				ID:        newTraceID(),
+				ID:        l.newTraceID(),
 				Role:      "assistant",
 				Kind:      "final",
 				Status:    "complete",
 				Content:   assistantMsg.Content,
 				CreatedAt: time.Now(),
 			})
-			return ChatMessage{Role: assistantMsg.Role, Content: assistantMsg.Content}, trace, nil
+			// A truncated answer keeps its reason so the UI can offer to continue it.
+			final := ChatMessage{Role: assistantMsg.Role, Content: assistantMsg.Content, FinishReason: providerFinishReason(choice.FinishReason)}
+			if l.citations {
+				final.Citations = citationsFor(final.Content, messages[base:])
+			}
+			return final, trace, nil
 		}
 
 		if len(choice.Message.ToolCalls) > 0 {
 			l.emitThinkingf("%s", assistantMsg.Content)
 		}
 
-		for _, tc := range choice.Message.ToolCalls {
+		l.emitProgress(iteration, fmt.Sprintf("Running %d tool(s)...", len(choice.Message.ToolCalls)))
+
+		// Fail-fast must stop at the first failed call, so nothing runs ahead.
+		var prefetched []toolOutcome
+		if l.parallelTools && !l.failFast && len(choice.Message.ToolCalls) > 1 {
+			prefetched = l.executeToolsParallel(ctx, choice.Message.ToolCalls)
+		}
+
+		for i, tc := range choice.Message.ToolCalls {
 			argsMap, parseErr := parseArguments(tc.Function.Arguments)
 			callPreview := truncate(tc.Function.Arguments, 200)
 			trace = append(trace, DialogueTrace{
-				ID:        newTraceID(),
+				ID:        l.newTraceID(),
 				Role:      "tool",
 				Kind:      "tool_call",
 				Title:     tc.Function.Name,
@@ -196,6 +304,9 @@ func (l *dialogueLoop) Run(ctx context.Context, req ChatRequest) (ChatMessage, [
 				Content:   fmt.Sprintf("Calling %s with %s", tc.Function.Name, truncate(tc.Function.Arguments, 320)),
 				CreatedAt: time.Now(),
 			})
+			if l.sink != nil {
+				l.sink.ToolCall(l.sessionID, tc.Function.Name)
+			}
 			if callPreview != "" {
 				l.emitThinkingf("Running %s (%s)", tc.Function.Name, callPreview)
 			} else {
@@ -205,19 +316,23 @@ func (l *dialogueLoop) Run(ctx context.Context, req ChatRequest) (ChatMessage, [
 			if tc.Function.Name == "request_fullfilled" {
 				summary := contentFromRequestFulfilled(argsMap, assistantMsg.Content)
 				trace = append(trace, DialogueTrace{
-					ID:        newTraceID(),
+					ID:        l.newTraceID(),
 					Role:      "assistant",
 					Kind:      "final",
 					Status:    "complete",
 					Content:   summary,
 					CreatedAt: time.Now(),
 				})
-				return ChatMessage{Role: "assistant", Content: summary}, trace, nil
+				final := ChatMessage{Role: "assistant", Content: summary}
+				if l.citations {
+					final.Citations = citationsFor(summary, messages[base:])
+				}
+				return final, trace, nil
 			}
 
 			if parseErr != nil {
 				trace = append(trace, DialogueTrace{
-					ID:        newTraceID(),
+					ID:        l.newTraceID(),
 					Role:      "tool",
 					Kind:      "tool_result",
 					Title:     tc.Function.Name,
@@ -229,9 +344,14 @@ func (l *dialogueLoop) Run(ctx context.Context, req ChatRequest) (ChatMessage, [
 				continue
 			}
 
-			result, status := l.toolExecutor.Execute(ctx, tc.Function.Name, argsMap)
+			var result, status string
+			if prefetched != nil && prefetched[i].ok {
+				result, status = prefetched[i].result, prefetched[i].status
+			} else {
+				result, status = l.toolExecutor.Execute(ctx, tc.Function.Name, argsMap)
+			}
 			trace = append(trace, DialogueTrace{
-				ID:        newTraceID(),
+				ID:        l.newTraceID(),
 				Role:      "tool",
 				Kind:      "tool_result",
 				Title:     tc.Function.Name,
@@ -241,6 +361,17 @@ func (l *dialogueLoop) Run(ctx context.Context, req ChatRequest) (ChatMessage, [
 			})
 			l.emitThinkingf("%s %s", tc.Function.Name, status)
 
+			if status == "error" && l.failFast {
+				messages = append(messages, chatCompletionMessage{
+					Role:       "tool",
+					Name:       tc.Function.Name,
+					Content:    result,
+					ToolCallID: tc.ID,
+				})
+				stop := fmt.Sprintf("Stopped because the %s tool failed: %s", tc.Function.Name, result)
+				return ChatMessage{Role: "assistant", Content: stop}, trace, fmt.Errorf("%w: %s: %s", ErrToolFailed, tc.Function.Name, result)
+			}
+
 			if status == "error" {
 				key := fmt.Sprintf("%s|%s", tc.Function.Name, tc.Function.Arguments)
 				failures[key]++
@@ -259,7 +390,7 @@ func (l *dialogueLoop) Run(ctx context.Context, req ChatRequest) (ChatMessage, [
 					if !continueLoop {
 						stop := fmt.Sprintf("Stopped after repeated %s tool failures at your request.", tc.Function.Name)
 						trace = append(trace, DialogueTrace{
-							ID:        newTraceID(),
+							ID:        l.newTraceID(),
 							Role:      "assistant",
 							Kind:      "final",
 							Status:    "cancelled",
@@ -283,7 +414,7 @@ func (l *dialogueLoop) Run(ctx context.Context, req ChatRequest) (ChatMessage, [
 	}
 
 	trace = append(trace, DialogueTrace{
-		ID:        newTraceID(),
+		ID:        l.newTraceID(),
 		Role:      "assistant",
 		Kind:      "timeout",
 		Status:    "timeout",
@@ -296,17 +427,23 @@ func (l *dialogueLoop) Run(ctx context.Context, req ChatRequest) (ChatMessage, [
 }
 
 func (l *dialogueLoop) requestCompletion(ctx context.Context, messages []chatCompletionMessage, tools []tools.ToolDefinition) (completionChoice, error) {
+	if err := requireAPIKey(l.provider, l.apiKey); err != nil {
+		return completionChoice{}, err
+	}
 	payload := completionRequest{
-		Model:       l.model,
-		Messages:    messages,
-		Stream:      false,
-		Tools:       tools,
-		Temperature: 0,
+		Model:          l.model,
+		Messages:       messages,
+		Stream:         false,
+		Tools:          tools,
+		samplingFields: samplingFor(l.provider, l.sampling),
 	}
 
 	// Avoid sending tool_choice when no tools are available; vLLM rejects that with 400.
 	if len(tools) > 0 {
 		payload.ToolChoice = "auto"
+		if l.provider != "ollama" {
+			payload.ParallelToolCalls = l.parallelToolCalls
+		}
 	}
 
 	body, err := json.Marshal(payload)
@@ -315,34 +452,43 @@ func (l *dialogueLoop) requestCompletion(ctx context.Context, messages []chatCom
 	}
 
 	url := l.completionsURL()
-	log.Printf("[%s] Sending dialogue completion request to %s with model %s", time.Now().Format(time.RFC3339), url, l.model)
+	log.Printf("[%s] Sending dialogue completion request to %s with model %s", time.Now().Format(time.RFC3339), RedactURL(url), l.model)
+	logRequestBody(l.logLevel, l.provider, url, body)
 	req, err := http.NewRequestWithContext(ctx, http.MethodPost, url, bytes.NewReader(body))
 	if err != nil {
 		return completionChoice{}, err
 	}
 	req.Header.Set("Content-Type", "application/json")
+	req.Header.Set("Accept", acceptJSON)
 	if strings.TrimSpace(l.apiKey) != "" {
 		req.Header.Set("Authorization", "Bearer "+l.apiKey)
 	}
 
+	started := time.Now()
 	resp, err := l.client.Do(req)
 	if err != nil {
-		return completionChoice{}, fmt.Errorf("%s completion request failed: %w", strings.ToUpper(l.provider), err)
+		err = fmt.Errorf("%s completion request failed: %w", strings.ToUpper(l.provider), err)
+		l.recordExchange(url, body, started, 0, "", err)
+		return completionChoice{}, err
 	}
 	defer resp.Body.Close()
-	log.Printf("[%s] Received dialogue completion response", time.Now().Format(time.RFC3339))
+	log.Printf("[%s] Received dialogue completion response: %s", time.Now().Format(time.RFC3339), resp.Status)
 
 	rawBody, err := io.ReadAll(resp.Body)
 	if err != nil {
 		return completionChoice{}, fmt.Errorf("%s completion read failed: %w", strings.ToUpper(l.provider), err)
 	}
+	l.recordExchange(url, body, started, resp.StatusCode, string(rawBody), nil)
 
 	if resp.StatusCode >= http.StatusBadRequest {
 		detail := strings.TrimSpace(string(rawBody))
 		if detail == "" {
 			detail = resp.Status
 		}
-		return completionChoice{}, fmt.Errorf("%s completion returned %s: %s", strings.ToUpper(l.provider), resp.Status, truncate(detail, 512))
+		if len(tools) > 0 && isToolsRejection(detail) {
+			return completionChoice{}, fmt.Errorf("%s completion returned %s: %w: %s", strings.ToUpper(l.provider), resp.Status, ErrToolsUnsupported, truncate(detail, 512))
+		}
+		return completionChoice{}, fmt.Errorf("%s completion returned %w: %s", strings.ToUpper(l.provider), statusError(resp), truncate(detail, 512))
 	}
 
 	if l.provider == "ollama" {
@@ -365,11 +511,14 @@ func (l *dialogueLoop) requestCompletion(ctx context.Context, messages []chatCom
 		return completionChoice{}, errors.New("no choices returned from provider")
 	}
 
-	return decoded.Choices[0], nil
+	choice := decoded.Choices[0]
+	choice.Message.Role = assistantRole(l.provider, choice.Message.Role)
+	choice.Usage = decoded.Usage.toUsage()
+	return choice, nil
 }
 
 func (l *dialogueLoop) completionsURL() string {
-	base := NormalizeBase(l.endpoint)
+	base := providerBase(l.provider, l.endpoint)
 	switch l.provider {
 	case "ollama":
 		return base + "/api/chat"
@@ -389,12 +538,10 @@ func decodeOllamaCompletion(body []byte) (completionChoice, error) {
 	}
 
 	choice := completionChoice{}
-	choice.Message.Role = decoded.Message.Role
-	if strings.TrimSpace(choice.Message.Role) == "" {
-		choice.Message.Role = "assistant"
-	}
+	choice.Message.Role = assistantRole("ollama", decoded.Message.Role)
 	choice.Message.Content = decoded.Message.Content
 	choice.Message.ToolCalls = convertOllamaChatToolCalls(decoded.Message.ToolCalls)
+	choice.Usage = ollamaUsage(decoded.PromptEvalCount, decoded.EvalCount)
 
 	return choice, nil
 }
@@ -432,7 +579,7 @@ func (l *dialogueLoop) handleContinuation(ctx context.Context, trace *[]Dialogue
 
 	if trace != nil {
 		*trace = append(*trace, DialogueTrace{
-			ID:        newTraceID(),
+			ID:        l.newTraceID(),
 			Role:      "assistant",
 			Kind:      "continuation",
 			Title:     "Approval required",
@@ -455,7 +602,7 @@ func (l *dialogueLoop) handleContinuation(ctx context.Context, trace *[]Dialogue
 			message = "User stopped generation."
 		}
 		*trace = append(*trace, DialogueTrace{
-			ID:        newTraceID(),
+			ID:        l.newTraceID(),
 			Role:      "assistant",
 			Kind:      "continuation",
 			Title:     "Approval resolved",
@@ -468,6 +615,35 @@ func (l *dialogueLoop) handleContinuation(ctx context.Context, trace *[]Dialogue
 	return decision == ContinuationDecisionContinue, nil
 }
 
+// Messages returns the messages appended during the last Run: assistant
+// turns, including those requesting tools, and tool results.
+func (l *dialogueLoop) Messages() []ChatMessage {
+	return l.appended
+}
+
+// Usage returns the provider-reported token counts summed over the last
+// Run's completions, or nil when the provider reported none.
+func (l *dialogueLoop) Usage() *Usage {
+	return l.usage
+}
+
+func chatMessagesFrom(messages []chatCompletionMessage) []ChatMessage {
+	out := make([]ChatMessage, 0, len(messages))
+	for _, msg := range messages {
+		out = append(out, ChatMessage{
+			Role:       msg.Role,
+			Content:    msg.Content,
+			ToolCalls:  convertChatToolCalls(msg.ToolCalls),
+			ToolCallID: msg.ToolCallID,
+		})
+	}
+	return out
+}
+
+func (l *dialogueLoop) newTraceID() string {
+	return WithNamespace(l.idPrefix, NewID(l.idStyle, "trace"))
+}
+
 func (l *dialogueLoop) emitThinkingf(format string, args ...any) {
 	if l.sink == nil || l.sessionID == "" {
 		return
@@ -482,3 +658,12 @@ func (l *dialogueLoop) emitThinkingf(format string, args ...any) {
 
 	l.sink.ThinkingUpdate(l.sessionID, chunk)
 }
+
+// emitProgress reports which step of the tool loop is running; iterations are
+// reported one-based.
+func (l *dialogueLoop) emitProgress(iteration int, message string) {
+	if l.sink == nil || l.sessionID == "" {
+		return
+	}
+	l.sink.Progress(l.sessionID, iteration+1, message)
+}
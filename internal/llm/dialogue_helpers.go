@@ -2,22 +2,58 @@ package llm
 
 import (
 	"encoding/json"
-	"fmt"
+	"log"
 	"strings"
-	"time"
 )
 
+// assistantRole coerces the role of a completion message to "assistant".
+// Some servers omit the role or echo another one; keeping it would put a
+// second user or system turn into the transcript.
+func assistantRole(provider, role string) string {
+	trimmed := strings.ToLower(strings.TrimSpace(role))
+	if trimmed != "" && trimmed != "assistant" {
+		log.Printf("[%s] unexpected response role %q; treating it as assistant", provider, role)
+	}
+	return "assistant"
+}
+
+// parseArguments decodes the arguments of a tool call. Some models encode the
+// JSON object twice, so a JSON string holding an object is unwrapped once.
 func parseArguments(raw string) (map[string]any, error) {
 	if strings.TrimSpace(raw) == "" {
 		return map[string]any{}, nil
 	}
 	var out map[string]any
-	if err := json.Unmarshal([]byte(raw), &out); err != nil {
+	err := json.Unmarshal([]byte(raw), &out)
+	if err == nil {
+		return out, nil
+	}
+	var inner string
+	if json.Unmarshal([]byte(raw), &inner) != nil {
+		return nil, err
+	}
+	if err := json.Unmarshal([]byte(inner), &out); err != nil {
 		return nil, err
 	}
 	return out, nil
 }
 
+// NormalizeToolArguments shows how the dialogue loop interprets a raw tool
+// argument value: strings holding a JSON object, even a double-encoded one,
+// become that object; other strings and all other values pass through
+// unchanged.
+func NormalizeToolArguments(raw any) any {
+	text, ok := raw.(string)
+	if !ok {
+		return raw
+	}
+	args, err := parseArguments(text)
+	if err != nil {
+		return raw
+	}
+	return args
+}
+
 func contentFromRequestFulfilled(args map[string]any, fallback string) string {
 	if val, ok := args["summary"].(string); ok && strings.TrimSpace(val) != "" {
 		return val
@@ -37,7 +73,3 @@ func truncate(text string, max int) string {
 	}
 	return text[:max-3] + "..."
 }
-
-func newTraceID() string {
-	return fmt.Sprintf("trace-%d", time.Now().UnixNano())
-}
@@ -3,10 +3,20 @@ package llm
 import (
 	"context"
 	"encoding/json"
+	"errors"
+	"fmt"
 	"net/http"
 	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"reflect"
+	"runtime"
 	"strings"
+	"sync"
 	"testing"
+	"time"
+
+	"shell-werk/internal/tools"
 )
 
 func TestWebSearchToolSuccess(t *testing.T) {
@@ -69,6 +79,31 @@ func TestWebSearchToolMissingKey(t *testing.T) {
 	}
 }
 
+func TestShellToolStreamsOutputLines(t *testing.T) {
+	if runtime.GOOS == "windows" {
+		t.Skip("uses a POSIX shell")
+	}
+
+	var lines []string
+	executor := NewToolExecutor(ToolExecutorConfig{
+		OnOutput: func(line string) { lines = append(lines, line) },
+	})
+
+	result, status := executor.Execute(context.Background(), "shell", map[string]any{
+		"command": "sh",
+		"args":    []any{"-c", "echo one; echo two"},
+	})
+	if status != "done" {
+		t.Fatalf("expected status done, got %s (%s)", status, result)
+	}
+	if strings.Join(lines, "|") != "one|two" {
+		t.Fatalf("expected both lines to be streamed, got %q", lines)
+	}
+	if result != "one\ntwo\n" {
+		t.Fatalf("expected the full output to be returned, got %q", result)
+	}
+}
+
 func TestDecodeOllamaCompletion(t *testing.T) {
 	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 		if r.URL.Path != "/api/chat" {
@@ -113,3 +148,684 @@ func TestDecodeOllamaCompletion(t *testing.T) {
 		t.Fatalf("unexpected tool call arguments: %s", choice.Message.ToolCalls[0].Function.Arguments)
 	}
 }
+
+func TestRequestCompletionTreatsEmptyRoleAsAssistant(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		fmt.Fprint(w, `{"message":{"role":"","content":"hello"},"done":true}`)
+	}))
+	defer server.Close()
+
+	loop := &dialogueLoop{provider: "ollama", endpoint: server.URL, model: "x", client: server.Client()}
+	choice, err := loop.requestCompletion(context.Background(), []chatCompletionMessage{{Role: "user", Content: "hi"}}, nil)
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if choice.Message.Role != "assistant" || choice.Message.Content != "hello" {
+		t.Fatalf("expected an assistant turn with the content, got %+v", choice.Message)
+	}
+}
+
+func TestRequestCompletionParsesUsage(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		fmt.Fprint(w, `{"choices":[{"message":{"role":"assistant","content":"hi"}}],"usage":{"prompt_tokens":21,"completion_tokens":4,"total_tokens":25}}`)
+	}))
+	defer server.Close()
+
+	loop := &dialogueLoop{provider: "vllm", endpoint: server.URL, model: "x", client: server.Client()}
+	choice, err := loop.requestCompletion(context.Background(), []chatCompletionMessage{{Role: "user", Content: "hi"}}, nil)
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if choice.Usage == nil || *choice.Usage != (Usage{PromptTokens: 21, CompletionTokens: 4}) {
+		t.Fatalf("expected provider usage, got %+v", choice.Usage)
+	}
+}
+
+func TestDialogueRequestSystemPromptPrecedesBuiltIn(t *testing.T) {
+	var received completionRequest
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if err := json.NewDecoder(r.Body).Decode(&received); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		_ = json.NewEncoder(w).Encode(map[string]any{
+			"choices": []map[string]any{
+				{"message": map[string]any{"role": "assistant", "content": "done"}},
+			},
+		})
+	}))
+	defer server.Close()
+
+	promptPath := filepath.Join(t.TempDir(), "prompt.txt")
+	if err := os.WriteFile(promptPath, []byte("Configured prompt for {{HOST_OS}}"), 0o644); err != nil {
+		t.Fatalf("failed to write prompt file: %v", err)
+	}
+
+	req := ChatRequest{
+		Provider:     "vllm",
+		Endpoint:     server.URL,
+		Model:        "x",
+		Message:      "hi",
+		Tools:        []string{"shell"},
+		SystemPrompt: "Only answer in haiku.",
+	}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{
+		Client:       server.Client(),
+		PromptLoader: NewSystemPromptLoader(promptPath),
+	})
+	if _, _, err := loop.Run(context.Background(), req); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+
+	if len(received.Messages) != 2 {
+		t.Fatalf("expected system and user messages, got %+v", received.Messages)
+	}
+	want := "Only answer in haiku.\n\nConfigured prompt for " + runtime.GOOS
+	if received.Messages[0].Role != "system" || received.Messages[0].Content != want {
+		t.Fatalf("expected the request prompt before the loaded one, got %+v", received.Messages[0])
+	}
+}
+
+type slowEchoExecutor struct {
+	delay time.Duration
+}
+
+func (e slowEchoExecutor) Execute(_ context.Context, _ string, args map[string]any) (string, string) {
+	time.Sleep(e.delay)
+	text, _ := args["text"].(string)
+	return text, "success"
+}
+
+func TestDialogueExtractsCitationsOfToolResults(t *testing.T) {
+	req := ChatRequest{Provider: "vllm", Model: "x", Message: "where are the docs?", ExtractCitations: true}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{ToolExecutor: slowEchoExecutor{}})
+	calls := 0
+	loop.completionRequester = func(context.Context, []chatCompletionMessage, []tools.ToolDefinition) (completionChoice, error) {
+		calls++
+		choice := completionChoice{}
+		choice.Message.Role = "assistant"
+		if calls == 1 {
+			choice.Message.ToolCalls = []chatToolCall{
+				{ID: "call-1", Type: "function", Function: toolCallFunction{Name: "echo", Arguments: `{"text":"Docs: https://docs.example.com/guide."}`}},
+			}
+			return choice, nil
+		}
+		choice.Message.Content = "The guide covers it [1]; see also [the site](https://example.com)."
+		return choice, nil
+	}
+
+	msg, _, err := loop.Run(context.Background(), req)
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	want := []Citation{{Marker: "[1]", Index: 1, ToolCallID: "call-1", Tool: "echo", Source: "https://docs.example.com/guide"}}
+	if !reflect.DeepEqual(msg.Citations, want) {
+		t.Fatalf("expected citations %+v, got %+v", want, msg.Citations)
+	}
+}
+
+func TestDialogueCitesToolResultsInRequestFulfilledSummary(t *testing.T) {
+	req := ChatRequest{Provider: "vllm", Model: "x", Message: "where are the docs?", ExtractCitations: true}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{ToolExecutor: slowEchoExecutor{}})
+	calls := 0
+	loop.completionRequester = func(context.Context, []chatCompletionMessage, []tools.ToolDefinition) (completionChoice, error) {
+		calls++
+		choice := completionChoice{}
+		choice.Message.Role = "assistant"
+		if calls == 1 {
+			choice.Message.ToolCalls = []chatToolCall{
+				{ID: "call-1", Type: "function", Function: toolCallFunction{Name: "echo", Arguments: `{"text":"Docs: https://docs.example.com/guide"}`}},
+			}
+			return choice, nil
+		}
+		choice.Message.ToolCalls = []chatToolCall{
+			{ID: "call-2", Type: "function", Function: toolCallFunction{Name: "request_fullfilled", Arguments: `{"summary":"The guide covers it [1]."}`}},
+		}
+		return choice, nil
+	}
+
+	msg, _, err := loop.Run(context.Background(), req)
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	want := []Citation{{Marker: "[1]", Index: 1, ToolCallID: "call-1", Tool: "echo", Source: "https://docs.example.com/guide"}}
+	if msg.Content != "The guide covers it [1]." || !reflect.DeepEqual(msg.Citations, want) {
+		t.Fatalf("expected the summary with citations %+v, got %+v", want, msg)
+	}
+}
+
+func TestDialogueRecordsToolAudit(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "tool_audit.json")
+	req := ChatRequest{Provider: "vllm", Model: "x", Message: "hi"}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{
+		ToolExecutor: slowEchoExecutor{},
+		ToolAudit:    NewToolAuditLog(path),
+	})
+	calls := 0
+	loop.completionRequester = func(context.Context, []chatCompletionMessage, []tools.ToolDefinition) (completionChoice, error) {
+		calls++
+		choice := completionChoice{}
+		choice.Message.Role = "assistant"
+		if calls == 1 {
+			choice.Message.ToolCalls = []chatToolCall{
+				{ID: "a", Type: "function", Function: toolCallFunction{Name: "echo", Arguments: `{"text":"hi"}`}},
+			}
+			return choice, nil
+		}
+		choice.Message.Content = "done"
+		return choice, nil
+	}
+
+	if _, _, err := loop.Run(context.Background(), req); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+
+	entries := NewToolAuditLog(path).Entries()
+	if len(entries) != 1 {
+		t.Fatalf("expected one persisted audit entry, got %+v", entries)
+	}
+	if entry := entries[0]; entry.Tool != "echo" || entry.Arguments != `{"text":"hi"}` || entry.Result != "hi" || entry.Time.IsZero() {
+		t.Fatalf("unexpected audit entry: %+v", entry)
+	}
+	if info, err := os.Stat(path); err != nil || (runtime.GOOS != "windows" && info.Mode().Perm() != 0o600) {
+		t.Fatalf("expected an owner-only audit file, got %v (%v)", info, err)
+	}
+}
+
+func TestDialogueParallelToolsPreserveOrder(t *testing.T) {
+	req := ChatRequest{Provider: "vllm", Model: "x", Message: "hi", ParallelTools: true}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{
+		ToolExecutor: slowEchoExecutor{delay: 200 * time.Millisecond},
+	})
+
+	var followUp []chatCompletionMessage
+	calls := 0
+	loop.completionRequester = func(_ context.Context, messages []chatCompletionMessage, _ []tools.ToolDefinition) (completionChoice, error) {
+		calls++
+		choice := completionChoice{}
+		choice.Message.Role = "assistant"
+		if calls == 1 {
+			choice.Message.ToolCalls = []chatToolCall{
+				{ID: "a", Type: "function", Function: toolCallFunction{Name: "web_search", Arguments: `{"text":"first"}`}},
+				{ID: "b", Type: "function", Function: toolCallFunction{Name: "web_search", Arguments: `{"text":"second"}`}},
+			}
+			return choice, nil
+		}
+		followUp = messages
+		choice.Message.Content = "done"
+		return choice, nil
+	}
+
+	start := time.Now()
+	if _, _, err := loop.Run(context.Background(), req); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if elapsed := time.Since(start); elapsed >= 400*time.Millisecond {
+		t.Fatalf("expected tools to run concurrently, took %s", elapsed)
+	}
+
+	var results []string
+	for _, msg := range followUp {
+		if msg.Role == "tool" {
+			results = append(results, msg.ToolCallID+"="+msg.Content)
+		}
+	}
+	if strings.Join(results, ",") != "a=first,b=second" {
+		t.Fatalf("expected tool results in call order, got %v", results)
+	}
+}
+
+func TestReconcileToolCallIDsFixesMismatches(t *testing.T) {
+	messages := []chatCompletionMessage{
+		{Role: "user", Content: "check"},
+		{Role: "assistant", ToolCalls: []chatToolCall{
+			{ID: "call-a", Function: toolCallFunction{Name: "shell"}},
+			{ID: "call-b", Function: toolCallFunction{Name: "web_search"}},
+		}},
+		{Role: "tool", Name: "web_search", ToolCallID: "stale", Content: "results"},
+		{Role: "tool", Name: "shell", ToolCallID: "call-a", Content: "ok"},
+	}
+	if fixes := reconcileToolCallIDs(messages); fixes != 1 {
+		t.Fatalf("expected one correction, got %d", fixes)
+	}
+	if messages[2].ToolCallID != "call-b" || messages[3].ToolCallID != "call-a" {
+		t.Fatalf("expected tool messages to reference their calls, got %+v", messages[2:])
+	}
+}
+
+func TestDialogueAssignsMissingToolCallIDs(t *testing.T) {
+	req := ChatRequest{Provider: "vllm", Model: "x", Message: "hi"}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{ToolExecutor: slowEchoExecutor{}})
+
+	var followUp []chatCompletionMessage
+	calls := 0
+	loop.completionRequester = func(_ context.Context, messages []chatCompletionMessage, _ []tools.ToolDefinition) (completionChoice, error) {
+		calls++
+		choice := completionChoice{}
+		choice.Message.Role = "assistant"
+		if calls == 1 {
+			choice.Message.ToolCalls = []chatToolCall{
+				{Type: "function", Function: toolCallFunction{Name: "echo", Arguments: `{"text":"hi"}`}},
+			}
+			return choice, nil
+		}
+		followUp = append([]chatCompletionMessage{}, messages...)
+		choice.Message.Content = "done"
+		return choice, nil
+	}
+
+	if _, _, err := loop.Run(context.Background(), req); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	assistant, tool := followUp[len(followUp)-2], followUp[len(followUp)-1]
+	if len(assistant.ToolCalls) != 1 || assistant.ToolCalls[0].ID == "" {
+		t.Fatalf("expected a generated tool call ID, got %+v", assistant)
+	}
+	if tool.ToolCallID != assistant.ToolCalls[0].ID {
+		t.Fatalf("expected the tool result to reference %q, got %+v", assistant.ToolCalls[0].ID, tool)
+	}
+}
+
+func TestDialogueEmitsProgressPerIteration(t *testing.T) {
+	req := ChatRequest{SessionID: "s1", Provider: "vllm", Model: "x", Message: "hi"}
+	sink := &mockSink{}
+	loop := NewDialogueLoop(req, sink, DialogueDependencies{
+		ToolExecutor: slowEchoExecutor{},
+	})
+
+	calls := 0
+	loop.completionRequester = func(_ context.Context, _ []chatCompletionMessage, _ []tools.ToolDefinition) (completionChoice, error) {
+		calls++
+		choice := completionChoice{}
+		choice.Message.Role = "assistant"
+		if calls <= 2 {
+			choice.Message.ToolCalls = []chatToolCall{
+				{ID: "a", Type: "function", Function: toolCallFunction{Name: "echo", Arguments: `{"text":"hi"}`}},
+			}
+			return choice, nil
+		}
+		choice.Message.Content = "done"
+		return choice, nil
+	}
+
+	if _, _, err := loop.Run(context.Background(), req); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+
+	want := []string{
+		"1:Thinking...", "1:Running 1 tool(s)...",
+		"2:Thinking...", "2:Running 1 tool(s)...",
+		"3:Thinking...",
+	}
+	if strings.Join(sink.progress, "|") != strings.Join(want, "|") {
+		t.Fatalf("expected progress %v, got %v", want, sink.progress)
+	}
+}
+
+func TestDialogueToolErrorPolicies(t *testing.T) {
+	unknownToolThenAnswer := func(seen *[]string) completionRequester {
+		calls := 0
+		return func(_ context.Context, messages []chatCompletionMessage, _ []tools.ToolDefinition) (completionChoice, error) {
+			calls++
+			choice := completionChoice{}
+			choice.Message.Role = "assistant"
+			if calls == 1 {
+				choice.Message.ToolCalls = []chatToolCall{
+					{ID: "call-1", Type: "function", Function: toolCallFunction{Name: "no_such_tool", Arguments: `{}`}},
+				}
+				return choice, nil
+			}
+			for _, msg := range messages {
+				if msg.Role == "tool" {
+					*seen = append(*seen, msg.Content)
+				}
+			}
+			choice.Message.Content = "Sorry, that tool does not exist."
+			return choice, nil
+		}
+	}
+
+	t.Run("recovers by default", func(t *testing.T) {
+		req := ChatRequest{SessionID: "s1", Provider: "vllm", Model: "x", Message: "hi"}
+		loop := NewDialogueLoop(req, nil, DialogueDependencies{})
+		var seen []string
+		loop.completionRequester = unknownToolThenAnswer(&seen)
+
+		msg, _, err := loop.Run(context.Background(), req)
+		if err != nil {
+			t.Fatalf("unexpected error: %v", err)
+		}
+		if len(seen) != 1 || !strings.Contains(seen[0], "not implemented") {
+			t.Fatalf("expected the tool error to reach the model, got %q", seen)
+		}
+		if !strings.Contains(msg.Content, "Sorry") {
+			t.Fatalf("expected the model's recovery answer, got %+v", msg)
+		}
+	})
+
+	t.Run("fails fast when configured", func(t *testing.T) {
+		req := ChatRequest{SessionID: "s1", Provider: "vllm", Model: "x", Message: "hi", FailFastOnToolError: true}
+		loop := NewDialogueLoop(req, nil, DialogueDependencies{})
+		var seen []string
+		loop.completionRequester = unknownToolThenAnswer(&seen)
+
+		_, _, err := loop.Run(context.Background(), req)
+		if !errors.Is(err, ErrToolFailed) {
+			t.Fatalf("expected ErrToolFailed, got %v", err)
+		}
+		if len(seen) != 0 {
+			t.Fatalf("expected no further completion after the failure, got %q", seen)
+		}
+		appended := loop.Messages()
+		if last := appended[len(appended)-1]; last.Role != "tool" || !strings.Contains(last.Content, "not implemented") {
+			t.Fatalf("expected the failed tool result in the transcript, got %+v", last)
+		}
+	})
+}
+
+// recordingExecutor echoes args["text"] and records which texts ran; texts in
+// fail report an error.
+type recordingExecutor struct {
+	mu   sync.Mutex
+	ran  []string
+	fail map[string]bool
+}
+
+func (e *recordingExecutor) Execute(_ context.Context, _ string, args map[string]any) (string, string) {
+	text, _ := args["text"].(string)
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	e.ran = append(e.ran, text)
+	if e.fail[text] {
+		return "boom", "error"
+	}
+	return text, "success"
+}
+
+func (e *recordingExecutor) executed() []string {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+	return append([]string(nil), e.ran...)
+}
+
+func TestDialogueFailFastRunsNoToolsAheadInParallel(t *testing.T) {
+	req := ChatRequest{Provider: "vllm", Model: "x", Message: "hi", ParallelTools: true, FailFastOnToolError: true}
+	executor := &recordingExecutor{fail: map[string]bool{"bad": true}}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{ToolExecutor: executor})
+	loop.completionRequester = func(context.Context, []chatCompletionMessage, []tools.ToolDefinition) (completionChoice, error) {
+		choice := completionChoice{}
+		choice.Message.Role = "assistant"
+		choice.Message.ToolCalls = []chatToolCall{
+			{ID: "a", Type: "function", Function: toolCallFunction{Name: "web_search", Arguments: `{"text":"bad"}`}},
+			{ID: "b", Type: "function", Function: toolCallFunction{Name: "web_search", Arguments: `{"text":"later"}`}},
+		}
+		return choice, nil
+	}
+
+	if _, _, err := loop.Run(context.Background(), req); !errors.Is(err, ErrToolFailed) {
+		t.Fatalf("expected ErrToolFailed, got %v", err)
+	}
+	if ran := executor.executed(); len(ran) != 1 || ran[0] != "bad" {
+		t.Fatalf("expected only the failing call to run, got %q", ran)
+	}
+}
+
+func TestDialogueRunsToolsWithSideEffectsOnlyWhenReached(t *testing.T) {
+	req := ChatRequest{Provider: "vllm", Model: "x", Message: "hi", ParallelTools: true}
+	executor := &recordingExecutor{fail: map[string]bool{"bad": true}}
+	prompter := &declinePrompter{}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{ToolExecutor: executor, ContinuationPrompter: prompter})
+	loop.failureLimit = 1
+	loop.completionRequester = func(context.Context, []chatCompletionMessage, []tools.ToolDefinition) (completionChoice, error) {
+		choice := completionChoice{}
+		choice.Message.Role = "assistant"
+		choice.Message.ToolCalls = []chatToolCall{
+			{ID: "a", Type: "function", Function: toolCallFunction{Name: "shell", Arguments: `{"text":"bad"}`}},
+			{ID: "b", Type: "function", Function: toolCallFunction{Name: "shell", Arguments: `{"text":"later"}`}},
+		}
+		return choice, nil
+	}
+
+	if _, _, err := loop.Run(context.Background(), req); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if prompter.asked != 1 {
+		t.Fatalf("expected the failure limit to ask once, got %d", prompter.asked)
+	}
+	if ran := executor.executed(); len(ran) != 1 || ran[0] != "bad" {
+		t.Fatalf("expected the later shell call not to run after the stop, got %q", ran)
+	}
+}
+
+func TestDialogueMarksTruncatedAnswer(t *testing.T) {
+	req := ChatRequest{Provider: "vllm", Model: "x", Message: "hi"}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{})
+	loop.completionRequester = func(context.Context, []chatCompletionMessage, []tools.ToolDefinition) (completionChoice, error) {
+		choice := completionChoice{FinishReason: "length"}
+		choice.Message.Role = "assistant"
+		choice.Message.Content = "The first half"
+		return choice, nil
+	}
+
+	msg, _, err := loop.Run(context.Background(), req)
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if msg.FinishReason != FinishReasonLength {
+		t.Fatalf("expected the answer to be marked as truncated, got %+v", msg)
+	}
+}
+
+type declinePrompter struct{ asked int }
+
+func (p *declinePrompter) RequestContinuation(context.Context, string, ContinuationRequest) (ContinuationDecision, error) {
+	p.asked++
+	return ContinuationDecisionCancel, nil
+}
+
+func TestDialogueStopsAtConfiguredIterationLimit(t *testing.T) {
+	req := ChatRequest{SessionID: "s1", Provider: "vllm", Model: "x", Message: "hi", MaxToolIterations: 2}
+	prompter := &declinePrompter{}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{
+		ToolExecutor:         slowEchoExecutor{},
+		ContinuationPrompter: prompter,
+	})
+
+	calls := 0
+	loop.completionRequester = func(_ context.Context, _ []chatCompletionMessage, _ []tools.ToolDefinition) (completionChoice, error) {
+		calls++
+		choice := completionChoice{}
+		choice.Message.Role = "assistant"
+		choice.Message.ToolCalls = []chatToolCall{
+			{ID: fmt.Sprintf("call-%d", calls), Type: "function", Function: toolCallFunction{Name: "echo", Arguments: `{"text":"hi"}`}},
+		}
+		return choice, nil
+	}
+
+	msg, _, err := loop.Run(context.Background(), req)
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if calls != 2 || prompter.asked != 1 {
+		t.Fatalf("expected 2 completions and one continuation prompt, got %d and %d", calls, prompter.asked)
+	}
+	if msg.FinishReason != FinishReasonToolLimit || !strings.Contains(msg.Content, "2 tool iterations") {
+		t.Fatalf("expected a truncation message, got %+v", msg)
+	}
+	appended := loop.Messages()
+	if last := appended[len(appended)-1]; last.Role != "assistant" || last.Content != msg.Content {
+		t.Fatalf("expected the truncation message at the end of the transcript, got %+v", last)
+	}
+}
+
+func TestDialogueIterationOverrideAppliesToOneRequest(t *testing.T) {
+	run := func(req ChatRequest) int {
+		loop := NewDialogueLoop(req, nil, DialogueDependencies{
+			ToolExecutor:         slowEchoExecutor{},
+			ContinuationPrompter: &declinePrompter{},
+		})
+		calls := 0
+		loop.completionRequester = func(_ context.Context, _ []chatCompletionMessage, _ []tools.ToolDefinition) (completionChoice, error) {
+			calls++
+			choice := completionChoice{}
+			choice.Message.Role = "assistant"
+			choice.Message.ToolCalls = []chatToolCall{
+				{ID: fmt.Sprintf("call-%d", calls), Type: "function", Function: toolCallFunction{Name: "echo", Arguments: `{"text":"hi"}`}},
+			}
+			return choice, nil
+		}
+		if _, _, err := loop.Run(context.Background(), req); err != nil {
+			t.Fatalf("unexpected error: %v", err)
+		}
+		return calls
+	}
+
+	req := ChatRequest{Provider: "vllm", Model: "x", Message: "hi", MaxToolIterations: 2, MaxToolIterationsOverride: 5}
+	if calls := run(req); calls != 5 {
+		t.Fatalf("expected the override to allow 5 iterations, got %d", calls)
+	}
+	req.MaxToolIterationsOverride = 0
+	if calls := run(req); calls != 2 {
+		t.Fatalf("expected the configured limit without an override, got %d", calls)
+	}
+	if limit := iterationLimitFor(ChatRequest{MaxToolIterationsOverride: 10_000}); limit != maxIterationOverride {
+		t.Fatalf("expected overrides to be capped at %d, got %d", maxIterationOverride, limit)
+	}
+}
+
+func TestDialogueRetriesWithoutToolsWhenModelRejectsThem(t *testing.T) {
+	var withTools, withoutTools int
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var payload map[string]any
+		_ = json.NewDecoder(r.Body).Decode(&payload)
+		if _, ok := payload["tools"]; ok {
+			withTools++
+			http.Error(w, `{"error":"registry/gemma:2b does not support tools"}`, http.StatusBadRequest)
+			return
+		}
+		withoutTools++
+		_ = json.NewEncoder(w).Encode(map[string]any{
+			"choices": []map[string]any{
+				{"message": map[string]any{"role": "assistant", "content": "answered without tools"}},
+			},
+		})
+	}))
+	defer server.Close()
+
+	req := ChatRequest{
+		Provider:     "vllm",
+		Endpoint:     server.URL,
+		Model:        "gemma:2b",
+		Message:      "hi",
+		SystemPrompt: "Be terse.",
+		Tools:        []string{"shell"},
+		ToolDefs:     []tools.ToolDefinition{{Type: "function", Function: tools.ToolFunctionDef{Name: "shell"}}},
+	}
+
+	if _, _, err := NewDialogueLoop(req, nil, DialogueDependencies{Client: server.Client()}).Run(context.Background(), req); !errors.Is(err, ErrToolsUnsupported) {
+		t.Fatalf("expected ErrToolsUnsupported without the fallback, got %v", err)
+	}
+
+	req.ToolFallback = true
+	support := NewToolSupport()
+	deps := DialogueDependencies{Client: server.Client(), ToolSupport: support}
+	msg, _, err := NewDialogueLoop(req, nil, deps).Run(context.Background(), req)
+	if err != nil {
+		t.Fatalf("expected the fallback to answer, got %v", err)
+	}
+	if msg.Content != "answered without tools" {
+		t.Fatalf("unexpected answer: %+v", msg)
+	}
+	if !support.Unsupported("vllm", server.URL, "gemma:2b") {
+		t.Fatal("expected the model to be remembered as not supporting tools")
+	}
+
+	withTools, withoutTools = 0, 0
+	if _, _, err := NewDialogueLoop(req, nil, deps).Run(context.Background(), req); err != nil {
+		t.Fatalf("unexpected error on the remembered model: %v", err)
+	}
+	if withTools != 0 || withoutTools != 1 {
+		t.Fatalf("expected tools to be omitted up front, got %d with and %d without", withTools, withoutTools)
+	}
+}
+
+func TestDialogueRecordsLastExchangeTrace(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		_ = json.NewEncoder(w).Encode(map[string]any{
+			"choices": []map[string]any{
+				{"message": map[string]any{"role": "assistant", "content": "done"}},
+			},
+		})
+	}))
+	defer server.Close()
+
+	recorder := NewTraceRecorder()
+	req := ChatRequest{
+		SessionID:    "s1",
+		Provider:     "vllm",
+		Endpoint:     server.URL,
+		APIKey:       "sk-secret",
+		Model:        "x",
+		Message:      "hi",
+		SystemPrompt: "Be terse.",
+	}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{Client: server.Client(), TraceRecorder: recorder})
+	if _, _, err := loop.Run(context.Background(), req); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+
+	last := recorder.Last()
+	if last == nil || last.SessionID != "s1" || len(last.Exchanges) != 1 {
+		t.Fatalf("expected one recorded exchange, got %+v", last)
+	}
+	exchange := last.Exchanges[0]
+	if exchange.Provider != "vllm" || exchange.Model != "x" || exchange.Status != http.StatusOK {
+		t.Fatalf("unexpected exchange metadata: %+v", exchange)
+	}
+	if exchange.Request["model"] != "x" || !strings.Contains(exchange.Response, "done") {
+		t.Fatalf("expected request and response summary, got %+v", exchange)
+	}
+	encoded, _ := json.Marshal(last)
+	if strings.Contains(string(encoded), "sk-secret") {
+		t.Fatalf("trace must not contain credentials: %s", encoded)
+	}
+
+	redacted := redactSecrets(map[string]any{"api_key": "sk-secret", "nested": map[string]any{"token": "t"}}).(map[string]any)
+	if redacted["api_key"] != redactedValue || redacted["nested"].(map[string]any)["token"] != redactedValue {
+		t.Fatalf("expected secrets to be redacted, got %+v", redacted)
+	}
+}
+
+func TestRedactSecretsKeepsTokenCounts(t *testing.T) {
+	redacted := redactSecrets(map[string]any{
+		"max_tokens":  float64(256),
+		"num_tokens":  float64(12),
+		"accessToken": "t",
+		"x-api-key":   "k",
+	}).(map[string]any)
+	if redacted["max_tokens"] != float64(256) || redacted["num_tokens"] != float64(12) {
+		t.Fatalf("expected token counts to survive, got %+v", redacted)
+	}
+	if redacted["accessToken"] != redactedValue || redacted["x-api-key"] != redactedValue {
+		t.Fatalf("expected credentials to be redacted, got %+v", redacted)
+	}
+}
+
+func TestNormalizeToolArguments(t *testing.T) {
+	object, ok := NormalizeToolArguments(`{"command":"ls"}`).(map[string]any)
+	if !ok || object["command"] != "ls" {
+		t.Fatalf("expected stringified JSON to decode, got %#v", object)
+	}
+
+	if got := NormalizeToolArguments("not json"); got != "not json" {
+		t.Fatalf("expected invalid JSON to stay a string, got %#v", got)
+	}
+
+	if got, ok := NormalizeToolArguments(`"{\"command\":\"ls\"}"`).(map[string]any); !ok || got["command"] != "ls" {
+		t.Fatalf("expected double-encoded JSON to be unwrapped, got %#v", got)
+	}
+
+	passthrough := map[string]any{"command": "pwd"}
+	if got, ok := NormalizeToolArguments(passthrough).(map[string]any); !ok || got["command"] != "pwd" {
+		t.Fatalf("expected objects to pass through, got %#v", got)
+	}
+}
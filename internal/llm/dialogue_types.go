@@ -8,6 +8,8 @@ type toolCallFunction struct {
 }
 
 type chatToolCall struct {
+	// Index identifies the call that a streamed fragment belongs to.
+	Index    *int             `json:"index,omitempty"`
 	ID       string           `json:"id"`
 	Type     string           `json:"type"`
 	Function toolCallFunction `json:"function"`
@@ -27,7 +29,9 @@ type completionRequest struct {
 	Stream      bool                    `json:"stream"`
 	Tools       []tools.ToolDefinition  `json:"tools,omitempty"`
 	ToolChoice  string                  `json:"tool_choice,omitempty"`
-	Temperature float32                 `json:"temperature,omitempty"`
+	// ParallelToolCalls is only sent to OpenAI-compatible providers.
+	ParallelToolCalls *bool `json:"parallel_tool_calls,omitempty"`
+	samplingFields
 }
 
 type completionChoice struct {
@@ -37,10 +41,13 @@ type completionChoice struct {
 		ToolCalls []chatToolCall `json:"tool_calls,omitempty"`
 	} `json:"message"`
 	FinishReason string `json:"finish_reason"`
+	// Usage is copied from the response, which reports it once for all choices.
+	Usage *Usage `json:"-"`
 }
 
 type completionResponse struct {
 	Choices []completionChoice `json:"choices"`
+	Usage   *completionUsage   `json:"usage"`
 	Error   struct {
 		Message string `json:"message"`
 	} `json:"error"`
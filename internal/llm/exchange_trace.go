@@ -0,0 +1,97 @@
+package llm
+
+import (
+	"encoding/json"
+	"sync"
+	"time"
+)
+
+// maxTracedExchanges bounds how many completions of one dialogue are kept.
+const maxTracedExchanges = 20
+
+// ExchangeRecord is a redacted snapshot of one completion request and its outcome.
+type ExchangeRecord struct {
+	Provider   string         `json:"provider"`
+	Model      string         `json:"model"`
+	URL        string         `json:"url"`
+	Request    map[string]any `json:"request"`
+	Status     int            `json:"status,omitempty"`
+	Response   string         `json:"response,omitempty"`
+	Error      string         `json:"error,omitempty"`
+	StartedAt  time.Time      `json:"startedAt"`
+	DurationMs int64          `json:"durationMs"`
+}
+
+// ExchangeTrace holds the provider exchanges of the most recent dialogue.
+type ExchangeTrace struct {
+	SessionID string           `json:"sessionId"`
+	Exchanges []ExchangeRecord `json:"exchanges"`
+}
+
+// TraceRecorder keeps the last dialogue's exchanges in memory for diagnostics.
+type TraceRecorder struct {
+	mu   sync.RWMutex
+	last *ExchangeTrace
+}
+
+func NewTraceRecorder() *TraceRecorder {
+	return &TraceRecorder{}
+}
+
+// Last returns a copy of the most recent trace, or nil if no dialogue ran yet.
+func (r *TraceRecorder) Last() *ExchangeTrace {
+	if r == nil {
+		return nil
+	}
+	r.mu.RLock()
+	defer r.mu.RUnlock()
+	if r.last == nil {
+		return nil
+	}
+	copied := *r.last
+	copied.Exchanges = append([]ExchangeRecord(nil), r.last.Exchanges...)
+	return &copied
+}
+
+func (r *TraceRecorder) store(trace ExchangeTrace) {
+	if r == nil {
+		return
+	}
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	r.last = &trace
+}
+
+// recordExchange appends a redacted exchange to the loop's pending trace.
+func (l *dialogueLoop) recordExchange(url string, body []byte, started time.Time, status int, response string, err error) {
+	if l.recorder == nil || len(l.exchanges) >= maxTracedExchanges {
+		return
+	}
+
+	var decoded map[string]any
+	if jsonErr := json.Unmarshal(body, &decoded); jsonErr == nil {
+		decoded, _ = redactSecrets(decoded).(map[string]any)
+	}
+
+	record := ExchangeRecord{
+		Provider:   l.provider,
+		Model:      l.model,
+		URL:        url,
+		Request:    decoded,
+		Status:     status,
+		Response:   truncate(response, 512),
+		StartedAt:  started,
+		DurationMs: time.Since(started).Milliseconds(),
+	}
+	if err != nil {
+		record.Error = err.Error()
+	}
+	l.exchanges = append(l.exchanges, record)
+}
+
+func (l *dialogueLoop) flushExchanges() {
+	if l.recorder == nil {
+		return
+	}
+	l.recorder.store(ExchangeTrace{SessionID: l.sessionID, Exchanges: l.exchanges})
+}
@@ -0,0 +1,80 @@
+package llm
+
+import (
+	"encoding/json"
+	"errors"
+	"strings"
+)
+
+type fineTuneMessage struct {
+	Role       string     `json:"role"`
+	Content    string     `json:"content"`
+	ToolCalls  []ToolCall `json:"tool_calls,omitempty"`
+	ToolCallID string     `json:"tool_call_id,omitempty"`
+}
+
+type fineTuneRecord struct {
+	Messages []fineTuneMessage `json:"messages"`
+}
+
+// ExportConversationJSONL renders history as one line in the OpenAI
+// fine-tuning format. Roles are lowercased, unknown roles and empty turns are
+// dropped, and UI-only fields such as the finish reason are left out. Lines
+// from several conversations can be concatenated by the caller.
+func ExportConversationJSONL(history []ChatMessage) (string, error) {
+	record := fineTuneRecord{Messages: []fineTuneMessage{}}
+	for _, msg := range history {
+		role := strings.ToLower(strings.TrimSpace(msg.Role))
+		switch role {
+		case "system", "user", "assistant", "tool":
+		default:
+			continue
+		}
+		if strings.TrimSpace(msg.Content) == "" && len(msg.ToolCalls) == 0 {
+			continue
+		}
+		out := fineTuneMessage{Role: role, Content: msg.Content}
+		switch role {
+		case "assistant":
+			out.ToolCalls = msg.ToolCalls
+		case "tool":
+			out.ToolCallID = msg.ToolCallID
+		}
+		record.Messages = append(record.Messages, out)
+	}
+	if len(record.Messages) == 0 {
+		return "", errors.New("conversation has no exportable messages")
+	}
+
+	data, err := json.Marshal(record)
+	if err != nil {
+		return "", err
+	}
+	return string(data) + "\n", nil
+}
+
+// TranscriptPlaintext renders history as plain text for copying, one labeled
+// block per turn separated by blank lines: "You: ..." and "Assistant: ...".
+// Tool results appear in a delimited "tool output" block; system messages and
+// assistant turns that only request tools are left out.
+func TranscriptPlaintext(history []ChatMessage) string {
+	var blocks []string
+	for _, msg := range history {
+		content := strings.TrimSpace(msg.Content)
+		if content == "" {
+			continue
+		}
+		switch strings.ToLower(strings.TrimSpace(msg.Role)) {
+		case "user":
+			blocks = append(blocks, "You: "+content)
+		case "assistant":
+			blocks = append(blocks, "Assistant: "+content)
+		case "tool":
+			blocks = append(blocks, "--- tool output ---\n"+content+"\n--- end tool output ---")
+		}
+	}
+	if len(blocks) == 0 {
+		return ""
+	}
+	return strings.Join(blocks, "\n\n") + "\n"
+}
@@ -0,0 +1,73 @@
+package llm
+
+import (
+	"encoding/json"
+	"strings"
+	"testing"
+)
+
+func TestExportConversationJSONL(t *testing.T) {
+	line, err := ExportConversationJSONL([]ChatMessage{
+		{Role: "System", Content: "Be terse."},
+		{Role: "user", Content: "list files"},
+		{Role: "assistant", Content: "", FinishReason: "stop"},
+		{Role: "assistant", Content: "ls -la", FinishReason: "stop"},
+		{Role: "thinking", Content: "internal"},
+	})
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if !strings.HasSuffix(line, "\n") || strings.Count(line, "\n") != 1 {
+		t.Fatalf("expected a single JSONL line, got %q", line)
+	}
+	if strings.Contains(line, "finishReason") {
+		t.Fatalf("internal metadata should be dropped: %s", line)
+	}
+
+	var decoded struct {
+		Messages []map[string]any `json:"messages"`
+	}
+	if err := json.Unmarshal([]byte(line), &decoded); err != nil {
+		t.Fatalf("line is not valid JSON: %v", err)
+	}
+	var roles []string
+	for _, msg := range decoded.Messages {
+		roles = append(roles, msg["role"].(string))
+	}
+	if strings.Join(roles, ",") != "system,user,assistant" {
+		t.Fatalf("unexpected message roles: %v", roles)
+	}
+
+	line, err = ExportConversationJSONL([]ChatMessage{
+		{Role: "user", Content: "list files"},
+		{Role: "assistant", ToolCalls: []ToolCall{{ID: "call-1", Type: "function", Function: ToolCallFunction{Name: "shell", Arguments: `{"command":"ls"}`}}}},
+		{Role: "tool", Content: "a.txt", ToolCallID: "call-1"},
+	})
+	if err != nil || !strings.Contains(line, `"tool_call_id":"call-1"`) {
+		t.Fatalf("expected the tool message to keep its call id, got %s (%v)", line, err)
+	}
+
+	if _, err := ExportConversationJSONL(nil); err == nil {
+		t.Fatalf("expected an error for an empty conversation")
+	}
+}
+
+func TestTranscriptPlaintext(t *testing.T) {
+	got := TranscriptPlaintext([]ChatMessage{
+		{Role: "system", Content: "Be terse."},
+		{Role: "user", Content: "list files"},
+		{Role: "assistant", ToolCalls: []ToolCall{{Type: "function", Function: ToolCallFunction{Name: "shell", Arguments: `{"command":"ls"}`}}}},
+		{Role: "tool", Content: "a.txt\nb.txt\n"},
+		{Role: "Assistant", Content: "There are two files."},
+	})
+	want := "You: list files\n\n" +
+		"--- tool output ---\na.txt\nb.txt\n--- end tool output ---\n\n" +
+		"Assistant: There are two files.\n"
+	if got != want {
+		t.Fatalf("unexpected transcript:\n%q\nwant:\n%q", got, want)
+	}
+
+	if got := TranscriptPlaintext(nil); got != "" {
+		t.Fatalf("expected an empty transcript, got %q", got)
+	}
+}
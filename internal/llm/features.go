@@ -0,0 +1,35 @@
+package llm
+
+import "shell-werk/internal/tools"
+
+// FeatureFlags reports which optional providers and capabilities are compiled
+// into this build so the UI can hide controls for absent features.
+type FeatureFlags struct {
+	MockProvider   bool `json:"mockProvider"`
+	OllamaProvider bool `json:"ollamaProvider"`
+	VLLMProvider   bool `json:"vllmProvider"`
+	Streaming      bool `json:"streaming"`
+	ToolCalling    bool `json:"toolCalling"`
+	Keyring        bool `json:"keyring"`
+	// DevTools enables state dumps; `wails dev` builds with the dev tag.
+	DevTools bool `json:"devTools"`
+}
+
+// Features returns the flags for the current build. Providers count as
+// compiled in when ProviderFor resolves them, streaming and tool calling
+// follow from the providers and built-in tools present, and the remaining
+// capabilities are toggled with build tags, e.g. `go build -tags keyring`.
+func Features() FeatureFlags {
+	_, mock := providerWithClient("mock", nil).(MockProvider)
+	_, ollama := providerWithClient("ollama", nil).(OllamaProvider)
+	_, vllm := providerWithClient("vllm", nil).(VLLMProvider)
+	return FeatureFlags{
+		MockProvider:   mock,
+		OllamaProvider: ollama,
+		VLLMProvider:   vllm,
+		Streaming:      (ollama && streamURL("ollama", "") != "") || (vllm && streamURL("vllm", "") != ""),
+		ToolCalling:    (ollama || vllm) && len(tools.DefaultTools()) > 0,
+		Keyring:        keyringEnabled,
+		DevTools:       devToolsEnabled,
+	}
+}
@@ -0,0 +1,5 @@
+//go:build dev
+
+package llm
+
+const devToolsEnabled = true
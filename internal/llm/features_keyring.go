@@ -0,0 +1,5 @@
+//go:build keyring
+
+package llm
+
+const keyringEnabled = true
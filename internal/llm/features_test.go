@@ -0,0 +1,37 @@
+package llm
+
+import (
+	"testing"
+
+	"shell-werk/internal/tools"
+)
+
+func TestFeaturesMatchBuild(t *testing.T) {
+	flags := Features()
+
+	if flags.Keyring != keyringEnabled {
+		t.Fatalf("expected keyring flag %v to match build tag, got %v", keyringEnabled, flags.Keyring)
+	}
+	if flags.DevTools != devToolsEnabled {
+		t.Fatalf("expected dev tools flag %v to match build tag, got %v", devToolsEnabled, flags.DevTools)
+	}
+
+	_, ollama := ProviderFor("ollama").(OllamaProvider)
+	if flags.OllamaProvider != ollama {
+		t.Fatalf("expected ollama flag %v to match ProviderFor, got %v", ollama, flags.OllamaProvider)
+	}
+	_, vllm := ProviderFor("vllm").(VLLMProvider)
+	if flags.VLLMProvider != vllm {
+		t.Fatalf("expected vllm flag %v to match ProviderFor, got %v", vllm, flags.VLLMProvider)
+	}
+	if !flags.MockProvider {
+		t.Fatalf("mock provider is always available")
+	}
+
+	if flags.Streaming && streamURL("ollama", "http://localhost:11434") == "" && streamURL("vllm", "http://localhost:8000") == "" {
+		t.Fatalf("streaming flagged without a provider that streams")
+	}
+	if flags.ToolCalling != ((ollama || vllm) && len(tools.DefaultTools()) > 0) {
+		t.Fatalf("expected tool calling to follow the compiled providers and tools, got %v", flags.ToolCalling)
+	}
+}
@@ -0,0 +1,44 @@
+package llm
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"strings"
+)
+
+type hashedToolCall struct {
+	Name      string `json:"name"`
+	Arguments string `json:"arguments"`
+}
+
+type hashedMessage struct {
+	Role      string           `json:"role"`
+	Content   string           `json:"content"`
+	ToolCalls []hashedToolCall `json:"toolCalls,omitempty"`
+}
+
+// ConversationHash returns a hex SHA-256 over the roles, contents and tool
+// calls of history. Volatile fields such as tool call ids, usage and finish
+// reasons are left out, so the hash can key caches across reloads.
+func ConversationHash(history []ChatMessage) string {
+	normalized := make([]hashedMessage, 0, len(history))
+	for _, msg := range history {
+		out := hashedMessage{
+			Role:    strings.ToLower(strings.TrimSpace(msg.Role)),
+			Content: strings.TrimSpace(msg.Content),
+		}
+		for _, call := range msg.ToolCalls {
+			out.ToolCalls = append(out.ToolCalls, hashedToolCall{
+				Name:      call.Function.Name,
+				Arguments: call.Function.Arguments,
+			})
+		}
+		normalized = append(normalized, out)
+	}
+
+	// JSON keeps field boundaries unambiguous, unlike joining the raw strings.
+	data, _ := json.Marshal(normalized)
+	sum := sha256.Sum256(data)
+	return hex.EncodeToString(sum[:])
+}
@@ -0,0 +1,32 @@
+package llm
+
+import "testing"
+
+func TestConversationHashIgnoresVolatileFields(t *testing.T) {
+	call := func(id string) []ToolCall {
+		return []ToolCall{{ID: id, Type: "function", Function: ToolCallFunction{Name: "shell", Arguments: `{"command":"ls"}`}}}
+	}
+	first := []ChatMessage{
+		{Role: "user", Content: "list files"},
+		{Role: "assistant", ToolCalls: call("call_1")},
+		{Role: "assistant", Content: "done", FinishReason: FinishReasonLength, Usage: &Usage{PromptTokens: 10}},
+	}
+	second := []ChatMessage{
+		{Role: "User", Content: "list files "},
+		{Role: "assistant", ToolCalls: call("call_99")},
+		{Role: "assistant", Content: "done", Usage: &Usage{PromptTokens: 42}},
+	}
+
+	hash := ConversationHash(first)
+	if len(hash) != 64 {
+		t.Fatalf("expected a hex SHA-256, got %q", hash)
+	}
+	if got := ConversationHash(second); got != hash {
+		t.Fatalf("expected equal hashes, got %q and %q", hash, got)
+	}
+
+	second[2].Content = "done!"
+	if ConversationHash(second) == hash {
+		t.Fatalf("expected a content change to change the hash")
+	}
+}
@@ -0,0 +1,69 @@
+package llm
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"net/http"
+	"strings"
+	"time"
+)
+
+// healthCheckTimeout keeps a "Test connection" click responsive.
+const healthCheckTimeout = 5 * time.Second
+
+// HealthStatus is the result of a connection test. Reachable means the server
+// answered at all; Detail explains failures and non-success statuses.
+type HealthStatus struct {
+	Reachable bool   `json:"reachable"`
+	LatencyMs int64  `json:"latencyMs"`
+	Detail    string `json:"detail,omitempty"`
+}
+
+// CheckHealth issues a lightweight listing request to the provider. Network
+// failures such as a refused connection are reported as unreachable rather
+// than returned as errors.
+func CheckHealth(ctx context.Context, target ModelsRequest, client *http.Client) HealthStatus {
+	provider := strings.ToLower(strings.TrimSpace(target.Provider))
+	base := providerBase(provider, target.Endpoint)
+
+	var url string
+	switch provider {
+	case "mock":
+		return HealthStatus{Reachable: true}
+	case "ollama":
+		url = base + "/api/tags"
+	case "vllm", "openai":
+		url = base + "/v1/models"
+	default:
+		return HealthStatus{Detail: fmt.Sprintf("unsupported provider: %s", target.Provider)}
+	}
+	if client == nil {
+		client = MakeClient()
+	}
+
+	ctx, cancel := context.WithTimeout(ctx, healthCheckTimeout)
+	defer cancel()
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, url, nil)
+	if err != nil {
+		return HealthStatus{Detail: RedactError(err).Error()}
+	}
+	if provider != "ollama" && strings.TrimSpace(target.APIKey) != "" {
+		req.Header.Set("Authorization", "Bearer "+target.APIKey)
+	}
+
+	start := time.Now()
+	resp, err := client.Do(req)
+	latency := time.Since(start).Milliseconds()
+	if err != nil {
+		return HealthStatus{LatencyMs: latency, Detail: RedactError(err).Error()}
+	}
+	defer resp.Body.Close()
+	_, _ = io.Copy(io.Discard, io.LimitReader(resp.Body, 64<<10))
+
+	status := HealthStatus{Reachable: true, LatencyMs: latency}
+	if resp.StatusCode >= http.StatusBadRequest {
+		status.Detail = fmt.Sprintf("server answered %s", resp.Status)
+	}
+	return status
+}
@@ -0,0 +1,37 @@
+package llm
+
+import (
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+)
+
+func TestCheckHealthReportsReachableServer(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodGet || r.URL.Path != "/api/tags" {
+			t.Fatalf("unexpected request %s %s", r.Method, r.URL.Path)
+		}
+		_, _ = w.Write([]byte(`{"models":[]}`))
+	}))
+	defer server.Close()
+
+	status := CheckHealth(context.Background(), ModelsRequest{Provider: "ollama", Endpoint: server.URL}, server.Client())
+	if !status.Reachable || status.Detail != "" {
+		t.Fatalf("expected a reachable server, got %+v", status)
+	}
+}
+
+func TestCheckHealthReportsRefusedConnection(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {}))
+	endpoint := server.URL
+	server.Close()
+
+	status := CheckHealth(context.Background(), ModelsRequest{Provider: "vllm", Endpoint: endpoint}, nil)
+	if status.Reachable {
+		t.Fatalf("expected an unreachable server, got %+v", status)
+	}
+	if status.Detail == "" {
+		t.Fatalf("expected a detail explaining the failure")
+	}
+}
@@ -1,13 +1,41 @@
 package llm
 
 import (
+	"fmt"
 	"net/http"
+	"net/url"
 	"strings"
 	"time"
 )
 
+// Accept headers for chat requests. Ollama streams newline-delimited JSON while
+// OpenAI-compatible servers stream server-sent events.
+const (
+	acceptJSON   = "application/json"
+	acceptSSE    = "text/event-stream"
+	acceptNDJSON = "application/x-ndjson"
+)
+
+// DefaultRequestTimeout bounds provider requests unless a timeout is configured.
+const DefaultRequestTimeout = 60 * time.Second
+
+// NoTimeout disables the request timeout, for cold starts of large local models.
+const NoTimeout time.Duration = -1
+
 func MakeClient() *http.Client {
-	return &http.Client{Timeout: 60 * time.Second}
+	return MakeClientWithTimeout(0)
+}
+
+// MakeClientWithTimeout builds a client with the given timeout. Zero uses
+// DefaultRequestTimeout and a negative value such as NoTimeout disables it.
+func MakeClientWithTimeout(timeout time.Duration) *http.Client {
+	switch {
+	case timeout == 0:
+		timeout = DefaultRequestTimeout
+	case timeout < 0:
+		timeout = 0
+	}
+	return &http.Client{Timeout: timeout}
 }
 
 func NormalizeBase(endpoint string) string {
@@ -21,3 +49,40 @@ func NormalizeBase(endpoint string) string {
 	}
 	return trimmed
 }
+
+// NormalizeBaseURL applies the NormalizeBase rules and validates the result for the
+// given provider, returning the cleaned URL or a user-facing error.
+func NormalizeBaseURL(provider, endpoint string) (string, error) {
+	provider = strings.ToLower(strings.TrimSpace(provider))
+	switch provider {
+	case "mock", "ollama", "vllm", "openai":
+	default:
+		return "", fmt.Errorf("unsupported provider: %s", provider)
+	}
+
+	base := strings.TrimSpace(endpoint)
+	if base == "" {
+		switch provider {
+		case "mock":
+			return "", nil
+		case "openai":
+			return DefaultOpenAIBaseURL, nil
+		}
+		return "", fmt.Errorf("endpoint is required for %s", provider)
+	}
+	if !strings.Contains(base, "://") {
+		base = "http://" + base
+	}
+
+	parsed, err := url.Parse(base)
+	if err != nil {
+		return "", fmt.Errorf("invalid endpoint %q: %w", endpoint, err)
+	}
+	if parsed.Scheme != "http" && parsed.Scheme != "https" {
+		return "", fmt.Errorf("invalid endpoint %q: scheme must be http or https", endpoint)
+	}
+	if parsed.Host == "" {
+		return "", fmt.Errorf("invalid endpoint %q: missing host", endpoint)
+	}
+	return strings.TrimRight(base, "/"), nil
+}
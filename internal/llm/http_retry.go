@@ -0,0 +1,67 @@
+package llm
+
+import (
+	"io"
+	"net/http"
+	"time"
+)
+
+// DefaultRequestAttempts is how often a listing or completion request is
+// tried before a transient failure is returned.
+const DefaultRequestAttempts = 3
+
+// retryBaseDelay is the wait before the first retry; it doubles each attempt.
+const retryBaseDelay = 200 * time.Millisecond
+
+// WithRetries returns a copy of client that retries transport errors and 5xx
+// responses with exponential backoff, which covers servers still loading a
+// model. attempts counts the first try; zero uses DefaultRequestAttempts.
+func WithRetries(client *http.Client, attempts int) *http.Client {
+	if attempts <= 0 {
+		attempts = DefaultRequestAttempts
+	}
+	next := client.Transport
+	if next == nil {
+		next = http.DefaultTransport
+	}
+	retrying := *client
+	retrying.Transport = retryTransport{next: next, attempts: attempts, base: retryBaseDelay}
+	return &retrying
+}
+
+type retryTransport struct {
+	next     http.RoundTripper
+	attempts int
+	base     time.Duration
+}
+
+func (t retryTransport) RoundTrip(req *http.Request) (*http.Response, error) {
+	for attempt := 1; ; attempt++ {
+		resp, err := t.next.RoundTrip(req)
+		transient := err != nil || resp.StatusCode >= http.StatusInternalServerError
+		// Requests whose body cannot be replayed are only sent once.
+		rewindable := req.Body == nil || req.GetBody != nil
+		if !transient || !rewindable || attempt >= t.attempts || req.Context().Err() != nil {
+			return resp, err
+		}
+		if resp != nil {
+			_, _ = io.Copy(io.Discard, resp.Body)
+			resp.Body.Close()
+		}
+
+		select {
+		case <-req.Context().Done():
+			return nil, req.Context().Err()
+		case <-time.After(t.base << (attempt - 1)):
+		}
+
+		if req.GetBody != nil {
+			body, bodyErr := req.GetBody()
+			if bodyErr != nil {
+				return nil, bodyErr
+			}
+			req = req.Clone(req.Context())
+			req.Body = body
+		}
+	}
+}
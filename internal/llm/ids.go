@@ -0,0 +1,50 @@
+package llm
+
+import (
+	"fmt"
+	"strings"
+	"sync/atomic"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+// IDStyle selects how trace, message, and request identifiers are generated.
+type IDStyle string
+
+const (
+	IDStyleTimestamp IDStyle = "timestamp"
+	IDStyleUUID      IDStyle = "uuid"
+)
+
+// ParseIDStyle normalizes a configured style, defaulting to timestamps.
+func ParseIDStyle(raw string) IDStyle {
+	if strings.EqualFold(strings.TrimSpace(raw), string(IDStyleUUID)) {
+		return IDStyleUUID
+	}
+	return IDStyleTimestamp
+}
+
+// idSequence disambiguates timestamp IDs created within the same clock tick.
+var idSequence atomic.Uint64
+
+// NewID returns an identifier in the given style. Timestamp IDs carry the
+// prefix and a process-wide sequence number, so IDs created in a tight loop
+// never collide; UUID IDs are plain v4 UUIDs so external systems can parse them.
+func NewID(style IDStyle, prefix string) string {
+	if style == IDStyleUUID {
+		return uuid.NewString()
+	}
+	return fmt.Sprintf("%s-%d-%d", prefix, time.Now().UnixNano(), idSequence.Add(1))
+}
+
+// WithNamespace prefixes id with namespace, e.g. "conv123-trace-…", so ids can
+// be correlated by systems consuming the conversation. An empty namespace
+// leaves id unchanged.
+func WithNamespace(namespace, id string) string {
+	namespace = strings.TrimRight(strings.TrimSpace(namespace), "-")
+	if namespace == "" {
+		return id
+	}
+	return namespace + "-" + id
+}
@@ -0,0 +1,84 @@
+package llm
+
+import (
+	"context"
+	"strings"
+	"testing"
+
+	"github.com/google/uuid"
+	"shell-werk/internal/tools"
+)
+
+func TestNewIDStyles(t *testing.T) {
+	if id := NewID(IDStyleTimestamp, "trace"); !strings.HasPrefix(id, "trace-") {
+		t.Fatalf("expected timestamp id with prefix, got %q", id)
+	}
+	if _, err := uuid.Parse(NewID(IDStyleUUID, "trace")); err != nil {
+		t.Fatalf("expected parseable UUID: %v", err)
+	}
+	if ParseIDStyle(" UUID ") != IDStyleUUID || ParseIDStyle("") != IDStyleTimestamp {
+		t.Fatalf("unexpected ParseIDStyle normalization")
+	}
+}
+
+func TestNewIDIsUniqueInTightLoops(t *testing.T) {
+	seen := make(map[string]struct{}, 10000)
+	for i := 0; i < 10000; i++ {
+		id := NewID(IDStyleTimestamp, "msg")
+		if _, dup := seen[id]; dup {
+			t.Fatalf("duplicate id %q after %d ids", id, i)
+		}
+		seen[id] = struct{}{}
+	}
+}
+
+func TestDialogueTraceUsesIDPrefix(t *testing.T) {
+	req := ChatRequest{Provider: "vllm", Model: "x", Message: "hi", IDPrefix: "conv123"}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{})
+	loop.completionRequester = func(context.Context, []chatCompletionMessage, []tools.ToolDefinition) (completionChoice, error) {
+		choice := completionChoice{}
+		choice.Message.Role = "assistant"
+		choice.Message.Content = "done"
+		return choice, nil
+	}
+
+	_, trace, err := loop.Run(context.Background(), req)
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if len(trace) == 0 {
+		t.Fatalf("expected trace entries")
+	}
+	for _, entry := range trace {
+		if !strings.HasPrefix(entry.ID, "conv123-trace-") {
+			t.Fatalf("expected namespaced trace id, got %q", entry.ID)
+		}
+	}
+	if id := WithNamespace("", "trace-1"); id != "trace-1" {
+		t.Fatalf("expected an empty namespace to leave the id alone, got %q", id)
+	}
+}
+
+func TestDialogueTraceUsesUUIDStyle(t *testing.T) {
+	req := ChatRequest{Provider: "vllm", Model: "x", Message: "hi", IDStyle: IDStyleUUID}
+	loop := NewDialogueLoop(req, nil, DialogueDependencies{})
+	loop.completionRequester = func(context.Context, []chatCompletionMessage, []tools.ToolDefinition) (completionChoice, error) {
+		choice := completionChoice{}
+		choice.Message.Role = "assistant"
+		choice.Message.Content = "done"
+		return choice, nil
+	}
+
+	_, trace, err := loop.Run(context.Background(), req)
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if len(trace) == 0 {
+		t.Fatalf("expected trace entries")
+	}
+	for _, entry := range trace {
+		if _, err := uuid.Parse(entry.ID); err != nil {
+			t.Fatalf("expected UUID trace id, got %q", entry.ID)
+		}
+	}
+}
@@ -0,0 +1,180 @@
+package llm
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+)
+
+// DefaultModelCacheTTL is how long a model listing is reused before the
+// provider is queried again.
+const DefaultModelCacheTTL = 5 * time.Minute
+
+// CacheEntryMeta describes one cached model listing.
+type CacheEntryMeta struct {
+	Key        string `json:"key"`
+	Provider   string `json:"provider"`
+	Endpoint   string `json:"endpoint"`
+	AgeSeconds int64  `json:"ageSeconds"`
+	ModelCount int    `json:"modelCount"`
+}
+
+type modelCacheEntry struct {
+	provider string
+	endpoint string
+	keyHash  string
+	models   []string
+	storedAt time.Time
+}
+
+// ModelCache keeps model listings per provider, base URL and API key so switching
+// between settings panels does not refetch them every time.
+type ModelCache struct {
+	mu      sync.Mutex
+	ttl     time.Duration
+	now     func() time.Time
+	entries map[string]modelCacheEntry
+}
+
+func NewModelCache(ttl time.Duration) *ModelCache {
+	return &ModelCache{ttl: ttl, now: time.Now, entries: map[string]modelCacheEntry{}}
+}
+
+// ModelCacheSnapshot is one cached listing in a form that survives JSON.
+type ModelCacheSnapshot struct {
+	Provider string    `json:"provider"`
+	Endpoint string    `json:"endpoint"`
+	KeyHash  string    `json:"keyHash,omitempty"`
+	Models   []string  `json:"models"`
+	StoredAt time.Time `json:"storedAt"`
+}
+
+// ModelCacheKey identifies a listing by provider, normalized base URL and a
+// hash of the API key, since gateways list different models per key.
+func ModelCacheKey(provider, endpoint, apiKey string) string {
+	return modelCacheKey(provider, endpoint, apiKeyHash(apiKey))
+}
+
+func modelCacheKey(provider, endpoint, keyHash string) string {
+	key := strings.ToLower(strings.TrimSpace(provider)) + "|" + NormalizeBase(endpoint)
+	if keyHash != "" {
+		key += "|" + keyHash
+	}
+	return key
+}
+
+// apiKeyHash shortens the key's SHA-256 so the key itself never shows up in
+// cache keys, debug listings or state dumps. It is empty without a key.
+func apiKeyHash(apiKey string) string {
+	apiKey = strings.TrimSpace(apiKey)
+	if apiKey == "" {
+		return ""
+	}
+	sum := sha256.Sum256([]byte(apiKey))
+	return hex.EncodeToString(sum[:8])
+}
+
+// Get returns a cached listing that has not expired yet.
+func (c *ModelCache) Get(provider, endpoint, apiKey string) ([]string, bool) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	entry, ok := c.entries[ModelCacheKey(provider, endpoint, apiKey)]
+	if !ok || c.now().Sub(entry.storedAt) > c.ttl {
+		return nil, false
+	}
+	return append([]string(nil), entry.models...), true
+}
+
+func (c *ModelCache) Put(provider, endpoint, apiKey string, models []string) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	keyHash := apiKeyHash(apiKey)
+	c.entries[modelCacheKey(provider, endpoint, keyHash)] = modelCacheEntry{
+		provider: strings.ToLower(strings.TrimSpace(provider)),
+		endpoint: NormalizeBase(endpoint),
+		keyHash:  keyHash,
+		models:   append([]string(nil), models...),
+		storedAt: c.now(),
+	}
+}
+
+// Entries lists every cached listing, including expired ones, sorted by key.
+func (c *ModelCache) Entries() []CacheEntryMeta {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	now := c.now()
+	out := make([]CacheEntryMeta, 0, len(c.entries))
+	for key, entry := range c.entries {
+		out = append(out, CacheEntryMeta{
+			Key:        key,
+			Provider:   entry.provider,
+			Endpoint:   entry.endpoint,
+			AgeSeconds: int64(now.Sub(entry.storedAt) / time.Second),
+			ModelCount: len(entry.models),
+		})
+	}
+	sort.Slice(out, func(i, j int) bool { return out[i].Key < out[j].Key })
+	return out
+}
+
+// Evict removes the entry stored under key and reports whether it existed.
+func (c *ModelCache) Evict(key string) bool {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	if _, ok := c.entries[key]; !ok {
+		return false
+	}
+	delete(c.entries, key)
+	return true
+}
+
+// Clear removes every cached listing.
+func (c *ModelCache) Clear() {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	c.entries = map[string]modelCacheEntry{}
+}
+
+// Snapshot copies every entry, including expired ones, sorted by key.
+func (c *ModelCache) Snapshot() []ModelCacheSnapshot {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	keys := make([]string, 0, len(c.entries))
+	for key := range c.entries {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+
+	out := make([]ModelCacheSnapshot, 0, len(keys))
+	for _, key := range keys {
+		entry := c.entries[key]
+		out = append(out, ModelCacheSnapshot{
+			Provider: entry.provider,
+			Endpoint: entry.endpoint,
+			KeyHash:  entry.keyHash,
+			Models:   append([]string(nil), entry.models...),
+			StoredAt: entry.storedAt,
+		})
+	}
+	return out
+}
+
+// Restore replaces the cache contents with a snapshot, keeping the stored
+// timestamps so entries expire as they would have.
+func (c *ModelCache) Restore(snapshot []ModelCacheSnapshot) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	c.entries = make(map[string]modelCacheEntry, len(snapshot))
+	for _, entry := range snapshot {
+		c.entries[modelCacheKey(entry.Provider, entry.Endpoint, entry.KeyHash)] = modelCacheEntry{
+			provider: strings.ToLower(strings.TrimSpace(entry.Provider)),
+			endpoint: NormalizeBase(entry.Endpoint),
+			keyHash:  entry.KeyHash,
+			models:   append([]string(nil), entry.Models...),
+			storedAt: entry.StoredAt,
+		}
+	}
+}
@@ -0,0 +1,70 @@
+package llm
+
+import (
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestModelCacheListAndEvict(t *testing.T) {
+	cache := NewModelCache(time.Minute)
+	now := time.Now()
+	cache.now = func() time.Time { return now }
+
+	cache.Put("ollama", "localhost:11434/", "", []string{"llama3", "qwen3"})
+	cache.Put("vllm", "http://gpu:8000", "", []string{"mistral"})
+	now = now.Add(10 * time.Second)
+
+	entries := cache.Entries()
+	if len(entries) != 2 {
+		t.Fatalf("expected two entries, got %+v", entries)
+	}
+	if entries[0].Key != "ollama|http://localhost:11434" || entries[0].ModelCount != 2 || entries[0].AgeSeconds != 10 {
+		t.Fatalf("unexpected ollama entry: %+v", entries[0])
+	}
+
+	if !cache.Evict(entries[0].Key) {
+		t.Fatalf("expected eviction to succeed")
+	}
+	if cache.Evict(entries[0].Key) {
+		t.Fatalf("expected second eviction to report a missing key")
+	}
+	remaining := cache.Entries()
+	if len(remaining) != 1 || remaining[0].Provider != "vllm" {
+		t.Fatalf("expected only the vllm entry to remain, got %+v", remaining)
+	}
+	if _, ok := cache.Get("ollama", "localhost:11434", ""); ok {
+		t.Fatalf("evicted entry should not be served")
+	}
+
+	now = now.Add(time.Minute)
+	if _, ok := cache.Get("vllm", "http://gpu:8000", ""); ok {
+		t.Fatalf("expired entry should not be served")
+	}
+}
+
+func TestModelCacheSeparatesListingsByAPIKey(t *testing.T) {
+	cache := NewModelCache(time.Minute)
+	cache.Put("openrouter", "https://openrouter.ai/api", "sk-team", []string{"gpt-4o"})
+
+	if _, ok := cache.Get("openrouter", "https://openrouter.ai/api", "sk-other"); ok {
+		t.Fatalf("a different API key should not be served another key's listing")
+	}
+	if _, ok := cache.Get("openrouter", "https://openrouter.ai/api", ""); ok {
+		t.Fatalf("a missing API key should not be served a keyed listing")
+	}
+	if models, ok := cache.Get("openrouter", "https://openrouter.ai/api", " sk-team "); !ok || len(models) != 1 {
+		t.Fatalf("expected the same key to hit the cache, got %v (ok=%v)", models, ok)
+	}
+
+	entries := cache.Entries()
+	if len(entries) != 1 || strings.Contains(entries[0].Key, "sk-team") {
+		t.Fatalf("expected one entry keyed by a hash of the API key, got %+v", entries)
+	}
+
+	restored := NewModelCache(time.Minute)
+	restored.Restore(cache.Snapshot())
+	if _, ok := restored.Get("openrouter", "https://openrouter.ai/api", "sk-team"); !ok {
+		t.Fatalf("expected a restored listing to keep its API key")
+	}
+}
@@ -0,0 +1,134 @@
+package llm
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"regexp"
+	"strings"
+	"sync"
+	"unicode"
+	"unicode/utf8"
+)
+
+// maxLabelLookups bounds concurrent /api/show calls when building labels.
+const maxLabelLookups = 4
+
+type ollamaShowResponse struct {
+	Details struct {
+		Family            string `json:"family"`
+		ParameterSize     string `json:"parameter_size"`
+		QuantizationLevel string `json:"quantization_level"`
+	} `json:"details"`
+}
+
+// ModelLabels builds display labels such as "qwen3 · 4B · Q4_K_M" from
+// Ollama's /api/show. Other providers expose no such details and get no
+// labels; models whose lookup fails are left out so the UI falls back to the
+// plain name.
+func ModelLabels(ctx context.Context, target ModelsRequest, models []string, client *http.Client) map[string]string {
+	labels := map[string]string{}
+	if !strings.EqualFold(strings.TrimSpace(target.Provider), "ollama") || len(models) == 0 {
+		return labels
+	}
+	if client == nil {
+		client = MakeClient()
+	}
+	base := NormalizeBase(target.Endpoint)
+
+	var mu sync.Mutex
+	var wg sync.WaitGroup
+	slots := make(chan struct{}, maxLabelLookups)
+	for _, model := range models {
+		wg.Add(1)
+		go func(model string) {
+			defer wg.Done()
+			slots <- struct{}{}
+			defer func() { <-slots }()
+
+			label, err := ollamaModelLabel(ctx, base, model, client)
+			if err != nil || label == "" {
+				return
+			}
+			mu.Lock()
+			labels[model] = label
+			mu.Unlock()
+		}(model)
+	}
+	wg.Wait()
+	return labels
+}
+
+func ollamaModelLabel(ctx context.Context, base, model string, client *http.Client) (string, error) {
+	body, err := json.Marshal(map[string]string{"model": model})
+	if err != nil {
+		return "", err
+	}
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, base+"/api/show", bytes.NewReader(body))
+	if err != nil {
+		return "", err
+	}
+	req.Header.Set("Content-Type", "application/json")
+
+	resp, err := client.Do(req)
+	if err != nil {
+		return "", err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode >= http.StatusBadRequest {
+		return "", fmt.Errorf("ollama show %s: %w", model, statusError(resp))
+	}
+
+	var decoded ollamaShowResponse
+	if err := json.NewDecoder(resp.Body).Decode(&decoded); err != nil {
+		return "", err
+	}
+
+	var parts []string
+	for _, part := range []string{decoded.Details.Family, decoded.Details.ParameterSize, decoded.Details.QuantizationLevel} {
+		if part = strings.TrimSpace(part); part != "" {
+			parts = append(parts, part)
+		}
+	}
+	return strings.Join(parts, " · "), nil
+}
+
+// opaqueModelID matches gateway ids that are just a hash.
+var opaqueModelID = regexp.MustCompile(`^[0-9a-fA-F]{16,}$`)
+
+// PrettyModelLabel derives a friendlier label from an OpenAI-compatible model
+// id: path prefixes such as "models/" are dropped, hashes are shortened and
+// other names are title-cased. It returns the id unchanged when there is
+// nothing to clean up.
+func PrettyModelLabel(id string) string {
+	name := strings.TrimSpace(id)
+	if i := strings.LastIndex(name, "/"); i >= 0 {
+		name = name[i+1:]
+	}
+	if name == "" {
+		return id
+	}
+	if opaqueModelID.MatchString(name) {
+		return "Model " + strings.ToLower(name[:8])
+	}
+
+	words := strings.FieldsFunc(name, func(r rune) bool { return r == '-' || r == '_' || r == ' ' })
+	for i, word := range words {
+		r, size := utf8.DecodeRuneInString(word)
+		words[i] = string(unicode.ToUpper(r)) + word[size:]
+	}
+	return strings.Join(words, " ")
+}
+
+// PrettyModelLabels maps each model whose prettified label differs from its id.
+func PrettyModelLabels(models []string) map[string]string {
+	labels := map[string]string{}
+	for _, model := range models {
+		if label := PrettyModelLabel(model); label != model {
+			labels[model] = label
+		}
+	}
+	return labels
+}
@@ -0,0 +1,55 @@
+package llm
+
+import (
+	"context"
+	"sync"
+)
+
+// maxParallelTools caps how many tool calls from one turn run at once.
+const maxParallelTools = 4
+
+type toolOutcome struct {
+	result string
+	status string
+	ok     bool
+}
+
+// readOnlyTools are the tools without side effects. Only they run ahead of the
+// sequential loop, which may still stop before it reaches a later call.
+var readOnlyTools = map[string]bool{"browser": true, "web_search": true}
+
+// executeToolsParallel runs the turn's read-only tool calls concurrently and
+// returns their outcomes indexed like calls. Calls after a request_fullfilled
+// signal, calls with unparsable arguments and calls of tools with side effects
+// are left for the sequential loop, which still appends every result in the
+// model's original order.
+func (l *dialogueLoop) executeToolsParallel(ctx context.Context, calls []chatToolCall) []toolOutcome {
+	outcomes := make([]toolOutcome, len(calls))
+	sem := make(chan struct{}, maxParallelTools)
+	var wg sync.WaitGroup
+
+	for i, tc := range calls {
+		if tc.Function.Name == "request_fullfilled" {
+			break
+		}
+		if !readOnlyTools[tc.Function.Name] {
+			continue
+		}
+		args, err := parseArguments(tc.Function.Arguments)
+		if err != nil {
+			continue
+		}
+
+		wg.Add(1)
+		go func(i int, name string, args map[string]any) {
+			defer wg.Done()
+			sem <- struct{}{}
+			defer func() { <-sem }()
+			result, status := l.toolExecutor.Execute(ctx, name, args)
+			outcomes[i] = toolOutcome{result: result, status: status, ok: true}
+		}(i, tc.Function.Name, args)
+	}
+
+	wg.Wait()
+	return outcomes
+}
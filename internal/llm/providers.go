@@ -10,6 +10,7 @@ import (
 	"log"
 	"net/http"
 	"strings"
+	"sync"
 	"time"
 
 	"shell-werk/internal/tools"
@@ -49,12 +50,73 @@ type VLLMProvider struct {
 	client *http.Client
 }
 
+// OpenAIProvider talks to api.openai.com, or another hosted OpenAI-compatible
+// API, through the vLLM code path. Unlike local servers it always needs a key.
+type OpenAIProvider struct {
+	client *http.Client
+}
+
+// DefaultOpenAIBaseURL is used when an OpenAI provider has no endpoint set.
+const DefaultOpenAIBaseURL = "https://api.openai.com"
+
+// ErrMissingProviderConfig is returned when a provider lacks a required setting.
+var ErrMissingProviderConfig = errors.New("missing provider configuration")
+
+// ErrInvalidRequest is returned when a request's own fields cannot be used.
+var ErrInvalidRequest = errors.New("invalid request")
+
+// StatusError reports a provider response with a non-2xx status.
+type StatusError struct {
+	StatusCode int
+	Status     string
+}
+
+func (e *StatusError) Error() string {
+	return e.Status
+}
+
+func statusError(resp *http.Response) *StatusError {
+	return &StatusError{StatusCode: resp.StatusCode, Status: resp.Status}
+}
+
+// DefaultBaseURL returns the endpoint a provider falls back to when none is configured.
+func DefaultBaseURL(provider string) string {
+	switch strings.ToLower(strings.TrimSpace(provider)) {
+	case "ollama":
+		return "http://localhost:11434"
+	case "vllm":
+		return "http://localhost:8000"
+	case "openai":
+		return DefaultOpenAIBaseURL
+	default:
+		return ""
+	}
+}
+
+// providerBase normalizes endpoint, falling back to the provider's default.
+func providerBase(provider, endpoint string) string {
+	if base := NormalizeBase(endpoint); base != "" {
+		return base
+	}
+	return NormalizeBase(DefaultBaseURL(provider))
+}
+
+// requireAPIKey rejects keyless requests to providers that cannot work without one.
+func requireAPIKey(provider, apiKey string) error {
+	if strings.EqualFold(strings.TrimSpace(provider), "openai") && strings.TrimSpace(apiKey) == "" {
+		return fmt.Errorf("%w: %s requires an API key", ErrMissingProviderConfig, strings.ToLower(strings.TrimSpace(provider)))
+	}
+	return nil
+}
+
 type chatPayload struct {
 	Model      string                 `json:"model"`
 	Messages   []ChatMessage          `json:"messages"`
 	Stream     bool                   `json:"stream"`
 	ToolChoice string                 `json:"tool_choice,omitempty"`
 	Tools      []tools.ToolDefinition `json:"tools,omitempty"`
+	ParallelToolCalls *bool           `json:"parallel_tool_calls,omitempty"`
+	samplingFields
 }
 
 type ollamaToolCall struct {
@@ -70,7 +132,9 @@ type ollamaResponse struct {
 		Content   string           `json:"content"`
 		ToolCalls []ollamaToolCall `json:"tool_calls,omitempty"`
 	} `json:"message"`
-	Error string `json:"error"`
+	Error           string `json:"error"`
+	PromptEvalCount int    `json:"prompt_eval_count"`
+	EvalCount       int    `json:"eval_count"`
 }
 
 type openAIChoice struct {
@@ -79,10 +143,12 @@ type openAIChoice struct {
 		Content   string     `json:"content"`
 		ToolCalls []ToolCall `json:"tool_calls,omitempty"`
 	} `json:"message"`
+	FinishReason string `json:"finish_reason"`
 }
 
 type vllmResponse struct {
-	Choices []openAIChoice `json:"choices"`
+	Choices []openAIChoice   `json:"choices"`
+	Usage   *completionUsage `json:"usage"`
 	Error   struct {
 		Message string `json:"message"`
 	} `json:"error"`
@@ -97,6 +163,8 @@ type ollamaTagsResponse struct {
 type vllmModelsResponse struct {
 	Data []struct {
 		ID string `json:"id"`
+		// MaxModelLen is vLLM's context length; OpenAI omits it.
+		MaxModelLen int `json:"max_model_len,omitempty"`
 	} `json:"data"`
 }
 
@@ -104,9 +172,10 @@ func (p OllamaProvider) Chat(ctx context.Context, req ChatRequest) (ChatMessage,
 	messages := ConversationFromRequest(req)
 
 	payload := chatPayload{
-		Model:    req.Model,
-		Stream:   false,
-		Messages: messages,
+		Model:          req.Model,
+		Stream:         false,
+		Messages:       messages,
+		samplingFields: samplingFor("ollama", req.Sampling),
 	}
 
 	body, err := json.Marshal(payload)
@@ -115,19 +184,21 @@ func (p OllamaProvider) Chat(ctx context.Context, req ChatRequest) (ChatMessage,
 	}
 
 	url := NormalizeBase(req.Endpoint) + "/api/chat"
-	log.Printf("[%s] Sending Ollama request to %s with model %s", time.Now().Format(time.RFC3339), url, req.Model)
+	log.Printf("[%s] Sending Ollama request to %s with model %s", time.Now().Format(time.RFC3339), RedactURL(url), req.Model)
+	logRequestBody(req.LogLevel, "ollama", url, body)
 	httpReq, err := http.NewRequestWithContext(ctx, http.MethodPost, url, bytes.NewReader(body))
 	if err != nil {
 		return ChatMessage{}, err
 	}
 	httpReq.Header.Set("Content-Type", "application/json")
+	httpReq.Header.Set("Accept", acceptJSON)
 
 	resp, err := p.client.Do(httpReq)
 	if err != nil {
 		return ChatMessage{}, fmt.Errorf("ollama request failed: %w", err)
 	}
 	defer resp.Body.Close()
-	log.Printf("[%s] Received Ollama response", time.Now().Format(time.RFC3339))
+	log.Printf("[%s] Received Ollama response: %s", time.Now().Format(time.RFC3339), resp.Status)
 
 	rawBody, err := io.ReadAll(resp.Body)
 	if err != nil {
@@ -139,7 +210,7 @@ func (p OllamaProvider) Chat(ctx context.Context, req ChatRequest) (ChatMessage,
 		if detail == "" {
 			detail = resp.Status
 		}
-		return ChatMessage{}, fmt.Errorf("ollama returned %s: %s", resp.Status, truncate(detail, 512))
+		return ChatMessage{}, fmt.Errorf("ollama returned %w: %s", statusError(resp), truncate(detail, 512))
 	}
 
 	var decoded ollamaResponse
@@ -156,10 +227,7 @@ func (p OllamaProvider) Chat(ctx context.Context, req ChatRequest) (ChatMessage,
 		content = "(no content returned)"
 	}
 
-	role := decoded.Message.Role
-	if role == "" {
-		role = "assistant"
-	}
+	role := assistantRole("ollama", decoded.Message.Role)
 
 	var toolCalls []ToolCall
 	for _, tc := range decoded.Message.ToolCalls {
@@ -173,22 +241,24 @@ func (p OllamaProvider) Chat(ctx context.Context, req ChatRequest) (ChatMessage,
 		})
 	}
 
-	return ChatMessage{Role: role, Content: content, ToolCalls: toolCalls}, nil
+	return ChatMessage{Role: role, Content: content, ToolCalls: toolCalls, Usage: ollamaUsage(decoded.PromptEvalCount, decoded.EvalCount)}, nil
 }
 
 func (p VLLMProvider) Chat(ctx context.Context, req ChatRequest) (ChatMessage, error) {
 	messages := ConversationFromRequest(req)
 
 	payload := chatPayload{
-		Model:    req.Model,
-		Stream:   false,
-		Messages: messages,
+		Model:          req.Model,
+		Stream:         false,
+		Messages:       messages,
+		samplingFields: samplingFor("vllm", req.Sampling),
 	}
 
 	// Only request tool selection when tools are actually provided to avoid vLLM 400s.
 	if len(req.Tools) > 0 {
 		payload.ToolChoice = "auto"
 		payload.Tools = req.ToolDefs
+		payload.ParallelToolCalls = req.ParallelToolCalls
 	}
 
 	body, err := json.Marshal(payload)
@@ -197,12 +267,14 @@ func (p VLLMProvider) Chat(ctx context.Context, req ChatRequest) (ChatMessage, e
 	}
 
 	url := NormalizeBase(req.Endpoint) + "/v1/chat/completions"
-	log.Printf("[%s] Sending VLLM request to %s with model %s", time.Now().Format(time.RFC3339), url, req.Model)
+	log.Printf("[%s] Sending VLLM request to %s with model %s", time.Now().Format(time.RFC3339), RedactURL(url), req.Model)
+	logRequestBody(req.LogLevel, "vllm", url, body)
 	httpReq, err := http.NewRequestWithContext(ctx, http.MethodPost, url, bytes.NewReader(body))
 	if err != nil {
 		return ChatMessage{}, err
 	}
 	httpReq.Header.Set("Content-Type", "application/json")
+	httpReq.Header.Set("Accept", acceptJSON)
 	if strings.TrimSpace(req.APIKey) != "" {
 		httpReq.Header.Set("Authorization", "Bearer "+req.APIKey)
 	}
@@ -212,7 +284,7 @@ func (p VLLMProvider) Chat(ctx context.Context, req ChatRequest) (ChatMessage, e
 		return ChatMessage{}, fmt.Errorf("vllm request failed: %w", err)
 	}
 	defer resp.Body.Close()
-	log.Printf("[%s] Received VLLM response", time.Now().Format(time.RFC3339))
+	log.Printf("[%s] Received VLLM response: %s", time.Now().Format(time.RFC3339), resp.Status)
 
 	rawBody, err := io.ReadAll(resp.Body)
 	if err != nil {
@@ -224,7 +296,7 @@ func (p VLLMProvider) Chat(ctx context.Context, req ChatRequest) (ChatMessage, e
 		if detail == "" {
 			detail = resp.Status
 		}
-		return ChatMessage{}, fmt.Errorf("vllm returned %s: %s", resp.Status, truncate(detail, 512))
+		return ChatMessage{}, fmt.Errorf("vllm returned %w: %s", statusError(resp), truncate(detail, 512))
 	}
 
 	var decoded vllmResponse
@@ -243,42 +315,76 @@ func (p VLLMProvider) Chat(ctx context.Context, req ChatRequest) (ChatMessage, e
 	if content == "" && len(decoded.Choices[0].Message.ToolCalls) == 0 {
 		content = "(no content returned)"
 	}
-	role := decoded.Choices[0].Message.Role
-	if role == "" {
-		role = "assistant"
-	}
+	role := assistantRole("vllm", decoded.Choices[0].Message.Role)
 
-	return ChatMessage{Role: role, Content: content, ToolCalls: decoded.Choices[0].Message.ToolCalls}, nil
+	return ChatMessage{
+		Role:         role,
+		Content:      content,
+		ToolCalls:    decoded.Choices[0].Message.ToolCalls,
+		FinishReason: providerFinishReason(decoded.Choices[0].FinishReason),
+		Usage:        decoded.Usage.toUsage(),
+	}, nil
+}
+
+func (p OpenAIProvider) Chat(ctx context.Context, req ChatRequest) (ChatMessage, error) {
+	if err := requireAPIKey("openai", req.APIKey); err != nil {
+		return ChatMessage{}, err
+	}
+	req.Endpoint = providerBase("openai", req.Endpoint)
+	return VLLMProvider{client: p.client}.Chat(ctx, req)
 }
 
 // ProviderFor chooses a provider implementation; defaults to mock.
 func ProviderFor(name string) ChatProvider {
+	return providerWithClient(name, MakeClient())
+}
+
+func providerWithClient(name string, client *http.Client) ChatProvider {
 	switch strings.ToLower(name) {
 	case "mock":
 		return MockProvider{}
 	case "ollama":
-		return OllamaProvider{client: MakeClient()}
+		return OllamaProvider{client: client}
 	case "vllm":
-		return VLLMProvider{client: MakeClient()}
+		return VLLMProvider{client: client}
+	case "openai":
+		return OpenAIProvider{client: client}
 	default:
 		return MockProvider{}
 	}
 }
 
+// ProviderRequiresAuth reports whether a provider normally needs an API key.
+// Local servers such as Ollama and vLLM default to unauthenticated access.
+func ProviderRequiresAuth(name string) bool {
+	switch strings.ToLower(strings.TrimSpace(name)) {
+	case "openai", "anthropic", "groq", "azure", "azure-openai", "mistral", "openrouter", "together", "gemini":
+		return true
+	default:
+		return false
+	}
+}
+
 // ListModels returns available model identifiers for the given provider and endpoint.
-// The HTTP client can be injected for tests; MakeClient is used when nil.
+// The HTTP client can be injected for tests; when nil, MakeClient is used with
+// the default retries.
 func ListModels(ctx context.Context, provider, endpoint, apiKey string, client *http.Client) ([]string, error) {
 	if client == nil {
-		client = MakeClient()
+		client = WithRetries(MakeClient(), 0)
 	}
 
-	base := NormalizeBase(endpoint)
+	base := providerBase(provider, endpoint)
 
 	switch strings.ToLower(provider) {
 	case "ollama":
 		return listOllamaModels(ctx, base, client)
 	case "vllm":
 		return listVLLMModels(ctx, base, apiKey, client)
+	case "openai":
+		if err := requireAPIKey(provider, apiKey); err != nil {
+			return nil, err
+		}
+		return listVLLMModels(ctx, base, apiKey, client)
 	case "mock":
 		return []string{"mock"}, nil
 	default:
@@ -286,6 +392,42 @@ func ListModels(ctx context.Context, provider, endpoint, apiKey string, client *
 	}
 }
 
+// ListModelsAll queries every target concurrently so one unreachable provider
+// does not block the rest. Results are keyed by target ID, or by provider name
+// when no ID is set.
+func ListModelsAll(ctx context.Context, targets []ModelsRequest, client *http.Client) AllModels {
+	result := AllModels{Models: map[string][]string{}, Errors: map[string]string{}}
+
+	var mu sync.Mutex
+	var wg sync.WaitGroup
+	for _, target := range targets {
+		wg.Add(1)
+		go func(target ModelsRequest) {
+			defer wg.Done()
+			models, err := ListModels(ctx, target.Provider, target.Endpoint, target.APIKey, client)
+
+			mu.Lock()
+			defer mu.Unlock()
+			key := modelsKey(target)
+			if err != nil {
+				result.Errors[key] = err.Error()
+				return
+			}
+			result.Models[key] = models
+		}(target)
+	}
+	wg.Wait()
+
+	return result
+}
+
+func modelsKey(target ModelsRequest) string {
+	if id := strings.TrimSpace(target.ID); id != "" {
+		return id
+	}
+	return strings.ToLower(strings.TrimSpace(target.Provider))
+}
+
 func listOllamaModels(ctx context.Context, base string, client *http.Client) ([]string, error) {
 	url := base + "/api/tags"
 	req, err := http.NewRequestWithContext(ctx, http.MethodGet, url, nil)
@@ -300,7 +442,7 @@ func listOllamaModels(ctx context.Context, base string, client *http.Client) ([]
 	defer resp.Body.Close()
 
 	if resp.StatusCode >= http.StatusBadRequest {
-		return nil, fmt.Errorf("ollama list models: %s", resp.Status)
+		return nil, fmt.Errorf("ollama list models: %w", statusError(resp))
 	}
 
 	var decoded ollamaTagsResponse
@@ -335,7 +477,7 @@ func listVLLMModels(ctx context.Context, base, apiKey string, client *http.Clien
 	defer resp.Body.Close()
 
 	if resp.StatusCode >= http.StatusBadRequest {
-		return nil, fmt.Errorf("vllm list models: %s", resp.Status)
+		return nil, fmt.Errorf("vllm list models: %w", statusError(resp))
 	}
 
 	var decoded vllmModelsResponse
@@ -1,10 +1,16 @@
 package llm
 
 import (
+	"bytes"
 	"context"
 	"encoding/json"
+	"errors"
+	"fmt"
+	"log"
 	"net/http"
 	"net/http/httptest"
+	"net/url"
+	"os"
 	"strings"
 	"testing"
 )
@@ -25,8 +31,224 @@ func TestNormalizeBase(t *testing.T) {
 	}
 }
 
+func TestNormalizeBaseURL(t *testing.T) {
+	got, err := NormalizeBaseURL("ollama", "localhost:11434/")
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if got != "http://localhost:11434" {
+		t.Fatalf("NormalizeBaseURL = %q, want http://localhost:11434", got)
+	}
+
+	for _, bad := range []string{"", "ftp://example.com", "http://"} {
+		if _, err := NormalizeBaseURL("vllm", bad); err == nil {
+			t.Errorf("expected error for %q", bad)
+		}
+	}
+}
+
+func TestProviderRequiresAuth(t *testing.T) {
+	for _, name := range []string{"openai", "Anthropic", "groq", "azure"} {
+		if !ProviderRequiresAuth(name) {
+			t.Errorf("expected %s to require auth", name)
+		}
+	}
+	for _, name := range []string{"ollama", "vllm", "mock", ""} {
+		if ProviderRequiresAuth(name) {
+			t.Errorf("expected %s not to require auth", name)
+		}
+	}
+}
+
+func TestValidateProvidersFlagsMalformedURL(t *testing.T) {
+	results := ValidateProviders([]ModelsRequest{
+		{ID: "local", Provider: "ollama", Endpoint: "localhost:11434/"},
+		{ID: "broken", Provider: "vllm", Endpoint: "ftp://example.com"},
+	})
+	if len(results) != 2 {
+		t.Fatalf("expected two results, got %+v", results)
+	}
+
+	local := results[0]
+	if !local.ValidURL || local.Error != "" || local.Endpoint != "http://localhost:11434" || len(local.Warnings) == 0 {
+		t.Fatalf("expected valid endpoint with a normalization warning, got %+v", local)
+	}
+	broken := results[1]
+	if broken.ID != "broken" || broken.ValidURL || broken.Error == "" {
+		t.Fatalf("expected malformed endpoint to be flagged, got %+v", broken)
+	}
+}
+
+func TestValidateProvidersWarnsAboutAPIKeyOnLoopback(t *testing.T) {
+	results := ValidateProviders([]ModelsRequest{
+		{ID: "local", Provider: "vllm", Endpoint: "http://127.0.0.1:8000", APIKey: "sk-local"},
+		{ID: "remote", Provider: "vllm", Endpoint: "https://gpu.example.com", APIKey: "sk-remote"},
+		{ID: "keyless", Provider: "ollama", Endpoint: "http://localhost:11434"},
+	})
+
+	hasKeyWarning := func(result ProviderValidation) bool {
+		for _, warning := range result.Warnings {
+			if strings.Contains(warning, "local endpoint") {
+				return true
+			}
+		}
+		return false
+	}
+	if local := results[0]; !hasKeyWarning(local) || !local.ValidURL || local.Error != "" {
+		t.Fatalf("expected an advisory warning for the loopback key, got %+v", local)
+	}
+	if hasKeyWarning(results[1]) || hasKeyWarning(results[2]) {
+		t.Fatalf("expected no key warning for remote or keyless endpoints, got %+v", results[1:])
+	}
+}
+
+func TestDetectDuplicateEndpointsGroupsSameURL(t *testing.T) {
+	groups := DetectDuplicateEndpoints([]ModelsRequest{
+		{ID: "work", Provider: "ollama", Endpoint: "localhost:11434/"},
+		{ID: "gpu", Provider: "vllm", Endpoint: "http://gpu:8000"},
+		{ID: "home", Provider: "ollama", Endpoint: "http://LOCALHOST:11434"},
+		{ID: "demo", Provider: "mock"},
+	})
+	if len(groups) != 1 || len(groups[0]) != 2 || groups[0][0] != "work" || groups[0][1] != "home" {
+		t.Fatalf("expected work and home to be grouped, got %v", groups)
+	}
+}
+
+func TestModelLabelsUseOllamaShowDetails(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		switch r.URL.Path {
+		case "/api/tags":
+			fmt.Fprint(w, `{"models":[{"name":"qwen3:4b"},{"name":"broken:1b"}]}`)
+		case "/api/show":
+			var body struct {
+				Model string `json:"model"`
+			}
+			if err := json.NewDecoder(r.Body).Decode(&body); err != nil {
+				t.Fatalf("failed to decode show payload: %v", err)
+			}
+			if body.Model != "qwen3:4b" {
+				http.Error(w, "not found", http.StatusNotFound)
+				return
+			}
+			fmt.Fprint(w, `{"details":{"family":"qwen3","parameter_size":"4B","quantization_level":"Q4_K_M"}}`)
+		default:
+			t.Fatalf("unexpected path: %s", r.URL.Path)
+		}
+	}))
+	defer server.Close()
+
+	target := ModelsRequest{Provider: "ollama", Endpoint: server.URL, DetailedLabels: true}
+	models, err := ListModels(context.Background(), target.Provider, target.Endpoint, "", server.Client())
+	if err != nil {
+		t.Fatalf("ListModels returned error: %v", err)
+	}
+	labels := ModelLabels(context.Background(), target, models, server.Client())
+	if labels["qwen3:4b"] != "qwen3 · 4B · Q4_K_M" {
+		t.Fatalf("expected enriched label, got %q", labels["qwen3:4b"])
+	}
+	if _, ok := labels["broken:1b"]; ok {
+		t.Fatalf("failed lookups should be left out, got %v", labels)
+	}
+}
+
+func TestProviderVersionReadsOllamaVersion(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.Path != "/api/version" {
+			http.NotFound(w, r)
+			return
+		}
+		_ = json.NewEncoder(w).Encode(map[string]string{"version": "0.5.7"})
+	}))
+	defer server.Close()
+
+	version, err := ProviderVersion(context.Background(), ModelsRequest{Provider: "ollama", Endpoint: server.URL}, server.Client())
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if version != "0.5.7" {
+		t.Fatalf("expected version 0.5.7, got %q", version)
+	}
+
+	version, err = ProviderVersion(context.Background(), ModelsRequest{Provider: "vllm", Endpoint: server.URL}, server.Client())
+	if err != nil || version != "" {
+		t.Fatalf("expected no version for missing endpoint, got %q (%v)", version, err)
+	}
+}
+
+func TestModelWarmStateReadsOllamaPs(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.Path != "/api/ps" {
+			http.NotFound(w, r)
+			return
+		}
+		fmt.Fprint(w, `{"models":[{"name":"llama3:latest","size_vram":4294967296,"expires_at":"2030-01-01T00:00:00Z"}]}`)
+	}))
+	defer server.Close()
+
+	infos, err := ModelWarmState(context.Background(), ModelsRequest{Provider: "ollama", Endpoint: server.URL}, []string{"qwen3:4b", "llama3:latest"}, server.Client())
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if len(infos) != 2 || infos[0].State != WarmStateCold || infos[1].State != WarmStateWarm {
+		t.Fatalf("expected llama3 warm and qwen3 cold, got %+v", infos)
+	}
+	if infos[1].SizeVRAM != 4294967296 || infos[1].ExpiresAt == nil {
+		t.Fatalf("expected VRAM size and expiry for the loaded model, got %+v", infos[1])
+	}
+
+	infos, err = ModelWarmState(context.Background(), ModelsRequest{Provider: "vllm", Endpoint: server.URL}, []string{"qwen3"}, server.Client())
+	if err != nil || len(infos) != 1 || infos[0].State != WarmStateUnknown {
+		t.Fatalf("expected an unknown state for vllm, got %+v (%v)", infos, err)
+	}
+}
+
+func TestProviderAccelerationReadsOllamaPs(t *testing.T) {
+	body := `{"models":[{"name":"llama3:latest","size":4294967296,"size_vram":4294967296}]}`
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.Path != "/api/ps" {
+			http.NotFound(w, r)
+			return
+		}
+		fmt.Fprint(w, body)
+	}))
+	defer server.Close()
+
+	target := ModelsRequest{Provider: "ollama", Endpoint: server.URL}
+	if got, err := ProviderAcceleration(context.Background(), target, server.Client()); err != nil || got != "GPU" {
+		t.Fatalf("expected GPU, got %q (%v)", got, err)
+	}
+
+	body = `{"models":[{"name":"llama3:70b","size":400,"size_vram":100}]}`
+	if got, err := ProviderAcceleration(context.Background(), target, server.Client()); err != nil || got != "75% CPU / 25% GPU" {
+		t.Fatalf("expected a CPU/GPU split, got %q (%v)", got, err)
+	}
+
+	target.Provider = "vllm"
+	if got, err := ProviderAcceleration(context.Background(), target, server.Client()); err != nil || got != "" {
+		t.Fatalf("expected no answer for vllm, got %q (%v)", got, err)
+	}
+}
+
+type recordingTransport struct{ url string }
+
+func (t *recordingTransport) RoundTrip(req *http.Request) (*http.Response, error) {
+	t.url = req.URL.String()
+	return nil, errors.New("offline")
+}
+
+func TestProviderAccelerationDefaultsToOllamaBase(t *testing.T) {
+	transport := &recordingTransport{}
+	target := ModelsRequest{Provider: "ollama"}
+	if _, err := ProviderAcceleration(context.Background(), target, &http.Client{Transport: transport}); err == nil {
+		t.Fatalf("expected the offline transport to fail")
+	}
+	if transport.url != "http://localhost:11434/api/ps" {
+		t.Fatalf("expected the default Ollama base without an endpoint, got %q", transport.url)
+	}
+}
+
 func TestProviderFor(t *testing.T) {
-	cases := []string{"mock", "ollama", "vllm", ""}
+	cases := []string{"mock", "ollama", "vllm", "OpenAI", ""}
 
 	for _, name := range cases {
 		provider := ProviderFor(name)
@@ -42,6 +264,12 @@ func TestProviderFor(t *testing.T) {
 			}
 			continue
 		}
+		if strings.ToLower(name) == "openai" {
+			if _, ok := provider.(OpenAIProvider); !ok {
+				t.Errorf("ProviderFor(%q) = %T, want OpenAIProvider", name, provider)
+			}
+			continue
+		}
 		if _, ok := provider.(MockProvider); !ok {
 			t.Errorf("ProviderFor(%q) = %T, want MockProvider", name, provider)
 		}
@@ -137,9 +365,10 @@ func TestOllamaProviderChatDefaultsAndError(t *testing.T) {
 
 func TestVLLMProviderChatSuccess(t *testing.T) {
 	var received chatPayload
-	var authHeader string
+	var authHeader, acceptHeader string
 	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 		authHeader = r.Header.Get("Authorization")
+		acceptHeader = r.Header.Get("Accept")
 		if r.URL.Path != "/v1/chat/completions" {
 			t.Fatalf("unexpected path: %s", r.URL.Path)
 		}
@@ -171,6 +400,49 @@ func TestVLLMProviderChatSuccess(t *testing.T) {
 	if authHeader != "Bearer secret" {
 		t.Fatalf("expected Authorization header to be set, got %q", authHeader)
 	}
+	if acceptHeader != "application/json" {
+		t.Fatalf("expected Accept application/json, got %q", acceptHeader)
+	}
+}
+
+func TestVLLMProviderLogsRequestBodyWhenEnabled(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		resp := vllmResponse{Choices: []openAIChoice{{}}}
+		resp.Choices[0].Message.Content = "pong"
+		_ = json.NewEncoder(w).Encode(resp)
+	}))
+	defer server.Close()
+
+	var logged bytes.Buffer
+	log.SetOutput(&logged)
+	defer log.SetOutput(os.Stderr)
+
+	provider := VLLMProvider{client: server.Client()}
+	req := ChatRequest{Endpoint: server.URL, Model: "mistral", Message: "ping", APIKey: "sk-secret"}
+	if _, err := provider.Chat(context.Background(), req); err != nil {
+		t.Fatalf("VLLMProvider.Chat returned error: %v", err)
+	}
+	if strings.Contains(logged.String(), "[debug]") {
+		t.Fatalf("expected no body logging by default, got:\n%s", logged.String())
+	}
+
+	logged.Reset()
+	req.LogLevel = LogLevelDebug
+	if _, err := provider.Chat(context.Background(), req); err != nil {
+		t.Fatalf("VLLMProvider.Chat returned error: %v", err)
+	}
+	out := logged.String()
+	for _, want := range []string{"Sending VLLM request", `[debug] vllm request to`, `"content":"ping"`, "Received VLLM response: 200 OK"} {
+		if !strings.Contains(out, want) {
+			t.Fatalf("expected log to contain %q, got:\n%s", want, out)
+		}
+	}
+	if strings.Contains(out, "sk-secret") {
+		t.Fatalf("API key leaked into the log:\n%s", out)
+	}
+	if ParseLogLevel(" DEBUG ") != LogLevelDebug || ParseLogLevel("") != LogLevelInfo {
+		t.Fatalf("expected log levels to parse case-insensitively with an info default")
+	}
 }
 
 func TestVLLMProviderChatDefaultsAndErrors(t *testing.T) {
@@ -193,6 +465,22 @@ func TestVLLMProviderChatDefaultsAndErrors(t *testing.T) {
 		}
 	})
 
+	t.Run("coerces a non-assistant role", func(t *testing.T) {
+		server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			_, _ = w.Write([]byte(`{"choices":[{"message":{"role":"user","content":"hello"}}]}`))
+		}))
+		defer server.Close()
+
+		provider := VLLMProvider{client: server.Client()}
+		msg, err := provider.Chat(context.Background(), ChatRequest{Endpoint: server.URL, Model: "x", Message: "hi"})
+		if err != nil {
+			t.Fatalf("unexpected error: %v", err)
+		}
+		if msg.Role != "assistant" || msg.Content != "hello" {
+			t.Fatalf("expected an assistant turn with the content, got %+v", msg)
+		}
+	})
+
 	t.Run("returns API error", func(t *testing.T) {
 		server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 			_ = json.NewEncoder(w).Encode(vllmResponse{Error: struct {
@@ -272,6 +560,32 @@ func TestListModelsVLLM(t *testing.T) {
 	}
 }
 
+func TestOpenAIRequiresAPIKey(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Header.Get("Authorization") != "Bearer sk-test" {
+			t.Fatalf("expected the API key to be sent, got %q", r.Header.Get("Authorization"))
+		}
+		fmt.Fprint(w, `{"data":[{"id":"gpt-4o-mini"}]}`)
+	}))
+	defer server.Close()
+
+	if _, err := ListModels(context.Background(), "openai", server.URL, "", server.Client()); !errors.Is(err, ErrMissingProviderConfig) {
+		t.Fatalf("expected missing config error when listing, got %v", err)
+	}
+	provider := OpenAIProvider{client: server.Client()}
+	if _, err := provider.Chat(context.Background(), ChatRequest{Endpoint: server.URL, Model: "gpt-4o-mini", Message: "hi"}); !errors.Is(err, ErrMissingProviderConfig) {
+		t.Fatalf("expected missing config error for chat, got %v", err)
+	}
+
+	models, err := ListModels(context.Background(), "openai", server.URL, "sk-test", server.Client())
+	if err != nil || len(models) != 1 || models[0] != "gpt-4o-mini" {
+		t.Fatalf("expected models with a key, got %v (%v)", models, err)
+	}
+	if base, err := NormalizeBaseURL("openai", ""); err != nil || base != DefaultOpenAIBaseURL {
+		t.Fatalf("expected the default OpenAI base URL, got %q (%v)", base, err)
+	}
+}
+
 func TestListModelsErrors(t *testing.T) {
 	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 		w.WriteHeader(http.StatusInternalServerError)
@@ -287,3 +601,214 @@ func TestListModelsErrors(t *testing.T) {
 		t.Fatalf("expected error for unsupported provider")
 	}
 }
+
+func TestOllamaProviderNestsSamplingUnderOptions(t *testing.T) {
+	var received map[string]any
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if err := json.NewDecoder(r.Body).Decode(&received); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		resp := ollamaResponse{}
+		resp.Message.Role = "assistant"
+		resp.Message.Content = "ok"
+		_ = json.NewEncoder(w).Encode(resp)
+	}))
+	defer server.Close()
+
+	temperature := 0.2
+	seed := 7
+	numCtx := 4096
+	provider := OllamaProvider{client: server.Client()}
+	_, err := provider.Chat(context.Background(), ChatRequest{
+		Endpoint: server.URL,
+		Model:    "llama3",
+		Message:  "hi",
+		Sampling: SamplingParams{Temperature: &temperature, Seed: &seed, NumCtx: &numCtx, Stop: []string{"</s>"}},
+	})
+	if err != nil {
+		t.Fatalf("OllamaProvider.Chat returned error: %v", err)
+	}
+
+	for _, key := range []string{"stop", "temperature", "seed", "num_ctx"} {
+		if _, ok := received[key]; ok {
+			t.Fatalf("expected %s to be omitted at the top level, got %+v", key, received)
+		}
+	}
+	options, ok := received["options"].(map[string]any)
+	if !ok {
+		t.Fatalf("expected options object, got %+v", received)
+	}
+	stop, ok := options["stop"].([]any)
+	if !ok || len(stop) != 1 || stop[0] != "</s>" {
+		t.Fatalf("expected stop nested under options, got %+v", options)
+	}
+	if options["temperature"] != 0.2 || options["seed"] != float64(7) || options["num_ctx"] != float64(4096) {
+		t.Fatalf("unexpected options payload: %+v", options)
+	}
+}
+
+func TestVLLMProviderKeepsSamplingTopLevel(t *testing.T) {
+	var received map[string]any
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if err := json.NewDecoder(r.Body).Decode(&received); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		resp := vllmResponse{Choices: []openAIChoice{{}}}
+		resp.Choices[0].Message.Content = "ok"
+		_ = json.NewEncoder(w).Encode(resp)
+	}))
+	defer server.Close()
+
+	topP := 0.9
+	provider := VLLMProvider{client: server.Client()}
+	_, err := provider.Chat(context.Background(), ChatRequest{
+		Endpoint: server.URL,
+		Model:    "mistral",
+		Message:  "hi",
+		Sampling: SamplingParams{TopP: &topP, Stop: []string{"\n\n"}},
+	})
+	if err != nil {
+		t.Fatalf("VLLMProvider.Chat returned error: %v", err)
+	}
+
+	if _, ok := received["options"]; ok {
+		t.Fatalf("expected no options object for OpenAI-compatible payloads, got %+v", received)
+	}
+	if received["top_p"] != 0.9 {
+		t.Fatalf("expected top_p at the top level, got %+v", received)
+	}
+	stop, ok := received["stop"].([]any)
+	if !ok || len(stop) != 1 || stop[0] != "\n\n" {
+		t.Fatalf("expected stop at the top level, got %+v", received)
+	}
+}
+
+func TestVLLMProviderSerializesParallelToolCalls(t *testing.T) {
+	var received map[string]any
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		received = nil
+		if err := json.NewDecoder(r.Body).Decode(&received); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		resp := vllmResponse{Choices: []openAIChoice{{}}}
+		resp.Choices[0].Message.Content = "ok"
+		_ = json.NewEncoder(w).Encode(resp)
+	}))
+	defer server.Close()
+
+	provider := VLLMProvider{client: server.Client()}
+	disabled := false
+	req := ChatRequest{Endpoint: server.URL, Model: "mistral", Message: "hi", Tools: []string{"shell"}, ParallelToolCalls: &disabled}
+	if _, err := provider.Chat(context.Background(), req); err != nil {
+		t.Fatalf("VLLMProvider.Chat returned error: %v", err)
+	}
+	if value, ok := received["parallel_tool_calls"]; !ok || value != false {
+		t.Fatalf("expected parallel_tool_calls=false, got %+v", received)
+	}
+
+	req.ParallelToolCalls = nil
+	if _, err := provider.Chat(context.Background(), req); err != nil {
+		t.Fatalf("VLLMProvider.Chat returned error: %v", err)
+	}
+	if _, ok := received["parallel_tool_calls"]; ok {
+		t.Fatalf("expected parallel_tool_calls to be omitted when unset, got %+v", received)
+	}
+}
+
+func TestListModelsAllCollectsPerProviderErrors(t *testing.T) {
+	healthy := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		_ = json.NewEncoder(w).Encode(ollamaTagsResponse{Models: []struct {
+			Name string `json:"name"`
+		}{{Name: "llama3"}}})
+	}))
+	defer healthy.Close()
+
+	failing := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.WriteHeader(http.StatusServiceUnavailable)
+	}))
+	defer failing.Close()
+
+	result := ListModelsAll(context.Background(), []ModelsRequest{
+		{ID: "local", Provider: "ollama", Endpoint: healthy.URL},
+		{ID: "gpu-box", Provider: "vllm", Endpoint: failing.URL},
+	}, nil)
+
+	if models := result.Models["local"]; len(models) != 1 || models[0] != "llama3" {
+		t.Fatalf("expected models from the healthy provider, got %+v", result.Models)
+	}
+	if _, ok := result.Models["gpu-box"]; ok {
+		t.Fatalf("expected no models for the failing provider")
+	}
+	if !strings.Contains(result.Errors["gpu-box"], "vllm list models") {
+		t.Fatalf("expected error entry for the failing provider, got %+v", result.Errors)
+	}
+	if len(result.Errors) != 1 {
+		t.Fatalf("expected exactly one error entry, got %+v", result.Errors)
+	}
+}
+
+func TestRedactErrorStripsURLCredentials(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {}))
+	endpoint := strings.Replace(server.URL, "http://", "http://alice:hunter2@", 1) + "/v1/models?api_key=sk-secret"
+	server.Close()
+
+	_, err := MakeClient().Get(endpoint)
+	if err == nil {
+		t.Fatalf("expected a connection error from a closed server")
+	}
+	redacted := RedactError(fmt.Errorf("list models: %w", err))
+	for _, secret := range []string{"alice", "hunter2", "sk-secret"} {
+		if strings.Contains(redacted.Error(), secret) {
+			t.Fatalf("expected %q to be redacted, got %q", secret, redacted.Error())
+		}
+	}
+	if !strings.Contains(redacted.Error(), "/v1/models") {
+		t.Fatalf("expected the rest of the URL to remain, got %q", redacted.Error())
+	}
+	var urlErr *url.Error
+	if !errors.As(redacted, &urlErr) {
+		t.Fatalf("expected the original error to stay reachable, got %T", redacted)
+	}
+}
+
+func TestListModelsRetriesWhileServerIsLoading(t *testing.T) {
+	calls := 0
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		calls++
+		if calls <= 2 {
+			w.WriteHeader(http.StatusServiceUnavailable)
+			return
+		}
+		fmt.Fprint(w, `{"data":[{"id":"qwen3"}]}`)
+	}))
+	defer server.Close()
+
+	models, err := ListModels(context.Background(), "vllm", server.URL, "", nil)
+	if err != nil {
+		t.Fatalf("ListModels returned error: %v", err)
+	}
+	if len(models) != 1 || models[0] != "qwen3" {
+		t.Fatalf("expected models after retries, got %+v", models)
+	}
+	if calls != 3 {
+		t.Fatalf("expected 3 attempts, got %d", calls)
+	}
+}
+
+func TestWithRetriesGivesUpAfterConfiguredAttempts(t *testing.T) {
+	calls := 0
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		calls++
+		w.WriteHeader(http.StatusBadGateway)
+	}))
+	defer server.Close()
+
+	resp, err := WithRetries(server.Client(), 1).Post(server.URL, "application/json", strings.NewReader(`{}`))
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	resp.Body.Close()
+	if resp.StatusCode != http.StatusBadGateway || calls != 1 {
+		t.Fatalf("expected a single attempt returning 502, got %d after %d calls", resp.StatusCode, calls)
+	}
+}
@@ -0,0 +1,130 @@
+package llm
+
+import (
+	"errors"
+	"net/url"
+	"regexp"
+	"strings"
+)
+
+const redactedValue = "[REDACTED]"
+
+// secretKeys are the credential key names, in snake case, that get redacted.
+// They are matched whole so counters such as max_tokens stay readable.
+var secretKeys = map[string]bool{
+	"api_key":       true,
+	"apikey":        true,
+	"authorization": true,
+	"password":      true,
+	"passwd":        true,
+	"secret":        true,
+	"token":         true,
+	"bearer":        true,
+	"private_key":   true,
+}
+
+// secretKeySuffixes catch prefixed credentials such as access_token or
+// client_secret.
+var secretKeySuffixes = []string{"_api_key", "_token", "_secret", "_password"}
+
+func isSecretKey(key string) bool {
+	name := snakeCase(key)
+	if secretKeys[name] {
+		return true
+	}
+	for _, suffix := range secretKeySuffixes {
+		if strings.HasSuffix(name, suffix) {
+			return true
+		}
+	}
+	return false
+}
+
+// snakeCase lowercases key and separates its words with underscores, so
+// "accessToken", "access-token" and "ACCESS_TOKEN" compare equal.
+func snakeCase(key string) string {
+	var b strings.Builder
+	for i, r := range key {
+		switch {
+		case r == '-' || r == ' ':
+			b.WriteByte('_')
+		case r >= 'A' && r <= 'Z':
+			if i > 0 && key[i-1] >= 'a' && key[i-1] <= 'z' {
+				b.WriteByte('_')
+			}
+			b.WriteRune(r + ('a' - 'A'))
+		default:
+			b.WriteRune(r)
+		}
+	}
+	return b.String()
+}
+
+// redactSecrets returns a copy of a decoded JSON value with credential-like
+// object keys replaced by a placeholder.
+func redactSecrets(value any) any {
+	switch v := value.(type) {
+	case map[string]any:
+		out := make(map[string]any, len(v))
+		for key, inner := range v {
+			if isSecretKey(key) {
+				out[key] = redactedValue
+				continue
+			}
+			out[key] = redactSecrets(inner)
+		}
+		return out
+	case []any:
+		out := make([]any, len(v))
+		for i, inner := range v {
+			out[i] = redactSecrets(inner)
+		}
+		return out
+	default:
+		return value
+	}
+}
+
+// urlUserinfo matches credentials embedded in a URL, e.g. "user:pass@".
+var urlUserinfo = regexp.MustCompile(`([a-zA-Z][a-zA-Z0-9+.-]*://)[^/\s@]+@`)
+
+// RedactURL drops userinfo and the query string, which may carry tokens, from
+// a URL. Values that don't parse have only their userinfo removed.
+func RedactURL(raw string) string {
+	parsed, err := url.Parse(strings.TrimSpace(raw))
+	if err != nil {
+		return urlUserinfo.ReplaceAllString(raw, "$1")
+	}
+	parsed.User = nil
+	parsed.RawQuery = ""
+	parsed.ForceQuery = false
+	return parsed.String()
+}
+
+// RedactError rewrites err's message so URLs in it carry no credentials. The
+// original error stays reachable through errors.Is and errors.As.
+func RedactError(err error) error {
+	if err == nil {
+		return nil
+	}
+	msg := err.Error()
+	redacted := msg
+	var urlErr *url.Error
+	if errors.As(err, &urlErr) && urlErr.URL != "" {
+		redacted = strings.ReplaceAll(redacted, urlErr.URL, RedactURL(urlErr.URL))
+	}
+	redacted = urlUserinfo.ReplaceAllString(redacted, "$1")
+	if redacted == msg {
+		return err
+	}
+	return &redactedError{msg: redacted, err: err}
+}
+
+type redactedError struct {
+	msg string
+	err error
+}
+
+func (e *redactedError) Error() string { return e.msg }
+
+func (e *redactedError) Unwrap() error { return e.err }
@@ -0,0 +1,33 @@
+package llm
+
+import (
+	"fmt"
+	"strings"
+)
+
+// ErrNothingToRegenerate is returned when a history does not end with an
+// assistant reply that could be generated again.
+var ErrNothingToRegenerate = fmt.Errorf("%w: history does not end with an assistant reply", ErrInvalidRequest)
+
+// TrimForRegeneration drops the trailing assistant reply, together with the
+// tool messages and tool-calling turns that led to it, so the last user
+// message can be answered afresh. The history must end with an assistant
+// turn that follows a user message.
+func TrimForRegeneration(history []ChatMessage) ([]ChatMessage, error) {
+	if len(history) == 0 || !strings.EqualFold(strings.TrimSpace(history[len(history)-1].Role), "assistant") {
+		return nil, ErrNothingToRegenerate
+	}
+	end := len(history)
+	for end > 0 {
+		switch strings.ToLower(strings.TrimSpace(history[end-1].Role)) {
+		case "assistant", "tool":
+			end--
+			continue
+		}
+		break
+	}
+	if end == 0 || !strings.EqualFold(strings.TrimSpace(history[end-1].Role), "user") {
+		return nil, ErrNothingToRegenerate
+	}
+	return append([]ChatMessage(nil), history[:end]...), nil
+}
@@ -0,0 +1,69 @@
+package llm
+
+import (
+	"errors"
+	"strings"
+)
+
+// FinishReasonRepetition marks a stream that was stopped by the RepetitionGuard.
+const FinishReasonRepetition = "repetition_detected"
+
+const (
+	defaultRepetitionMinWords   = 3
+	defaultRepetitionMaxRepeats = 4
+	repetitionMaxPhraseWords    = 64
+	repetitionTailBytes         = 16_384
+)
+
+var errRepetitionDetected = errors.New("repetition detected")
+
+// RepetitionGuard stops a stream whose tail keeps repeating the same phrase,
+// which small models sometimes do indefinitely.
+type RepetitionGuard struct {
+	Enabled bool `json:"enabled"`
+	// MinWords is the shortest phrase, in words, that counts as a repeat.
+	MinWords int `json:"minWords,omitempty"`
+	// MaxRepeats is how many back-to-back copies of a phrase are tolerated.
+	MaxRepeats int `json:"maxRepeats,omitempty"`
+}
+
+func (g *RepetitionGuard) active() bool {
+	return g != nil && g.Enabled
+}
+
+func (g RepetitionGuard) withDefaults() RepetitionGuard {
+	if g.MinWords <= 0 {
+		g.MinWords = defaultRepetitionMinWords
+	}
+	if g.MaxRepeats <= 0 {
+		g.MaxRepeats = defaultRepetitionMaxRepeats
+	}
+	return g
+}
+
+// repeats reports whether text ends with more than MaxRepeats consecutive
+// copies of a phrase of at least MinWords words.
+func (g RepetitionGuard) repeats(text string) bool {
+	g = g.withDefaults()
+	if len(text) > repetitionTailBytes {
+		text = text[len(text)-repetitionTailBytes:]
+	}
+	words := strings.Fields(text)
+	copies := g.MaxRepeats + 1
+
+	for size := g.MinWords; size <= repetitionMaxPhraseWords && size*copies <= len(words); size++ {
+		if periodic(words[len(words)-size*copies:], size) {
+			return true
+		}
+	}
+	return false
+}
+
+func periodic(words []string, period int) bool {
+	for i := period; i < len(words); i++ {
+		if words[i] != words[i-period] {
+			return false
+		}
+	}
+	return true
+}
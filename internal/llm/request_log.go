@@ -0,0 +1,50 @@
+package llm
+
+import (
+	"encoding/json"
+	"log"
+	"strings"
+)
+
+// LogLevel selects how much the backend logs. Debug adds the redacted body of
+// every completion request to the info output.
+type LogLevel string
+
+const (
+	LogLevelInfo  LogLevel = "info"
+	LogLevelDebug LogLevel = "debug"
+)
+
+// ParseLogLevel normalizes a configured level, defaulting to info.
+func ParseLogLevel(raw string) LogLevel {
+	if strings.EqualFold(strings.TrimSpace(raw), string(LogLevelDebug)) {
+		return LogLevelDebug
+	}
+	return LogLevelInfo
+}
+
+// logAt logs a line tagged with level when the configured level includes it.
+func logAt(configured, level LogLevel, format string, args ...any) {
+	if level == LogLevelDebug && configured != LogLevelDebug {
+		return
+	}
+	log.Printf("["+string(level)+"] "+format, args...)
+}
+
+// logRequestBody writes an outgoing completion body at debug level. Secrets in
+// the body are redacted the same way as exchange traces.
+func logRequestBody(level LogLevel, provider, url string, body []byte) {
+	if level != LogLevelDebug {
+		return
+	}
+	var decoded any
+	if err := json.Unmarshal(body, &decoded); err != nil {
+		logAt(level, LogLevelDebug, "%s request to %s: (unparseable body, %d bytes)", provider, RedactURL(url), len(body))
+		return
+	}
+	redacted, err := json.Marshal(redactSecrets(decoded))
+	if err != nil {
+		return
+	}
+	logAt(level, LogLevelDebug, "%s request to %s: %s", provider, RedactURL(url), redacted)
+}
@@ -0,0 +1,166 @@
+package llm
+
+import (
+	"fmt"
+	"strings"
+)
+
+// Ranges accepted by both Ollama and OpenAI-compatible servers.
+const (
+	maxTemperature = 2.0
+	maxTopP        = 1.0
+	minNumCtx      = 1
+)
+
+// SamplingParams carries optional generation settings for a request. Nil or
+// empty fields are left to the provider's defaults.
+type SamplingParams struct {
+	Temperature *float64 `json:"temperature,omitempty"`
+	TopP        *float64 `json:"topP,omitempty"`
+	Seed        *int     `json:"seed,omitempty"`
+	NumCtx      *int     `json:"numCtx,omitempty"`
+	Stop        []string `json:"stop,omitempty"`
+}
+
+// Merge returns p with every field set in override replacing p's value.
+func (p SamplingParams) Merge(override SamplingParams) SamplingParams {
+	if override.Temperature != nil {
+		p.Temperature = override.Temperature
+	}
+	if override.TopP != nil {
+		p.TopP = override.TopP
+	}
+	if override.Seed != nil {
+		p.Seed = override.Seed
+	}
+	if override.NumCtx != nil {
+		p.NumCtx = override.NumCtx
+	}
+	if len(override.Stop) > 0 {
+		p.Stop = override.Stop
+	}
+	return p
+}
+
+// supportedSampling lists the wire names of sampling parameters each provider
+// is known to honour. Anything else is ignored or rejected by the server.
+var supportedSampling = map[string][]string{
+	"ollama": {"temperature", "top_p", "top_k", "seed", "num_ctx", "num_predict", "stop", "repeat_penalty"},
+	"vllm":   {"temperature", "top_p", "top_k", "seed", "max_tokens", "stop", "presence_penalty", "frequency_penalty", "logit_bias"},
+	"openai": {"temperature", "top_p", "seed", "max_tokens", "stop", "presence_penalty", "frequency_penalty", "logit_bias"},
+}
+
+// SupportedSamplingParams returns the sampling parameters provider accepts so
+// the UI can hide controls that would have no effect.
+func SupportedSamplingParams(provider string) []string {
+	params := supportedSampling[strings.ToLower(strings.TrimSpace(provider))]
+	return append([]string{}, params...)
+}
+
+// SamplingPreview holds the values that will be sent after clamping, plus a
+// note for every value that was changed.
+type SamplingPreview struct {
+	Params      SamplingParams `json:"params"`
+	Adjustments []string       `json:"adjustments"`
+}
+
+// Clamp limits each field to the range providers accept and reports what changed.
+func (p SamplingParams) Clamp() SamplingPreview {
+	preview := SamplingPreview{Params: p, Adjustments: []string{}}
+	if p.Temperature != nil {
+		if clamped := clampFloat(*p.Temperature, 0, maxTemperature); clamped != *p.Temperature {
+			preview.Params.Temperature = &clamped
+			preview.Adjustments = append(preview.Adjustments, fmt.Sprintf("temperature was clamped to %g", clamped))
+		}
+	}
+	if p.TopP != nil {
+		if clamped := clampFloat(*p.TopP, 0, maxTopP); clamped != *p.TopP {
+			preview.Params.TopP = &clamped
+			preview.Adjustments = append(preview.Adjustments, fmt.Sprintf("topP was clamped to %g", clamped))
+		}
+	}
+	if p.NumCtx != nil && *p.NumCtx < minNumCtx {
+		clamped := minNumCtx
+		preview.Params.NumCtx = &clamped
+		preview.Adjustments = append(preview.Adjustments, fmt.Sprintf("numCtx was raised to %d", clamped))
+	}
+	return preview
+}
+
+func clampFloat(value, min, max float64) float64 {
+	if value < min {
+		return min
+	}
+	if value > max {
+		return max
+	}
+	return value
+}
+
+// ollamaOptions mirrors Ollama's "options" object. Ollama silently ignores
+// sampling fields sent at the top level of a chat request.
+type ollamaOptions struct {
+	Temperature *float64 `json:"temperature,omitempty"`
+	TopP        *float64 `json:"top_p,omitempty"`
+	Seed        *int     `json:"seed,omitempty"`
+	NumCtx      *int     `json:"num_ctx,omitempty"`
+	Stop        []string `json:"stop,omitempty"`
+}
+
+// samplingFields is embedded into provider payloads. OpenAI-compatible
+// providers read the top-level fields while Ollama only reads Options.
+type samplingFields struct {
+	Temperature *float64       `json:"temperature,omitempty"`
+	TopP        *float64       `json:"top_p,omitempty"`
+	Seed        *int           `json:"seed,omitempty"`
+	Stop        []string       `json:"stop,omitempty"`
+	Options     *ollamaOptions `json:"options,omitempty"`
+}
+
+func (p SamplingParams) isZero() bool {
+	return p.Temperature == nil && p.TopP == nil && p.Seed == nil && p.NumCtx == nil && len(p.Stop) == 0
+}
+
+// samplingFor maps the sampling params onto the wire shape expected by the provider.
+func samplingFor(provider string, params SamplingParams) samplingFields {
+	if params.isZero() {
+		return samplingFields{}
+	}
+
+	if strings.EqualFold(strings.TrimSpace(provider), "ollama") {
+		return samplingFields{Options: &ollamaOptions{
+			Temperature: params.Temperature,
+			TopP:        params.TopP,
+			Seed:        params.Seed,
+			NumCtx:      params.NumCtx,
+			Stop:        params.Stop,
+		}}
+	}
+
+	// num_ctx has no OpenAI equivalent; the server's context length applies.
+	return samplingFields{
+		Temperature: params.Temperature,
+		TopP:        params.TopP,
+		Seed:        params.Seed,
+		Stop:        params.Stop,
+	}
+}
+
+// apply copies the populated fields into a map-based payload.
+func (f samplingFields) apply(payload map[string]any) {
+	if f.Temperature != nil {
+		payload["temperature"] = *f.Temperature
+	}
+	if f.TopP != nil {
+		payload["top_p"] = *f.TopP
+	}
+	if f.Seed != nil {
+		payload["seed"] = *f.Seed
+	}
+	if len(f.Stop) > 0 {
+		payload["stop"] = f.Stop
+	}
+	if f.Options != nil {
+		payload["options"] = f.Options
+	}
+}
@@ -0,0 +1,49 @@
+package llm
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestSupportedSamplingParams(t *testing.T) {
+	has := func(params []string, name string) bool {
+		for _, p := range params {
+			if p == name {
+				return true
+			}
+		}
+		return false
+	}
+
+	ollama := SupportedSamplingParams("Ollama")
+	if !has(ollama, "seed") || !has(ollama, "num_ctx") || has(ollama, "logit_bias") {
+		t.Fatalf("unexpected ollama params: %v", ollama)
+	}
+	if vllm := SupportedSamplingParams("vllm"); !has(vllm, "logit_bias") || has(vllm, "num_ctx") {
+		t.Fatalf("unexpected vllm params: %v", vllm)
+	}
+	if unknown := SupportedSamplingParams("other"); unknown == nil || len(unknown) != 0 {
+		t.Fatalf("expected an empty list for unknown providers, got %v", unknown)
+	}
+}
+
+func TestSamplingClampReportsAdjustments(t *testing.T) {
+	temp, topP, numCtx, seed := 5.0, -0.5, 0, 7
+	preview := SamplingParams{Temperature: &temp, TopP: &topP, NumCtx: &numCtx, Seed: &seed}.Clamp()
+
+	if *preview.Params.Temperature != 2 || *preview.Params.TopP != 0 || *preview.Params.NumCtx != 1 {
+		t.Fatalf("expected clamped values, got %+v", preview.Params)
+	}
+	if *preview.Params.Seed != 7 || temp != 5.0 {
+		t.Fatalf("expected seed untouched and input unchanged, got %+v", preview.Params)
+	}
+	notes := strings.Join(preview.Adjustments, "; ")
+	if len(preview.Adjustments) != 3 || !strings.Contains(notes, "temperature was clamped to 2") {
+		t.Fatalf("unexpected adjustments: %v", preview.Adjustments)
+	}
+
+	inRange := 0.7
+	if got := (SamplingParams{Temperature: &inRange}).Clamp(); len(got.Adjustments) != 0 {
+		t.Fatalf("expected no adjustments for in-range values, got %v", got.Adjustments)
+	}
+}
@@ -0,0 +1,42 @@
+package llm
+
+import (
+	"fmt"
+	"os"
+	"runtime"
+	"strings"
+)
+
+// shellContextMessage describes the host shell for the model. Only the OS,
+// the shell path and the working directory are read; no other environment
+// variables are ever included.
+func shellContextMessage() string {
+	shellPath := strings.TrimSpace(os.Getenv("SHELL"))
+	if shellPath == "" && runtime.GOOS == "windows" {
+		shellPath = strings.TrimSpace(os.Getenv("ComSpec"))
+	}
+	if shellPath == "" {
+		shellPath = "unknown"
+	}
+	cwd, err := os.Getwd()
+	if err != nil {
+		cwd = "unknown"
+	}
+
+	return fmt.Sprintf("Shell context:\n- OS: %s\n- Shell: %s\n- Working directory: %s", runtime.GOOS, shellPath, cwd)
+}
+
+// withShellContext inserts the shell context after any leading system messages.
+func withShellContext(messages []chatCompletionMessage, include bool) []chatCompletionMessage {
+	if !include {
+		return messages
+	}
+	at := 0
+	for at < len(messages) && messages[at].Role == "system" {
+		at++
+	}
+	out := make([]chatCompletionMessage, 0, len(messages)+1)
+	out = append(out, messages[:at]...)
+	out = append(out, chatCompletionMessage{Role: "system", Content: shellContextMessage()})
+	return append(out, messages[at:]...)
+}
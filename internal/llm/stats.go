@@ -0,0 +1,51 @@
+package llm
+
+import (
+	"strings"
+	"unicode/utf8"
+)
+
+// ConversationStats summarizes a conversation for the session info panel.
+type ConversationStats struct {
+	Messages          int `json:"messages"`
+	UserMessages      int `json:"userMessages"`
+	AssistantMessages int `json:"assistantMessages"`
+	SystemMessages    int `json:"systemMessages"`
+	ToolMessages      int `json:"toolMessages"`
+	Characters        int `json:"characters"`
+	Words             int `json:"words"`
+	EstimatedTokens   int `json:"estimatedTokens"`
+}
+
+// ComputeConversationStats counts messages per role along with character,
+// word, and estimated token totals across all message content.
+func ComputeConversationStats(history []ChatMessage) ConversationStats {
+	stats := ConversationStats{}
+	for _, msg := range history {
+		stats.Messages++
+		switch strings.ToLower(strings.TrimSpace(msg.Role)) {
+		case "user":
+			stats.UserMessages++
+		case "assistant":
+			stats.AssistantMessages++
+		case "system":
+			stats.SystemMessages++
+		case "tool":
+			stats.ToolMessages++
+		}
+		stats.Characters += utf8.RuneCountInString(msg.Content)
+		stats.Words += len(strings.Fields(msg.Content))
+		stats.EstimatedTokens += EstimateTokens(msg.Content)
+	}
+	return stats
+}
+
+// EstimateTokens approximates the token count of text using the common
+// four-characters-per-token heuristic.
+func EstimateTokens(text string) int {
+	runes := utf8.RuneCountInString(text)
+	if runes == 0 {
+		return 0
+	}
+	return (runes + 3) / 4
+}
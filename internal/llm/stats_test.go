@@ -0,0 +1,31 @@
+package llm
+
+import "testing"
+
+func TestComputeConversationStats(t *testing.T) {
+	history := []ChatMessage{
+		{Role: "system", Content: "Be brief."},
+		{Role: "user", Content: "list files"},
+		{Role: "assistant", Content: "", ToolCalls: []ToolCall{{Type: "function", Function: ToolCallFunction{Name: "shell"}}}},
+		{Role: "tool", Content: "a.txt b.txt"},
+		{Role: "assistant", Content: "Two files."},
+	}
+
+	stats := ComputeConversationStats(history)
+
+	if stats.Messages != 5 {
+		t.Fatalf("expected 5 messages, got %d", stats.Messages)
+	}
+	if stats.UserMessages != 1 || stats.AssistantMessages != 2 || stats.SystemMessages != 1 || stats.ToolMessages != 1 {
+		t.Fatalf("unexpected role counts: %+v", stats)
+	}
+	if stats.Characters != 40 {
+		t.Fatalf("expected 40 characters, got %d", stats.Characters)
+	}
+	if stats.Words != 8 {
+		t.Fatalf("expected 8 words, got %d", stats.Words)
+	}
+	if stats.EstimatedTokens != 12 {
+		t.Fatalf("expected 12 estimated tokens, got %d", stats.EstimatedTokens)
+	}
+}
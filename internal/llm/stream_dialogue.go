@@ -0,0 +1,80 @@
+package llm
+
+import (
+	"context"
+	"strings"
+
+	"shell-werk/internal/tools"
+)
+
+// StreamDialogue runs the dialogue loop with every completion streamed, so
+// answers arrive as deltas while the loop handles tools, continuation prompts,
+// failure limits and the trace exactly as for non-streamed dialogues. A nil
+// executor uses the default tools configured from req.
+func (s *Streamer) StreamDialogue(ctx context.Context, req ChatRequest, executor ToolExecutor) (ChatMessage, []DialogueTrace, error) {
+	if strings.EqualFold(req.Provider, "mock") {
+		msg, err := s.StreamChat(ctx, req)
+		return msg, nil, err
+	}
+
+	deps := s.dialogueDeps
+	deps.ToolExecutor = executor
+	deps.ToolAudit = s.toolAudit
+	deps.ToolSupport = s.toolSupport
+	if deps.ContinuationPrompter == nil {
+		// Nobody can be asked, so a stream stops at the iteration limit.
+		deps.ContinuationPrompter = stopAtLimitPrompter{}
+	}
+
+	loop := NewDialogueLoop(req, s.sink, deps)
+	loop.completionRequester = s.streamingCompletion(req)
+	return loop.Run(ctx, req)
+}
+
+// UseDialogueDependencies sets the prompt loader, continuation prompter and
+// trace recorder of streamed dialogues. Like UseToolAudit, call it before
+// streaming starts.
+func (s *Streamer) UseDialogueDependencies(deps DialogueDependencies) {
+	s.dialogueDeps = deps
+}
+
+// streamingCompletion requests each dialogue completion as a stream.
+func (s *Streamer) streamingCompletion(req ChatRequest) completionRequester {
+	return func(ctx context.Context, messages []chatCompletionMessage, toolDefs []tools.ToolDefinition) (completionChoice, error) {
+		round := req
+		round.ToolDefs = toolDefs
+		msg, err := s.streamMessages(ctx, round, messages)
+		if err != nil {
+			return completionChoice{}, err
+		}
+
+		choice := completionChoice{FinishReason: msg.FinishReason, Usage: msg.Usage}
+		choice.Message.Role = msg.Role
+		choice.Message.Content = msg.Content
+		if len(msg.ToolCalls) > 0 {
+			choice.Message.ToolCalls = toChatToolCalls(msg.ToolCalls)
+		}
+		return choice, nil
+	}
+}
+
+type stopAtLimitPrompter struct{}
+
+func (stopAtLimitPrompter) RequestContinuation(context.Context, string, ContinuationRequest) (ContinuationDecision, error) {
+	return ContinuationDecisionCancel, nil
+}
+
+func toChatToolCalls(calls []ToolCall) []chatToolCall {
+	out := make([]chatToolCall, 0, len(calls))
+	for _, call := range calls {
+		out = append(out, chatToolCall{
+			ID:   call.ID,
+			Type: call.Type,
+			Function: toolCallFunction{
+				Name:      call.Function.Name,
+				Arguments: call.Function.Arguments,
+			},
+		})
+	}
+	return out
+}
@@ -0,0 +1,89 @@
+package llm
+
+import (
+	"bufio"
+	"encoding/json"
+	"errors"
+	"io"
+	"strings"
+)
+
+// streamEventReader splits a streaming response body into payloads. SSE
+// events end at a blank line and their data lines are joined, so a JSON
+// object spread over several data lines parses as one. Lines without a field
+// prefix, as in Ollama's NDJSON stream, are payloads of their own. Invalid
+// UTF-8 is replaced rather than failing the stream.
+type streamEventReader struct {
+	reader  *bufio.Reader
+	pending []string
+	done    bool
+}
+
+func newStreamEventReader(reader *bufio.Reader) *streamEventReader {
+	return &streamEventReader{reader: reader}
+}
+
+// next returns the next payload, or io.EOF once the body is exhausted. A
+// payload cut off by the end of the body is still returned.
+func (r *streamEventReader) next() (string, error) {
+	for !r.done {
+		raw, err := r.reader.ReadBytes('\n')
+		if err != nil {
+			if !errors.Is(err, io.EOF) {
+				return "", err
+			}
+			r.done = true
+		}
+		line := strings.TrimSpace(strings.ToValidUTF8(string(raw), "\uFFFD"))
+		if payload, ok := r.feed(line); ok {
+			return payload, nil
+		}
+	}
+	if payload := r.flush(); payload != "" {
+		return payload, nil
+	}
+	return "", io.EOF
+}
+
+// feed consumes one trimmed line and returns the payload it completes, if any.
+func (r *streamEventReader) feed(line string) (string, bool) {
+	switch {
+	case line == "":
+		payload := r.flush()
+		return payload, payload != ""
+	case strings.HasPrefix(line, ":"), strings.HasPrefix(line, "event:"), strings.HasPrefix(line, "id:"), strings.HasPrefix(line, "retry:"):
+		return "", false
+	case strings.HasPrefix(line, "data:"):
+		data := strings.TrimSpace(strings.TrimPrefix(line, "data:"))
+		// Some servers end events with a single newline; without this their
+		// consecutive events would be joined into one invalid payload.
+		if complete := r.completePending(); complete != "" {
+			r.pending = []string{data}
+			return complete, true
+		}
+		r.pending = append(r.pending, data)
+		return "", false
+	case len(r.pending) > 0:
+		// A data field continued on a line of its own.
+		r.pending = append(r.pending, line)
+		return "", false
+	default:
+		return line, true
+	}
+}
+
+// completePending flushes the pending data when it already forms a whole payload.
+func (r *streamEventReader) completePending() string {
+	joined := strings.Join(r.pending, "\n")
+	if joined == "" || !(json.Valid([]byte(joined)) || strings.EqualFold(joined, "[DONE]")) {
+		return ""
+	}
+	r.pending = nil
+	return joined
+}
+
+func (r *streamEventReader) flush() string {
+	joined := strings.Join(r.pending, "\n")
+	r.pending = nil
+	return joined
+}
@@ -0,0 +1,59 @@
+package llm
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// StreamToFile runs a streaming completion without emitting UI events and
+// atomically writes the final answer to path, returning the bytes written.
+// When req.ToolRoot is set the output must land inside it.
+func StreamToFile(ctx context.Context, req ChatRequest, path string) (int, error) {
+	path = strings.TrimSpace(path)
+	if path == "" {
+		return 0, fmt.Errorf("output path is empty")
+	}
+	if req.ToolRoot != "" {
+		if !filepath.IsAbs(path) {
+			path = filepath.Join(req.ToolRoot, path)
+		}
+		if err := ensureWithinRoot(req.ToolRoot, filepath.Dir(path)); err != nil {
+			return 0, fmt.Errorf("output %s is outside the tool root %s", path, req.ToolRoot)
+		}
+	}
+
+	msg, err := NewStreamer(discardSink{}).StreamChat(ctx, req)
+	if err != nil {
+		return 0, err
+	}
+
+	if err := writeFileAtomic(path, []byte(msg.Content)); err != nil {
+		return 0, err
+	}
+	return len(msg.Content), nil
+}
+
+// writeFileAtomic writes to a temporary sibling and renames it into place so
+// readers never observe a partial file.
+func writeFileAtomic(path string, data []byte) error {
+	tmp, err := os.CreateTemp(filepath.Dir(path), "."+filepath.Base(path)+".tmp-*")
+	if err != nil {
+		return fmt.Errorf("write %s: %w", path, err)
+	}
+	defer os.Remove(tmp.Name())
+
+	if _, err := tmp.Write(data); err != nil {
+		tmp.Close()
+		return fmt.Errorf("write %s: %w", path, err)
+	}
+	if err := tmp.Close(); err != nil {
+		return fmt.Errorf("write %s: %w", path, err)
+	}
+	if err := os.Rename(tmp.Name(), path); err != nil {
+		return fmt.Errorf("write %s: %w", path, err)
+	}
+	return nil
+}
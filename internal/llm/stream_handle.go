@@ -0,0 +1,52 @@
+package llm
+
+import (
+	"context"
+	"fmt"
+)
+
+// StreamHandle controls a stream started with StartStream. It lets embedders
+// stop the stream directly instead of looking it up by session ID.
+type StreamHandle struct {
+	cancel context.CancelFunc
+	done   chan struct{}
+	msg    ChatMessage
+	err    error
+}
+
+// StartStream runs StreamChat, or StreamDialogue when tools are offered, in
+// the background and returns a handle to it.
+func (s *Streamer) StartStream(ctx context.Context, req ChatRequest) *StreamHandle {
+	ctx, cancel := context.WithCancel(ctx)
+	h := &StreamHandle{cancel: cancel, done: make(chan struct{})}
+
+	go func() {
+		defer close(h.done)
+		defer cancel()
+		if len(req.ToolDefs) > 0 {
+			h.msg, _, h.err = s.StreamDialogue(ctx, req, nil)
+		} else {
+			h.msg, h.err = s.StreamChat(ctx, req)
+		}
+		if h.err != nil && ctx.Err() != nil {
+			h.err = fmt.Errorf("stream cancelled: %w", ctx.Err())
+		}
+	}()
+	return h
+}
+
+// Cancel stops the stream; it is safe to call more than once.
+func (h *StreamHandle) Cancel() {
+	h.cancel()
+}
+
+// Done is closed once the stream has finished.
+func (h *StreamHandle) Done() <-chan struct{} {
+	return h.done
+}
+
+// Wait blocks until the stream finishes and returns its result.
+func (h *StreamHandle) Wait() (ChatMessage, error) {
+	<-h.done
+	return h.msg, h.err
+}
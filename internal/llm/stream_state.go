@@ -3,22 +3,26 @@ package llm
 import (
 	"errors"
 	"strings"
+	"unicode/utf8"
 )
 
 var errStreamDone = errors.New("stream done")
 
 type streamingChunk struct {
 	Choices []streamingChoice `json:"choices"`
+	Usage   *completionUsage  `json:"usage"`
 	Error   struct {
 		Message string `json:"message"`
 	} `json:"error"`
 }
 
 type ollamaStreamChunk struct {
-	Model   string              `json:"model"`
-	Message ollamaStreamMessage `json:"message"`
-	Done    bool                `json:"done"`
-	Error   string              `json:"error"`
+	Model           string              `json:"model"`
+	Message         ollamaStreamMessage `json:"message"`
+	Done            bool                `json:"done"`
+	Error           string              `json:"error"`
+	PromptEvalCount int                 `json:"prompt_eval_count"`
+	EvalCount       int                 `json:"eval_count"`
 }
 
 type ollamaStreamMessage struct {
@@ -40,6 +44,8 @@ type ollamaStreamToolCall struct {
 type streamingChoice struct {
 	Delta   streamingDelta   `json:"delta"`
 	Message streamingMessage `json:"message"`
+	// FinishReason arrives with the last delta of an OpenAI-compatible stream.
+	FinishReason string `json:"finish_reason"`
 }
 
 type streamingDelta struct {
@@ -59,6 +65,23 @@ type streamingState struct {
 	sessionID  string
 	final      *strings.Builder
 	inThinking bool
+	repetition *RepetitionGuard
+	// minDelta buffers answer text until this many characters are pending.
+	minDelta int
+	pending  strings.Builder
+	// maxTokens stops the stream once the estimated answer length reaches it.
+	maxTokens int
+	// answerRunes counts the runes written to final, so the token estimate
+	// does not rescan the whole answer on every chunk.
+	answerRunes int
+	// started is called once, when the first answer or thinking text arrives.
+	started func()
+	// usage is what the provider reported, if anything.
+	usage *Usage
+	// transform rewrites answer text before it is emitted; nil keeps it as is.
+	transform AnswerTransform
+	// finishReason is the provider's finish_reason, if it sent one.
+	finishReason string
 }
 
 func (s *streamingState) consume(content string) {
@@ -99,17 +122,69 @@ func (s *streamingState) consume(content string) {
 	}
 }
 
+func (s *streamingState) markStarted() {
+	if s.started != nil {
+		s.started()
+		s.started = nil
+	}
+}
+
 func (s *streamingState) emitAnswer(chunk string) {
+	if chunk == "" {
+		return
+	}
+	s.markStarted()
+	if s.transform != nil {
+		chunk = s.transform.Transform(chunk)
+	}
+	s.appendAnswer(chunk)
+}
+
+// appendAnswer keeps and emits answer text that has been transformed.
+func (s *streamingState) appendAnswer(chunk string) {
 	if chunk == "" {
 		return
 	}
 	s.final.WriteString(chunk)
-	s.sink.AnswerUpdate(s.sessionID, chunk)
+	s.answerRunes += utf8.RuneCountInString(chunk)
+	if s.minDelta <= 0 {
+		s.sink.AnswerUpdate(s.sessionID, chunk)
+		return
+	}
+	s.pending.WriteString(chunk)
+	if utf8.RuneCountInString(s.pending.String()) >= s.minDelta {
+		s.flush()
+	}
+}
+
+// finish emits what the transform still holds back and any buffered answer
+// text; call it once the stream has ended.
+func (s *streamingState) finish() {
+	if s.transform != nil {
+		s.appendAnswer(s.transform.Flush())
+	}
+	s.flush()
+}
+
+// flush emits any buffered answer text.
+func (s *streamingState) flush() {
+	if s.pending.Len() == 0 {
+		return
+	}
+	s.sink.AnswerUpdate(s.sessionID, s.pending.String())
+	s.pending.Reset()
 }
 
 func (s *streamingState) emitThinking(chunk string) {
 	if chunk == "" {
 		return
 	}
+	s.markStarted()
+	s.flush()
 	s.sink.ThinkingUpdate(s.sessionID, chunk)
 }
+
+// estimatedTokens is EstimateTokens of the answer written so far.
+func (s *streamingState) estimatedTokens() int {
+	return (s.answerRunes + 3) / 4
+}
@@ -11,6 +11,8 @@ import (
 	"log"
 	"net/http"
 	"strings"
+	"sync"
+	"time"
 )
 
 type StreamEventSink interface {
@@ -18,61 +20,134 @@ type StreamEventSink interface {
 	ThinkingUpdate(sessionID, chunk string)
 	ThinkingEnd(sessionID string)
 	AnswerUpdate(sessionID, chunk string)
+	AnswerDone(sessionID, finishReason string)
+	ToolCall(sessionID, name string)
+	// ToolCallDelta reports a fragment of a tool call's arguments as it
+	// streams in; index is the call's position within the turn.
+	ToolCallDelta(sessionID string, index int, fragment string)
+	// ToolOutput reports one line of a running tool's output.
+	ToolOutput(sessionID, chunk string)
+	Heartbeat(sessionID string)
+	Progress(sessionID string, iteration int, message string)
+	Usage(sessionID string, usage Usage)
 }
 
 // Streamer handles streaming chat responses and pushes events to the UI sink.
 type Streamer struct {
-	sink StreamEventSink
+	sink    StreamEventSink
+	support streamingSupport
+	// heartbeat is how often Heartbeat fires while waiting for the first
+	// delta; zero disables it.
+	heartbeat time.Duration
+	// transforms create the answer transforms applied to every stream.
+	transforms []func() AnswerTransform
+	// toolAudit records the tools run by StreamDialogue; nil disables it.
+	toolAudit *ToolAuditLog
+	// toolSupport remembers models that rejected tools.
+	toolSupport *ToolSupport
+	// dialogueDeps supplies the prompt loader, continuation prompter and
+	// trace recorder of StreamDialogue.
+	dialogueDeps DialogueDependencies
 }
 
+// defaultHeartbeatInterval keeps the UI alive during long prompt processing.
+const defaultHeartbeatInterval = 3 * time.Second
+
 func NewStreamer(sink StreamEventSink) *Streamer {
-	return &Streamer{sink: sink}
+	return &Streamer{sink: sink, heartbeat: defaultHeartbeatInterval, toolSupport: NewToolSupport()}
 }
 
+// StreamChat streams the reply for req. When req.StreamFallback is set and the
+// endpoint is known, or found, to reject streaming, the reply is fetched in a
+// single request instead.
 func (s *Streamer) StreamChat(ctx context.Context, req ChatRequest) (ChatMessage, error) {
-	provider := strings.ToLower(req.Provider)
-	if provider == "mock" {
+	if req.StreamFallback {
+		if supported, ok := s.support.lookup(req); ok && !supported {
+			return s.completeWithoutStreaming(ctx, req)
+		}
+	}
+
+	msg, err := s.streamChat(ctx, req)
+	if errors.Is(err, ErrStreamingUnsupported) {
+		s.support.record(req, false)
+		if req.StreamFallback {
+			return s.completeWithoutStreaming(ctx, req)
+		}
+	}
+	return msg, err
+}
+
+func (s *Streamer) streamChat(ctx context.Context, req ChatRequest) (ChatMessage, error) {
+	if strings.EqualFold(req.Provider, "mock") {
 		return MockProvider{}.Chat(ctx, req)
 	}
+	return s.streamMessagesWithToolFallback(ctx, req, requestMessages(req))
+}
 
-	url := streamURL(provider, NormalizeBase(req.Endpoint))
+// requestMessages builds the provider messages for req's history.
+func requestMessages(req ChatRequest) []chatCompletionMessage {
+	return withShellContext(withSystemPrompt(convertHistory(req.History), req.SystemPrompt), req.IncludeShellContext)
+}
+
+// streamMessages streams one completion for messages, which may already
+// contain tool turns.
+func (s *Streamer) streamMessages(ctx context.Context, req ChatRequest, messages []chatCompletionMessage) (ChatMessage, error) {
+	provider := strings.ToLower(req.Provider)
+	if err := requireAPIKey(provider, req.APIKey); err != nil {
+		return ChatMessage{}, err
+	}
+	url := streamURL(provider, providerBase(provider, req.Endpoint))
 	if url == "" {
 		return ChatMessage{}, fmt.Errorf("unsupported provider for streaming: %s", provider)
 	}
 
 	payload := map[string]any{
 		"model":    req.Model,
-		"messages": convertHistory(req.History),
+		"messages": messages,
 		"stream":   true,
 	}
 	if len(req.ToolDefs) > 0 {
 		payload["tools"] = req.ToolDefs
 	}
-	if provider == "vllm" && len(req.ToolDefs) > 0 {
+	if provider != "ollama" {
+		// Ask for a final usage chunk; Ollama always reports its counts.
+		payload["stream_options"] = map[string]any{"include_usage": true}
+	}
+	if provider != "ollama" && len(req.ToolDefs) > 0 {
 		payload["tool_choice"] = "auto"
+		if req.ParallelToolCalls != nil {
+			payload["parallel_tool_calls"] = *req.ParallelToolCalls
+		}
 	}
+	samplingFor(provider, req.Sampling).apply(payload)
 
 	data, err := json.Marshal(payload)
 	if err != nil {
 		return ChatMessage{}, err
 	}
 
+	log.Printf("[%s] Sending %s stream request to %s with model %s", time.Now().Format(time.RFC3339), provider, RedactURL(url), req.Model)
+	logRequestBody(req.LogLevel, provider, url, data)
 	httpReq, err := http.NewRequestWithContext(ctx, http.MethodPost, url, bytes.NewReader(data))
 	if err != nil {
 		return ChatMessage{}, err
 	}
 	httpReq.Header.Set("Content-Type", "application/json")
-	httpReq.Header.Set("Accept", "text/event-stream")
-	if provider == "vllm" && strings.TrimSpace(req.APIKey) != "" {
+	httpReq.Header.Set("Accept", streamAccept(provider))
+	if provider != "ollama" && strings.TrimSpace(req.APIKey) != "" {
 		httpReq.Header.Set("Authorization", "Bearer "+req.APIKey)
 	}
 
-	client := MakeClient()
+	stopHeartbeat := s.startHeartbeat(req.SessionID)
+	defer stopHeartbeat()
+
+	client := MakeClientWithTimeout(req.RequestTimeout)
 	resp, err := client.Do(httpReq)
 	if err != nil {
 		return ChatMessage{}, fmt.Errorf("stream request to %s failed: %w", url, err)
 	}
 	defer resp.Body.Close()
+	log.Printf("[%s] Received %s stream response: %s", time.Now().Format(time.RFC3339), provider, resp.Status)
 
 	if resp.StatusCode >= http.StatusBadRequest {
 		body, _ := io.ReadAll(io.LimitReader(resp.Body, 4_096))
@@ -80,31 +155,146 @@ func (s *Streamer) StreamChat(ctx context.Context, req ChatRequest) (ChatMessage
 		if detail == "" {
 			detail = resp.Status
 		}
-		return ChatMessage{}, fmt.Errorf("streaming request failed (%s): %s", resp.Status, truncate(detail, 512))
+		if isStreamingRejection(detail) {
+			return ChatMessage{}, fmt.Errorf("%w (%s): %s", ErrStreamingUnsupported, resp.Status, truncate(detail, 512))
+		}
+		if len(req.ToolDefs) > 0 && isToolsRejection(detail) {
+			return ChatMessage{}, fmt.Errorf("%w (%s): %s", ErrToolsUnsupported, resp.Status, truncate(detail, 512))
+		}
+		return ChatMessage{}, fmt.Errorf("streaming request failed (%w): %s", statusError(resp), truncate(detail, 512))
 	}
 
 	reader := bufio.NewReader(resp.Body)
 	final := &strings.Builder{}
 	var role string
 	var toolCalls []ToolCall
-	state := streamingState{sink: s.sink, sessionID: req.SessionID, final: final}
-
+	state := streamingState{
+		sink:       s.sink,
+		sessionID:  req.SessionID,
+		final:      final,
+		repetition: req.RepetitionGuard,
+		minDelta:   req.MinDeltaChars,
+		maxTokens:  req.HardMaxCompletionTokens,
+		started:    stopHeartbeat,
+		transform:  s.answerTransform(req),
+	}
+
+	finishReason := ""
 	if err := s.consumeStream(reader, &state, &role, &toolCalls); err != nil && !errors.Is(err, errStreamDone) {
-		return ChatMessage{}, err
+		if ctx.Err() != nil {
+			// Report what was generated so cost tracking survives cancellation.
+			state.finish()
+			s.sink.Usage(req.SessionID, estimateUsage(messages, final.String()))
+			s.sink.AnswerDone(req.SessionID, FinishReasonCancelled)
+			return ChatMessage{Role: "assistant", Content: final.String(), FinishReason: FinishReasonCancelled}, ctx.Err()
+		}
+		// Returning closes the response body, which cancels the generation.
+		switch {
+		case errors.Is(err, errRepetitionDetected):
+			finishReason = FinishReasonRepetition
+		case errors.Is(err, errTokenCapReached):
+			finishReason = FinishReasonLength
+		default:
+			return ChatMessage{}, err
+		}
 	}
 
+	state.finish()
 	if role == "" {
 		role = "assistant"
 	}
+	if finishReason == "" {
+		finishReason = providerFinishReason(state.finishReason)
+	}
+
+	doneReason := finishReason
+	if doneReason == "" {
+		doneReason = "stop"
+		if len(toolCalls) > 0 {
+			doneReason = "tool_calls"
+		}
+	}
+	usage := state.usage
+	if usage == nil {
+		estimated := estimateUsage(messages, final.String())
+		usage = &estimated
+	}
+	s.sink.Usage(req.SessionID, *usage)
+	s.sink.AnswerDone(req.SessionID, doneReason)
 
-	return ChatMessage{Role: role, Content: final.String(), ToolCalls: toolCalls}, nil
+	return ChatMessage{Role: role, Content: final.String(), ToolCalls: toolCalls, FinishReason: finishReason, Usage: usage}, nil
+}
+
+// startHeartbeat emits Heartbeat on a timer until the returned stop function
+// is called. Stop waits for the timer goroutine so sink calls never overlap.
+func (s *Streamer) startHeartbeat(sessionID string) func() {
+	if s.heartbeat <= 0 {
+		return func() {}
+	}
+	done := make(chan struct{})
+	finished := make(chan struct{})
+	go func() {
+		defer close(finished)
+		ticker := time.NewTicker(s.heartbeat)
+		defer ticker.Stop()
+		for {
+			select {
+			case <-done:
+				return
+			case <-ticker.C:
+				s.sink.Heartbeat(sessionID)
+			}
+		}
+	}()
+
+	var once sync.Once
+	return func() {
+		once.Do(func() {
+			close(done)
+			<-finished
+		})
+	}
+}
+
+const resumeInstruction = "Continue your previous answer exactly where it stopped. Do not repeat text you already wrote."
+
+// ResumeChat continues an interrupted answer from its saved partial content.
+// The partial is sent back as an assistant turn followed by a continuation
+// instruction; new deltas are emitted on the same session so the UI appends
+// them to the existing message.
+func (s *Streamer) ResumeChat(ctx context.Context, req ChatRequest, partial string) (ChatMessage, error) {
+	if strings.TrimSpace(partial) == "" {
+		return s.StreamChat(ctx, req)
+	}
+
+	resumed := req
+	resumed.History = append(append([]ChatMessage{}, req.History...),
+		ChatMessage{Role: "assistant", Content: partial},
+		ChatMessage{Role: "user", Content: resumeInstruction},
+	)
+
+	msg, err := s.StreamChat(ctx, resumed)
+	if err != nil {
+		return ChatMessage{}, err
+	}
+	msg.Content = partial + msg.Content
+	return msg, nil
+}
+
+// streamAccept returns the Accept header for a streaming request. Servers that
+// content-negotiate otherwise answer with a single JSON body.
+func streamAccept(provider string) string {
+	if provider == "ollama" {
+		return acceptNDJSON
+	}
+	return acceptSSE
 }
 
 func streamURL(provider, base string) string {
 	switch provider {
 	case "ollama":
 		return base + "/api/chat"
-	case "vllm":
+	case "vllm", "openai":
 		return base + "/v1/chat/completions"
 	default:
 		return ""
@@ -122,6 +312,23 @@ func convertHistory(history []ChatMessage) []chatCompletionMessage {
 	return messages
 }
 
+// withSystemPrompt prepends prompt as a system message unless it is empty or
+// the conversation already starts with a system message of its own. A leading
+// compaction summary is folded into the prompt so only one system message is sent.
+func withSystemPrompt(messages []chatCompletionMessage, prompt string) []chatCompletionMessage {
+	prompt = strings.TrimSpace(prompt)
+	if prompt == "" {
+		return messages
+	}
+	if len(messages) > 0 && messages[0].Role == "system" {
+		if !isCompactionSummary(ChatMessage{Role: messages[0].Role, Content: messages[0].Content}) {
+			return messages
+		}
+		return append([]chatCompletionMessage{{Role: "system", Content: prompt + "\n\n" + messages[0].Content}}, messages[1:]...)
+	}
+	return append([]chatCompletionMessage{{Role: "system", Content: prompt}}, messages...)
+}
+
 func extractRole(choice streamingChoice) string {
 	if choice.Delta.Role != "" {
 		return choice.Delta.Role
@@ -150,6 +357,33 @@ func convertChatToolCalls(calls []chatToolCall) []ToolCall {
 	return out
 }
 
+// mergeToolCallDeltas folds streamed tool-call fragments into calls.
+// OpenAI-compatible servers split a call's arguments across chunks tagged with
+// the call's index; calls without an index arrive whole.
+func mergeToolCallDeltas(calls []ToolCall, deltas []chatToolCall) []ToolCall {
+	for _, delta := range deltas {
+		if delta.Index == nil || *delta.Index < 0 {
+			calls = append(calls, convertChatToolCalls([]chatToolCall{delta})...)
+			continue
+		}
+		for len(calls) <= *delta.Index {
+			calls = append(calls, ToolCall{Type: "function"})
+		}
+		call := &calls[*delta.Index]
+		if delta.ID != "" {
+			call.ID = delta.ID
+		}
+		if delta.Type != "" {
+			call.Type = delta.Type
+		}
+		if delta.Function.Name != "" {
+			call.Function.Name = delta.Function.Name
+		}
+		call.Function.Arguments += delta.Function.Arguments
+	}
+	return calls
+}
+
 func convertOllamaToolCalls(calls []ollamaStreamToolCall) []chatToolCall {
 	if len(calls) == 0 {
 		return nil
@@ -207,8 +441,12 @@ func (s *Streamer) tryOpenAIChunk(chunk string, state *streamingState, role *str
 	if openai.Error.Message != "" {
 		return true, fmt.Errorf(openai.Error.Message)
 	}
+	if openai.Usage != nil {
+		state.usage = openai.Usage.toUsage()
+	}
 	if len(openai.Choices) == 0 {
-		return false, nil
+		// The usage chunk requested with include_usage carries no choices.
+		return openai.Usage != nil, nil
 	}
 	s.applyStreamingChoices(openai.Choices, state, role, toolCalls)
 	return true, nil
@@ -248,6 +486,7 @@ func (s *Streamer) tryOllamaChunk(chunk string, state *streamingState, role *str
 	}
 
 	if ollama.Done {
+		state.usage = ollamaUsage(ollama.PromptEvalCount, ollama.EvalCount)
 		return true, errStreamDone
 	}
 	if ollama.Message.Content == "" && len(ollama.Message.ToolCalls) == 0 {
@@ -261,8 +500,19 @@ func (s *Streamer) applyStreamingChoices(choices []streamingChoice, state *strea
 		if *role == "" {
 			*role = extractRole(choice)
 		}
-		*toolCalls = append(*toolCalls, convertChatToolCalls(choice.Delta.ToolCalls)...)
-		*toolCalls = append(*toolCalls, convertChatToolCalls(choice.Message.ToolCalls)...)
+		if choice.FinishReason != "" {
+			state.finishReason = choice.FinishReason
+		}
+		if len(choice.Delta.ToolCalls) > 0 {
+			*toolCalls = mergeToolCallDeltas(*toolCalls, choice.Delta.ToolCalls)
+			for _, delta := range choice.Delta.ToolCalls {
+				if delta.Index != nil && *delta.Index >= 0 && delta.Function.Arguments != "" {
+					s.sink.ToolCallDelta(state.sessionID, *delta.Index, delta.Function.Arguments)
+				}
+			}
+		} else {
+			*toolCalls = append(*toolCalls, convertChatToolCalls(choice.Message.ToolCalls)...)
+		}
 
 		content := choice.Delta.Content
 		if content == "" {
@@ -276,18 +526,14 @@ func (s *Streamer) applyStreamingChoices(choices []streamingChoice, state *strea
 }
 
 func (s *Streamer) consumeStream(reader *bufio.Reader, state *streamingState, role *string, toolCalls *[]ToolCall) error {
+	events := newStreamEventReader(reader)
 	for {
-		line, err := reader.ReadString('\n')
-		if err != nil {
-			if errors.Is(err, io.EOF) {
-				return errStreamDone
-			}
-			return err
+		chunk, readErr := events.next()
+		if errors.Is(readErr, io.EOF) {
+			return errStreamDone
 		}
-
-		chunk := strings.TrimSpace(line)
-		if chunk == "" {
-			continue
+		if readErr != nil {
+			return readErr
 		}
 		if err := s.processStreamLine(chunk, state, role, toolCalls); err != nil {
 			if errors.Is(err, errStreamDone) {
@@ -295,5 +541,11 @@ func (s *Streamer) consumeStream(reader *bufio.Reader, state *streamingState, ro
 			}
 			return err
 		}
+		if state.repetition.active() && state.repetition.repeats(state.final.String()) {
+			return errRepetitionDetected
+		}
+		if state.maxTokens > 0 && state.estimatedTokens() >= state.maxTokens {
+			return errTokenCapReached
+		}
 	}
 }
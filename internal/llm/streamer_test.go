@@ -1,19 +1,50 @@
 package llm
 
 import (
+	"bufio"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"runtime"
 	"strings"
+	"sync"
 	"testing"
+	"testing/iotest"
+	"time"
 )
 
 type mockSink struct {
     think []string
     answer []string
+    done []string
+    progress []string
+    usage []Usage
+    tools []string
+    toolDeltas []string
+    toolOutput []string
+    heartbeats int
 }
 
 func (m *mockSink) ThinkingStart(sessionID string)            {}
 func (m *mockSink) ThinkingUpdate(_ string, chunk string) { m.think = append(m.think, chunk) }
 func (m *mockSink) ThinkingEnd(sessionID string)              {}
 func (m *mockSink) AnswerUpdate(_ string, chunk string)   { m.answer = append(m.answer, chunk) }
+func (m *mockSink) AnswerDone(_ string, reason string)    { m.done = append(m.done, reason) }
+func (m *mockSink) Heartbeat(string) { m.heartbeats++ }
+func (m *mockSink) ToolCall(_ string, name string) { m.tools = append(m.tools, name) }
+func (m *mockSink) ToolCallDelta(_ string, index int, fragment string) {
+	m.toolDeltas = append(m.toolDeltas, fmt.Sprintf("%d:%s", index, fragment))
+}
+func (m *mockSink) ToolOutput(_ string, chunk string) { m.toolOutput = append(m.toolOutput, chunk) }
+func (m *mockSink) Usage(_ string, usage Usage) { m.usage = append(m.usage, usage) }
+func (m *mockSink) Progress(_ string, iteration int, message string) {
+	m.progress = append(m.progress, fmt.Sprintf("%d:%s", iteration, message))
+}
 
 func TestOllamaThinkingChunkHandled(t *testing.T) {
     sink := &mockSink{}
@@ -34,3 +65,699 @@ func TestOllamaThinkingChunkHandled(t *testing.T) {
         t.Fatalf("expected thinking to be captured, got %+v", sink.think)
     }
 }
+
+func TestStreamChatSendsStreamingAcceptHeader(t *testing.T) {
+	var accept string
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		accept = r.Header.Get("Accept")
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"ok\"}}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	streamer := NewStreamer(&mockSink{})
+	if _, err := streamer.StreamChat(context.Background(), ChatRequest{
+		SessionID: "s1",
+		Provider:  "vllm",
+		Endpoint:  server.URL,
+		Model:     "x",
+		History:   []ChatMessage{{Role: "user", Content: "hi"}},
+	}); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if accept != "text/event-stream" {
+		t.Fatalf("expected Accept text/event-stream, got %q", accept)
+	}
+}
+
+func TestStreamChatPrependsRequestSystemPrompt(t *testing.T) {
+	var received struct {
+		Messages []chatCompletionMessage `json:"messages"`
+	}
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if err := json.NewDecoder(r.Body).Decode(&received); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\",\"content\":\"ok\"}}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	streamer := NewStreamer(&mockSink{})
+	msg, err := streamer.StreamChat(context.Background(), ChatRequest{
+		SessionID:    "s1",
+		Provider:     "vllm",
+		Endpoint:     server.URL,
+		Model:        "x",
+		History:      []ChatMessage{{Role: "user", Content: "hi"}},
+		SystemPrompt: "Be terse.",
+	})
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if msg.Content != "ok" {
+		t.Fatalf("unexpected content: %q", msg.Content)
+	}
+	if len(received.Messages) != 2 || received.Messages[0].Role != "system" || received.Messages[0].Content != "Be terse." {
+		t.Fatalf("expected request-level system prompt first, got %+v", received.Messages)
+	}
+}
+
+func TestResumeChatMergesPartialWithContinuation(t *testing.T) {
+	var received struct {
+		Messages []chatCompletionMessage `json:"messages"`
+	}
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if err := json.NewDecoder(r.Body).Decode(&received); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\" wor\"}}]}\n\n")
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"ld!\"}}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	sink := &mockSink{}
+	streamer := NewStreamer(sink)
+	msg, err := streamer.ResumeChat(context.Background(), ChatRequest{
+		SessionID: "s1",
+		Provider:  "vllm",
+		Endpoint:  server.URL,
+		Model:     "x",
+		History:   []ChatMessage{{Role: "user", Content: "say hello world"}},
+	}, "Hello")
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+
+	if msg.Content != "Hello world!" {
+		t.Fatalf("expected partial and continuation to form the full answer, got %q", msg.Content)
+	}
+	if strings.Join(sink.answer, "") != " world!" {
+		t.Fatalf("expected only new deltas to be emitted, got %+v", sink.answer)
+	}
+	if len(received.Messages) != 3 {
+		t.Fatalf("expected history, partial, and instruction, got %+v", received.Messages)
+	}
+	if received.Messages[1].Role != "assistant" || received.Messages[1].Content != "Hello" {
+		t.Fatalf("expected partial assistant turn, got %+v", received.Messages[1])
+	}
+	if received.Messages[2].Role != "user" || received.Messages[2].Content != resumeInstruction {
+		t.Fatalf("expected continuation instruction, got %+v", received.Messages[2])
+	}
+}
+
+func TestStreamingUnsupportedFallsBackToSingleResponse(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var payload struct {
+			Stream bool `json:"stream"`
+		}
+		if err := json.NewDecoder(r.Body).Decode(&payload); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		if payload.Stream {
+			w.WriteHeader(http.StatusBadRequest)
+			fmt.Fprint(w, `{"error":{"message":"stream mode is not supported for this model"}}`)
+			return
+		}
+		fmt.Fprint(w, `{"choices":[{"message":{"role":"assistant","content":"plain answer"}}]}`)
+	}))
+	defer server.Close()
+
+	sink := &mockSink{}
+	streamer := NewStreamer(sink)
+	req := ChatRequest{
+		SessionID: "s1",
+		Provider:  "vllm",
+		Endpoint:  server.URL,
+		Model:     "x",
+		History:   []ChatMessage{{Role: "user", Content: "hi"}},
+	}
+
+	supported, err := streamer.SupportsStreaming(context.Background(), req)
+	if err != nil {
+		t.Fatalf("unexpected probe error: %v", err)
+	}
+	if supported {
+		t.Fatalf("expected streaming to be reported as unsupported")
+	}
+
+	req.StreamFallback = true
+	msg, err := streamer.StreamChat(context.Background(), req)
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if msg.Content != "plain answer" {
+		t.Fatalf("unexpected content: %q", msg.Content)
+	}
+	if strings.Join(sink.answer, "") != "plain answer" {
+		t.Fatalf("expected fallback answer to reach the sink, got %+v", sink.answer)
+	}
+}
+
+func TestStreamChatIncludesShellContextWhenEnabled(t *testing.T) {
+	var received struct {
+		Messages []chatCompletionMessage `json:"messages"`
+	}
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if err := json.NewDecoder(r.Body).Decode(&received); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"ok\"}}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	streamer := NewStreamer(&mockSink{})
+	_, err := streamer.StreamChat(context.Background(), ChatRequest{
+		SessionID:           "s1",
+		Provider:            "vllm",
+		Endpoint:            server.URL,
+		Model:               "x",
+		History:             []ChatMessage{{Role: "user", Content: "hi"}},
+		IncludeShellContext: true,
+	})
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+
+	cwd, err := os.Getwd()
+	if err != nil {
+		t.Fatalf("getwd: %v", err)
+	}
+	if len(received.Messages) != 2 || received.Messages[0].Role != "system" {
+		t.Fatalf("expected shell context system message first, got %+v", received.Messages)
+	}
+	shellContext := received.Messages[0].Content
+	if !strings.Contains(shellContext, runtime.GOOS) || !strings.Contains(shellContext, cwd) {
+		t.Fatalf("expected OS and cwd in shell context, got %q", shellContext)
+	}
+}
+
+func TestStreamChatStopsOnRepetition(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		for i := 0; i < 50; i++ {
+			fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"I am stuck here. \"}}]}\n\n")
+		}
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"never reached\"}}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	sink := &mockSink{}
+	streamer := NewStreamer(sink)
+	msg, err := streamer.StreamChat(context.Background(), ChatRequest{
+		SessionID:       "s1",
+		Provider:        "vllm",
+		Endpoint:        server.URL,
+		Model:           "x",
+		History:         []ChatMessage{{Role: "user", Content: "hi"}},
+		RepetitionGuard: &RepetitionGuard{Enabled: true, MinWords: 3, MaxRepeats: 3},
+	})
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if msg.FinishReason != FinishReasonRepetition {
+		t.Fatalf("expected repetition finish reason, got %q", msg.FinishReason)
+	}
+	if strings.Contains(msg.Content, "never reached") || strings.Count(msg.Content, "I am stuck here.") > 5 {
+		t.Fatalf("expected stream to stop early, got %q", msg.Content)
+	}
+	if len(sink.done) != 1 || sink.done[0] != FinishReasonRepetition {
+		t.Fatalf("expected done event with repetition reason, got %+v", sink.done)
+	}
+}
+
+func TestStreamChatStopsAtHardTokenCap(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		for i := 0; i < 200; i++ {
+			fmt.Fprintf(w, "data: {\"choices\":[{\"delta\":{\"content\":\"word%d \"}}]}\n\n", i)
+		}
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	sink := &mockSink{}
+	streamer := NewStreamer(sink)
+	msg, err := streamer.StreamChat(context.Background(), ChatRequest{
+		SessionID:               "s1",
+		Provider:                "vllm",
+		Endpoint:                server.URL,
+		Model:                   "x",
+		History:                 []ChatMessage{{Role: "user", Content: "hi"}},
+		HardMaxCompletionTokens: 10,
+	})
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if msg.FinishReason != FinishReasonLength {
+		t.Fatalf("expected length finish reason, got %q", msg.FinishReason)
+	}
+	if tokens := EstimateTokens(msg.Content); tokens < 10 || tokens > 12 {
+		t.Fatalf("expected the stream to stop near the cap, got %d tokens", tokens)
+	}
+	if len(sink.done) != 1 || sink.done[0] != FinishReasonLength {
+		t.Fatalf("expected done event with length reason, got %+v", sink.done)
+	}
+}
+
+// firstAnswerSink signals once the first answer delta has arrived.
+type firstAnswerSink struct {
+	*mockSink
+	first chan struct{}
+	once  sync.Once
+}
+
+func (f *firstAnswerSink) AnswerUpdate(sessionID, chunk string) {
+	f.mockSink.AnswerUpdate(sessionID, chunk)
+	f.once.Do(func() { close(f.first) })
+}
+
+func TestStreamHandleCancelStopsStream(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"partial\"}}]}\n\n")
+		if flusher, ok := w.(http.Flusher); ok {
+			flusher.Flush()
+		}
+		// Block until the client goes away, then try to keep generating.
+		select {
+		case <-r.Context().Done():
+		case <-time.After(5 * time.Second):
+		}
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\" late\"}}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	sink := &firstAnswerSink{mockSink: &mockSink{}, first: make(chan struct{})}
+	streamer := NewStreamer(sink)
+	handle := streamer.StartStream(context.Background(), ChatRequest{
+		SessionID: "s1",
+		Provider:  "vllm",
+		Endpoint:  server.URL,
+		Model:     "x",
+		History:   []ChatMessage{{Role: "user", Content: "hi"}},
+	})
+
+	select {
+	case <-sink.first:
+	case <-time.After(2 * time.Second):
+		t.Fatalf("first delta never arrived")
+	}
+	handle.Cancel()
+	select {
+	case <-handle.Done():
+	case <-time.After(2 * time.Second):
+		t.Fatalf("stream did not stop after cancel")
+	}
+	if _, err := handle.Wait(); !errors.Is(err, context.Canceled) {
+		t.Fatalf("expected cancellation error, got %v", err)
+	}
+	if len(sink.answer) != 1 || sink.answer[0] != "partial" {
+		t.Fatalf("expected no deltas after cancel, got %q", sink.answer)
+	}
+}
+
+// cancelOnAnswerSink cancels the stream as soon as the first answer delta arrives.
+type cancelOnAnswerSink struct {
+	*mockSink
+	cancel context.CancelFunc
+}
+
+func (c cancelOnAnswerSink) AnswerUpdate(sessionID, chunk string) {
+	c.mockSink.AnswerUpdate(sessionID, chunk)
+	c.cancel()
+}
+
+func TestStreamChatEmitsUsageOnCancel(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"partial answer text\"}}]}\n\n")
+		if flusher, ok := w.(http.Flusher); ok {
+			flusher.Flush()
+		}
+		select {
+		case <-r.Context().Done():
+		case <-time.After(5 * time.Second):
+		}
+	}))
+	defer server.Close()
+
+	ctx, cancel := context.WithCancel(context.Background())
+	defer cancel()
+	sink := &mockSink{}
+	streamer := NewStreamer(cancelOnAnswerSink{mockSink: sink, cancel: cancel})
+	msg, err := streamer.StreamChat(ctx, ChatRequest{
+		SessionID: "s1",
+		Provider:  "vllm",
+		Endpoint:  server.URL,
+		Model:     "x",
+		History:   []ChatMessage{{Role: "user", Content: "hi"}},
+	})
+	if !errors.Is(err, context.Canceled) {
+		t.Fatalf("expected cancellation error, got %v", err)
+	}
+	if msg.Content != "partial answer text" || msg.FinishReason != FinishReasonCancelled {
+		t.Fatalf("expected partial message marked cancelled, got %+v", msg)
+	}
+	if len(sink.usage) != 1 || !sink.usage[0].Estimated || sink.usage[0].CompletionTokens == 0 {
+		t.Fatalf("expected an estimated usage event, got %+v", sink.usage)
+	}
+	if len(sink.done) != 1 || sink.done[0] != FinishReasonCancelled {
+		t.Fatalf("expected a cancelled done event, got %v", sink.done)
+	}
+}
+
+func TestStreamDialogueAssemblesSplitToolCall(t *testing.T) {
+	var followUp []chatCompletionMessage
+	calls := 0
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		calls++
+		if calls == 1 {
+			fmt.Fprint(w, `data: {"choices":[{"delta":{"tool_calls":[{"index":0,"id":"call-1","type":"function","function":{"name":"echo","arguments":"{\"text\":"}}]}}]}`+"\n\n")
+			fmt.Fprint(w, `data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"hi\"}"}}]}}]}`+"\n\n")
+			fmt.Fprint(w, "data: [DONE]\n\n")
+			return
+		}
+		var payload struct {
+			Messages []chatCompletionMessage `json:"messages"`
+		}
+		if err := json.NewDecoder(r.Body).Decode(&payload); err != nil {
+			t.Fatalf("failed to decode payload: %v", err)
+		}
+		followUp = payload.Messages
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"done\"}}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	sink := &mockSink{}
+	streamer := NewStreamer(sink)
+	msg, _, err := streamer.StreamDialogue(context.Background(), ChatRequest{
+		SessionID: "s1",
+		Provider:  "vllm",
+		Endpoint:  server.URL,
+		Model:     "x",
+		History:   []ChatMessage{{Role: "user", Content: "say hi"}},
+	}, slowEchoExecutor{})
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if msg.Content != "done" {
+		t.Fatalf("expected final answer, got %+v", msg)
+	}
+	if len(sink.tools) != 1 || sink.tools[0] != "echo" {
+		t.Fatalf("expected one tool call event, got %v", sink.tools)
+	}
+	if want := []string{`0:{"text":`, `0:"hi"}`}; strings.Join(sink.toolDeltas, "|") != strings.Join(want, "|") {
+		t.Fatalf("expected ordered argument deltas %v, got %v", want, sink.toolDeltas)
+	}
+
+	for len(followUp) > 0 && followUp[0].Role == "system" {
+		followUp = followUp[1:]
+	}
+	if len(followUp) != 3 {
+		t.Fatalf("expected user, assistant and tool messages, got %+v", followUp)
+	}
+	call := followUp[1].ToolCalls
+	if len(call) != 1 || call[0].Function.Arguments != `{"text":"hi"}` {
+		t.Fatalf("expected the split arguments to be joined, got %+v", call)
+	}
+	if tool := followUp[2]; tool.Role != "tool" || tool.ToolCallID != "call-1" || tool.Content != "hi" {
+		t.Fatalf("expected the tool result to be sent back, got %+v", tool)
+	}
+}
+
+func TestStreamChatStripsANSIFromAnswerDeltas(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		// The color code is split across two deltas.
+		for _, delta := range []string{`ls: \u001b[3`, `1mdenied\u001b[0m`, ` ok`} {
+			fmt.Fprintf(w, "data: {\"choices\":[{\"delta\":{\"content\":\"%s\"}}]}\n\n", delta)
+		}
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	sink := &mockSink{}
+	msg, err := NewStreamer(sink).StreamChat(context.Background(), ChatRequest{
+		SessionID: "s1",
+		Provider:  "vllm",
+		Endpoint:  server.URL,
+		Model:     "x",
+		History:   []ChatMessage{{Role: "user", Content: "hi"}},
+		StripANSI: true,
+	})
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	for _, delta := range sink.answer {
+		if strings.ContainsRune(delta, '\x1b') {
+			t.Fatalf("expected escape codes to be removed, got %q", sink.answer)
+		}
+	}
+	if got := strings.Join(sink.answer, ""); got != "ls: denied ok" || msg.Content != got {
+		t.Fatalf("expected clean answer, got %q (message %q)", got, msg.Content)
+	}
+}
+
+func TestStreamChatFlushesTextHeldBackByANSIStripper(t *testing.T) {
+	for _, ending := range []string{`\u001b`, `\u001bq tail`} {
+		server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			fmt.Fprintf(w, "data: {\"choices\":[{\"delta\":{\"content\":\"done %s\"}}]}\n\n", ending)
+			fmt.Fprint(w, "data: [DONE]\n\n")
+		}))
+
+		sink := &mockSink{}
+		msg, err := NewStreamer(sink).StreamChat(context.Background(), ChatRequest{
+			SessionID: "s1",
+			Provider:  "vllm",
+			Endpoint:  server.URL,
+			Model:     "x",
+			History:   []ChatMessage{{Role: "user", Content: "hi"}},
+			StripANSI: true,
+		})
+		server.Close()
+		if err != nil {
+			t.Fatalf("unexpected error: %v", err)
+		}
+		want := "done " + strings.ReplaceAll(ending, `\u001b`, "")
+		if got := strings.Join(sink.answer, ""); got != want || msg.Content != want {
+			t.Fatalf("expected %q after the stream ended, got %q (message %q)", want, got, msg.Content)
+		}
+	}
+}
+
+func TestStreamChatReportsProviderFinishReason(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"The answer is\"}}]}\n\n")
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{},\"finish_reason\":\"length\"}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	sink := &mockSink{}
+	msg, err := NewStreamer(sink).StreamChat(context.Background(), ChatRequest{
+		SessionID: "s1",
+		Provider:  "vllm",
+		Endpoint:  server.URL,
+		Model:     "x",
+		History:   []ChatMessage{{Role: "user", Content: "hi"}},
+	})
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if msg.FinishReason != FinishReasonLength {
+		t.Fatalf("expected a truncated message, got %+v", msg)
+	}
+	if len(sink.done) != 1 || sink.done[0] != FinishReasonLength {
+		t.Fatalf("expected the done event to carry length, got %v", sink.done)
+	}
+}
+
+func TestStreamChatKeepsContentOfOllamaFinalChunk(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		fmt.Fprint(w, "{\"message\":{\"role\":\"assistant\",\"content\":\"Hello\"},\"done\":false}\n")
+		// The final chunk carries the last token and has no trailing newline.
+		fmt.Fprint(w, "{\"message\":{\"role\":\"assistant\",\"content\":\"!\"},\"done\":true}")
+	}))
+	defer server.Close()
+
+	sink := &mockSink{}
+	msg, err := NewStreamer(sink).StreamChat(context.Background(), ChatRequest{
+		SessionID: "s1",
+		Provider:  "ollama",
+		Endpoint:  server.URL,
+		Model:     "x",
+		History:   []ChatMessage{{Role: "user", Content: "hi"}},
+	})
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if msg.Content != "Hello!" || strings.Join(sink.answer, "") != "Hello!" {
+		t.Fatalf("expected the final delta to be kept, got %q (events %v)", msg.Content, sink.answer)
+	}
+	if len(sink.done) != 1 {
+		t.Fatalf("expected exactly one done event, got %v", sink.done)
+	}
+}
+
+func TestConsumeStreamHandlesMultiLineAndSplitFrames(t *testing.T) {
+	body := ": keep-alive\n" +
+		"data: {\"choices\":[{\"delta\":\n" +
+		"data: {\"content\":\"Hel\"}}]}\n\n" +
+		"event: message\n" +
+		"data: {\"choices\":[{\"delta\":{\"content\":\"lo \xff\"}}]}\n" +
+		"data: {\"choices\":[{\"delta\":{\"content\":\"world\"}}]}\n\n" +
+		"data: [DONE]\n\n"
+
+	sink := &mockSink{}
+	streamer := &Streamer{sink: sink}
+	state := streamingState{sink: sink, sessionID: "s1", final: &strings.Builder{}}
+	role := ""
+	var toolCalls []ToolCall
+
+	// One byte per read splits every JSON object across reads.
+	reader := bufio.NewReader(iotest.OneByteReader(strings.NewReader(body)))
+	if err := streamer.consumeStream(reader, &state, &role, &toolCalls); !errors.Is(err, errStreamDone) {
+		t.Fatalf("expected the stream to finish, got %v", err)
+	}
+	if got := state.final.String(); got != "Hello \uFFFDworld" {
+		t.Fatalf("unexpected answer %q", got)
+	}
+}
+
+func TestStreamChatReportsProviderUsage(t *testing.T) {
+	cases := map[string]string{
+		"vllm": "data: {\"choices\":[{\"delta\":{\"content\":\"ok\"}}]}\n\n" +
+			"data: {\"choices\":[],\"usage\":{\"prompt_tokens\":12,\"completion_tokens\":3}}\n\n" +
+			"data: [DONE]\n\n",
+		"ollama": "{\"message\":{\"role\":\"assistant\",\"content\":\"ok\"},\"done\":false}\n" +
+			"{\"message\":{\"role\":\"assistant\",\"content\":\"\"},\"done\":true,\"prompt_eval_count\":12,\"eval_count\":3}\n",
+	}
+	for provider, body := range cases {
+		t.Run(provider, func(t *testing.T) {
+			var includeUsage bool
+			server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				var payload struct {
+					StreamOptions struct {
+						IncludeUsage bool `json:"include_usage"`
+					} `json:"stream_options"`
+				}
+				_ = json.NewDecoder(r.Body).Decode(&payload)
+				includeUsage = payload.StreamOptions.IncludeUsage
+				fmt.Fprint(w, body)
+			}))
+			defer server.Close()
+
+			sink := &mockSink{}
+			msg, err := NewStreamer(sink).StreamChat(context.Background(), ChatRequest{
+				SessionID: "s1",
+				Provider:  provider,
+				Endpoint:  server.URL,
+				Model:     "x",
+				History:   []ChatMessage{{Role: "user", Content: "hi"}},
+			})
+			if err != nil {
+				t.Fatalf("unexpected error: %v", err)
+			}
+			want := Usage{PromptTokens: 12, CompletionTokens: 3}
+			if len(sink.usage) != 1 || sink.usage[0] != want {
+				t.Fatalf("expected one usage event %+v, got %+v", want, sink.usage)
+			}
+			if msg.Usage == nil || *msg.Usage != want {
+				t.Fatalf("expected usage on the message, got %+v", msg.Usage)
+			}
+			if includeUsage != (provider != "ollama") {
+				t.Fatalf("unexpected include_usage %v for %s", includeUsage, provider)
+			}
+		})
+	}
+}
+
+func TestStreamChatEmitsHeartbeatBeforeFirstDelta(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		time.Sleep(150 * time.Millisecond)
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"ok\"}}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	sink := &mockSink{}
+	streamer := NewStreamer(sink)
+	streamer.heartbeat = 20 * time.Millisecond
+	if _, err := streamer.StreamChat(context.Background(), ChatRequest{
+		SessionID: "s1",
+		Provider:  "vllm",
+		Endpoint:  server.URL,
+		Model:     "x",
+		History:   []ChatMessage{{Role: "user", Content: "hi"}},
+	}); err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if sink.heartbeats == 0 {
+		t.Fatalf("expected at least one heartbeat while waiting for the first delta")
+	}
+}
+
+func TestStreamChatCoalescesSmallDeltas(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		for _, ch := range "abcdefghijkl" {
+			fmt.Fprintf(w, "data: {\"choices\":[{\"delta\":{\"content\":%q}}]}\n\n", string(ch))
+		}
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	sink := &mockSink{}
+	streamer := NewStreamer(sink)
+	msg, err := streamer.StreamChat(context.Background(), ChatRequest{
+		SessionID:     "s1",
+		Provider:      "vllm",
+		Endpoint:      server.URL,
+		Model:         "x",
+		History:       []ChatMessage{{Role: "user", Content: "hi"}},
+		MinDeltaChars: 5,
+	})
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	if msg.Content != "abcdefghijkl" {
+		t.Fatalf("unexpected content: %q", msg.Content)
+	}
+	if strings.Join(sink.answer, "|") != "abcde|fghij|kl" {
+		t.Fatalf("expected coalesced chunks, got %q", sink.answer)
+	}
+}
+
+func TestStreamToFileWritesFullAnswer(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"Hello, \"}}]}\n\n")
+		fmt.Fprint(w, "data: {\"choices\":[{\"delta\":{\"content\":\"file!\"}}]}\n\n")
+		fmt.Fprint(w, "data: [DONE]\n\n")
+	}))
+	defer server.Close()
+
+	root := t.TempDir()
+	req := ChatRequest{
+		Provider: "vllm",
+		Endpoint: server.URL,
+		Model:    "x",
+		History:  []ChatMessage{{Role: "user", Content: "hi"}},
+		ToolRoot: root,
+	}
+
+	written, err := StreamToFile(context.Background(), req, "answer.txt")
+	if err != nil {
+		t.Fatalf("unexpected error: %v", err)
+	}
+	data, err := os.ReadFile(filepath.Join(root, "answer.txt"))
+	if err != nil {
+		t.Fatalf("failed to read output: %v", err)
+	}
+	if string(data) != "Hello, file!" || written != len(data) {
+		t.Fatalf("expected full answer on disk, got %q (%d bytes reported)", data, written)
+	}
+
+	if _, err := StreamToFile(context.Background(), req, filepath.Join(t.TempDir(), "escape.txt")); err == nil {
+		t.Fatalf("expected output outside the tool root to be rejected")
+	}
+}
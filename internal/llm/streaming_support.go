@@ -0,0 +1,114 @@
+package llm
+
+import (
+	"context"
+	"errors"
+	"strings"
+	"sync"
+)
+
+// ErrStreamingUnsupported is returned when an endpoint rejects `stream: true`.
+var ErrStreamingUnsupported = errors.New("streaming not supported")
+
+const streamingProbeMessage = "Reply with OK."
+
+// streamingSupport caches probe results per provider, endpoint and model.
+type streamingSupport struct {
+	mu    sync.Mutex
+	known map[string]bool
+}
+
+func streamingSupportKey(req ChatRequest) string {
+	return strings.ToLower(req.Provider) + "|" + NormalizeBase(req.Endpoint) + "|" + req.Model
+}
+
+func (c *streamingSupport) lookup(req ChatRequest) (supported, ok bool) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	supported, ok = c.known[streamingSupportKey(req)]
+	return supported, ok
+}
+
+func (c *streamingSupport) record(req ChatRequest, supported bool) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	if c.known == nil {
+		c.known = make(map[string]bool)
+	}
+	c.known[streamingSupportKey(req)] = supported
+}
+
+// isStreamingRejection recognizes error bodies from servers that refuse streamed responses.
+func isStreamingRejection(detail string) bool {
+	lower := strings.ToLower(detail)
+	if !strings.Contains(lower, "stream") {
+		return false
+	}
+	for _, phrase := range []string{"not supported", "unsupported", "not implemented", "not available"} {
+		if strings.Contains(lower, phrase) {
+			return true
+		}
+	}
+	return false
+}
+
+// SupportsStreaming sends a minimal streaming request to find out whether the
+// endpoint accepts `stream: true` for req.Model. Definitive answers are cached;
+// transport errors are returned without caching so a later probe can retry.
+func (s *Streamer) SupportsStreaming(ctx context.Context, req ChatRequest) (bool, error) {
+	if strings.EqualFold(req.Provider, "mock") {
+		return true, nil
+	}
+	if supported, ok := s.support.lookup(req); ok {
+		return supported, nil
+	}
+
+	probe := ChatRequest{
+		Provider: req.Provider,
+		Endpoint: req.Endpoint,
+		APIKey:   req.APIKey,
+		Model:    req.Model,
+		History:  []ChatMessage{{Role: "user", Content: streamingProbeMessage}},
+	}
+	_, err := (&Streamer{sink: discardSink{}}).streamChat(ctx, probe)
+	switch {
+	case errors.Is(err, ErrStreamingUnsupported):
+		s.support.record(req, false)
+		return false, nil
+	case err != nil:
+		return false, err
+	}
+	s.support.record(req, true)
+	return true, nil
+}
+
+// completeWithoutStreaming issues a regular request and replays the answer
+// through the sink so the UI behaves as if it had been streamed.
+func (s *Streamer) completeWithoutStreaming(ctx context.Context, req ChatRequest) (ChatMessage, error) {
+	msg, err := providerWithClient(req.Provider, WithRetries(MakeClientWithTimeout(req.RequestTimeout), req.RequestAttempts)).Chat(ctx, req)
+	if err != nil {
+		return ChatMessage{}, err
+	}
+	final := &strings.Builder{}
+	state := streamingState{sink: s.sink, sessionID: req.SessionID, final: final}
+	state.consume(msg.Content)
+	state.finish()
+	s.sink.AnswerDone(req.SessionID, "stop")
+	msg.Content = final.String()
+	return msg, nil
+}
+
+// discardSink drops stream events; used for probes that must not reach the UI.
+type discardSink struct{}
+
+func (discardSink) ThinkingStart(string)              {}
+func (discardSink) ThinkingUpdate(string, string)     {}
+func (discardSink) ThinkingEnd(string)                {}
+func (discardSink) AnswerUpdate(string, string)       {}
+func (discardSink) AnswerDone(string, string)         {}
+func (discardSink) Progress(string, int, string)      {}
+func (discardSink) Usage(string, Usage)               {}
+func (discardSink) ToolCall(string, string)           {}
+func (discardSink) ToolCallDelta(string, int, string) {}
+func (discardSink) ToolOutput(string, string)         {}
+func (discardSink) Heartbeat(string)                  {}
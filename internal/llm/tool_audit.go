@@ -0,0 +1,142 @@
+package llm
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"io/fs"
+	"log"
+	"os"
+	"path/filepath"
+	"strings"
+	"sync"
+	"time"
+)
+
+// maxToolAuditEntries bounds the audit log; the oldest entries are dropped.
+const maxToolAuditEntries = 500
+
+// ToolAuditEntry records one tool invocation for later review.
+type ToolAuditEntry struct {
+	Tool string `json:"tool"`
+	// Arguments is the JSON-encoded argument object, recorded unredacted.
+	Arguments string    `json:"arguments"`
+	Status    string    `json:"status"`
+	Result    string    `json:"result"`
+	Time      time.Time `json:"time"`
+}
+
+// ToolAuditLog keeps the most recent tool invocations and persists them to a
+// file only the current user can read, since arguments may contain secrets.
+// An empty path keeps the log in memory.
+type ToolAuditLog struct {
+	mu      sync.RWMutex
+	path    string
+	entries []ToolAuditEntry
+}
+
+// NewToolAuditLog loads the log at path. A missing or unreadable file starts
+// an empty log.
+func NewToolAuditLog(path string) *ToolAuditLog {
+	l := &ToolAuditLog{path: strings.TrimSpace(path)}
+	if l.path == "" {
+		return l
+	}
+	data, err := os.ReadFile(l.path)
+	if err != nil {
+		if !errors.Is(err, fs.ErrNotExist) {
+			log.Printf("tool audit log unreadable: %v", err)
+		}
+		return l
+	}
+	if err := json.Unmarshal(data, &l.entries); err != nil {
+		log.Printf("tool audit log is not valid JSON, starting a new one: %v", err)
+		l.entries = nil
+	}
+	return l
+}
+
+// Entries returns a copy of the log, oldest first.
+func (l *ToolAuditLog) Entries() []ToolAuditEntry {
+	if l == nil {
+		return nil
+	}
+	l.mu.RLock()
+	defer l.mu.RUnlock()
+	return append([]ToolAuditEntry{}, l.entries...)
+}
+
+// Record appends entry and persists the log. Persistence failures are logged
+// rather than returned so auditing never breaks a running dialogue.
+func (l *ToolAuditLog) Record(entry ToolAuditEntry) {
+	if l == nil {
+		return
+	}
+	l.mu.Lock()
+	defer l.mu.Unlock()
+
+	l.entries = append(l.entries, entry)
+	if excess := len(l.entries) - maxToolAuditEntries; excess > 0 {
+		l.entries = append([]ToolAuditEntry(nil), l.entries[excess:]...)
+	}
+	if l.path == "" {
+		return
+	}
+	if err := l.write(); err != nil {
+		log.Printf("tool audit log not saved: %v", err)
+	}
+}
+
+func (l *ToolAuditLog) write() error {
+	data, err := json.MarshalIndent(l.entries, "", "  ")
+	if err != nil {
+		return err
+	}
+	if err := os.MkdirAll(filepath.Dir(l.path), 0o700); err != nil {
+		return err
+	}
+	tmp := l.path + ".tmp"
+	if err := os.WriteFile(tmp, data, 0o600); err != nil {
+		return err
+	}
+	return os.Rename(tmp, l.path)
+}
+
+// UseToolAudit records the tools run by later streamed dialogues in auditLog.
+// Like UseAnswerTransform, call it before streaming starts.
+func (s *Streamer) UseToolAudit(auditLog *ToolAuditLog) {
+	s.toolAudit = auditLog
+}
+
+// auditedExecutor records every call of next in log.
+type auditedExecutor struct {
+	next ToolExecutor
+	log  *ToolAuditLog
+}
+
+// AuditTools wraps executor so each invocation is recorded in auditLog. A nil
+// log returns executor unchanged.
+func AuditTools(executor ToolExecutor, auditLog *ToolAuditLog) ToolExecutor {
+	if auditLog == nil || executor == nil {
+		return executor
+	}
+	return auditedExecutor{next: executor, log: auditLog}
+}
+
+func (e auditedExecutor) Execute(ctx context.Context, name string, args map[string]any) (string, string) {
+	started := time.Now()
+	result, status := e.next.Execute(ctx, name, args)
+
+	encoded, err := json.Marshal(args)
+	if err != nil {
+		encoded = []byte("{}")
+	}
+	e.log.Record(ToolAuditEntry{
+		Tool:      name,
+		Arguments: string(encoded),
+		Status:    status,
+		Result:    truncate(result, 512),
+		Time:      started,
+	})
+	return result, status
+}
@@ -0,0 +1,63 @@
+package llm
+
+import (
+	"fmt"
+	"log"
+	"strings"
+)
+
+type pendingToolCall struct {
+	id   string
+	name string
+}
+
+// reconcileToolCallIDs makes every tool message reference a call from the
+// assistant turn before it, which OpenAI-compatible servers enforce with a
+// 400. Calls without an ID get a generated one, and a tool message with an
+// unknown ID is pointed at the first unanswered call, preferring one with the
+// same tool name. It returns the number of corrections made.
+func reconcileToolCallIDs(messages []chatCompletionMessage) int {
+	fixes := 0
+	var pending []pendingToolCall
+	for i := range messages {
+		msg := &messages[i]
+		switch msg.Role {
+		case "assistant":
+			pending = pending[:0]
+			for j := range msg.ToolCalls {
+				call := &msg.ToolCalls[j]
+				if strings.TrimSpace(call.ID) == "" {
+					call.ID = fmt.Sprintf("call_%d_%d", i, j)
+					fixes++
+				}
+				pending = append(pending, pendingToolCall{id: call.ID, name: call.Function.Name})
+			}
+		case "tool":
+			match := -1
+			for k, call := range pending {
+				if call.id == msg.ToolCallID {
+					match = k
+					break
+				}
+			}
+			if match < 0 {
+				if len(pending) == 0 {
+					log.Printf("tool message %q references unknown tool_call_id %q and no call is pending", msg.Name, msg.ToolCallID)
+					continue
+				}
+				match = 0
+				for k, call := range pending {
+					if call.name == msg.Name {
+						match = k
+						break
+					}
+				}
+				log.Printf("tool message %q references unknown tool_call_id %q; using %q", msg.Name, msg.ToolCallID, pending[match].id)
+				msg.ToolCallID = pending[match].id
+				fixes++
+			}
+			pending = append(pending[:match], pending[match+1:]...)
+		}
+	}
+	return fixes
+}
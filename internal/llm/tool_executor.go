@@ -4,6 +4,7 @@ import (
 	"compress/gzip"
 	"context"
 	"encoding/json"
+	"errors"
 	"fmt"
 	"io"
 	"net/http"
@@ -12,8 +13,12 @@ import (
 	"strings"
 
 	"shell-werk/internal/shell"
+	"shell-werk/internal/tools"
 )
 
+// ErrToolFailed is returned by dialogues that fail fast on tool errors.
+var ErrToolFailed = errors.New("tool failed")
+
 type ToolExecutor interface {
 	Execute(ctx context.Context, name string, args map[string]any) (string, string)
 }
@@ -23,6 +28,15 @@ type ToolExecutorConfig struct {
 	WebSearchAPIKey   string
 	WebSearchEndpoint string
 	ShellFactory      func() *shell.Executor
+	// ShellOptions configures the default shell factory's env and working directory.
+	ShellOptions shell.Options
+	// ToolRoot, when set, must contain the shell working directory.
+	ToolRoot string
+	// CustomTools are user-defined tools run through the shell executor.
+	CustomTools []tools.CustomTool
+	// OnOutput receives each stdout line of shell and custom tools while they
+	// run; nil only returns the output once the command finishes.
+	OnOutput func(line string)
 }
 
 type defaultToolExecutor struct {
@@ -30,6 +44,10 @@ type defaultToolExecutor struct {
 	webSearchAPIKey   string
 	webSearchEndpoint string
 	shellFactory      func() *shell.Executor
+	shellDir          string
+	toolRoot          string
+	customTools       map[string]tools.CustomTool
+	onOutput          func(line string)
 }
 
 func NewToolExecutor(cfg ToolExecutorConfig) ToolExecutor {
@@ -43,9 +61,20 @@ func NewToolExecutor(cfg ToolExecutorConfig) ToolExecutor {
 		endpoint = braveSearchEndpointDefault
 	}
 
+	shellOptions := cfg.ShellOptions
+	if strings.TrimSpace(shellOptions.Dir) == "" {
+		shellOptions.Dir = cfg.ToolRoot
+	}
 	shellFactory := cfg.ShellFactory
 	if shellFactory == nil {
-		shellFactory = shell.NewExecutor
+		shellFactory = func() *shell.Executor {
+			return shell.NewExecutorWithOptions(shellOptions)
+		}
+	}
+
+	customTools := make(map[string]tools.CustomTool, len(cfg.CustomTools))
+	for _, tool := range cfg.CustomTools {
+		customTools[tool.Name] = tool
 	}
 
 	return &defaultToolExecutor{
@@ -53,6 +82,10 @@ func NewToolExecutor(cfg ToolExecutorConfig) ToolExecutor {
 		webSearchAPIKey:   strings.TrimSpace(cfg.WebSearchAPIKey),
 		webSearchEndpoint: endpoint,
 		shellFactory:      shellFactory,
+		shellDir:          shellOptions.Dir,
+		toolRoot:          strings.TrimSpace(cfg.ToolRoot),
+		customTools:       customTools,
+		onOutput:          cfg.OnOutput,
 	}
 }
 
@@ -65,6 +98,9 @@ func (e *defaultToolExecutor) Execute(ctx context.Context, name string, args map
 	case "web_search":
 		return e.webSearch(ctx, args)
 	default:
+		if tool, ok := e.customTools[name]; ok {
+			return e.custom(ctx, tool, args)
+		}
 		return fmt.Sprintf("tool %s is not implemented", name), "error"
 	}
 }
@@ -120,9 +156,32 @@ func (e *defaultToolExecutor) shell(ctx context.Context, args map[string]any) (s
 	} else if rawArgs, ok := args["args"].([]string); ok {
 		cmdArgs = append(cmdArgs, rawArgs...)
 	}
+	return e.runCommand(ctx, cmdName, cmdArgs, false)
+}
+
+func (e *defaultToolExecutor) custom(ctx context.Context, tool tools.CustomTool, args map[string]any) (string, string) {
+	cmdName, cmdArgs, err := tool.Expand(args)
+	if err != nil {
+		return err.Error(), "error"
+	}
+	// Substituted values come from the model, so they must never pass through
+	// a shell that could read them as code.
+	return e.runCommand(ctx, cmdName, cmdArgs, true)
+}
+
+func (e *defaultToolExecutor) runCommand(ctx context.Context, cmdName string, cmdArgs []string, direct bool) (string, string) {
+	if e.toolRoot != "" && e.shellDir != "" {
+		if err := ensureWithinRoot(e.toolRoot, e.shellDir); err != nil {
+			return fmt.Sprintf("working directory %s is outside the tool root %s", e.shellDir, e.toolRoot), "error"
+		}
+	}
 
 	executor := e.shellFactory()
-	output, err := executor.Execute(ctx, cmdName, cmdArgs)
+	run := executor.ExecuteStreaming
+	if direct {
+		run = executor.ExecuteDirectStreaming
+	}
+	output, err := run(ctx, cmdName, cmdArgs, e.onOutput)
 	if err != nil {
 		failure := strings.TrimSpace(output)
 		if failure == "" {
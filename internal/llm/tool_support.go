@@ -0,0 +1,93 @@
+package llm
+
+import (
+	"context"
+	"errors"
+	"log"
+	"strings"
+	"sync"
+)
+
+// ErrToolsUnsupported is returned when an endpoint rejects a request because
+// it offers tools.
+var ErrToolsUnsupported = errors.New("tools not supported")
+
+// ToolSupport remembers models whose endpoint rejected tool definitions so
+// later requests to them leave tools out. The zero value is ready to use and
+// a nil ToolSupport remembers nothing.
+type ToolSupport struct {
+	mu          sync.Mutex
+	unsupported map[string]bool
+}
+
+func NewToolSupport() *ToolSupport {
+	return &ToolSupport{}
+}
+
+func toolSupportKey(provider, endpoint, model string) string {
+	return streamingSupportKey(ChatRequest{Provider: provider, Endpoint: endpoint, Model: model})
+}
+
+// Unsupported reports whether model at endpoint already rejected tools.
+func (t *ToolSupport) Unsupported(provider, endpoint, model string) bool {
+	if t == nil {
+		return false
+	}
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	return t.unsupported[toolSupportKey(provider, endpoint, model)]
+}
+
+func (t *ToolSupport) markUnsupported(provider, endpoint, model string) {
+	if t == nil {
+		return
+	}
+	log.Printf("[%s] model %s rejected tools; retrying without them", provider, model)
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	if t.unsupported == nil {
+		t.unsupported = make(map[string]bool)
+	}
+	t.unsupported[toolSupportKey(provider, endpoint, model)] = true
+}
+
+// isToolsRejection recognizes error bodies from models or servers that refuse
+// requests carrying tools, e.g. Ollama's "does not support tools" or vLLM
+// started without a tool call parser.
+func isToolsRejection(detail string) bool {
+	lower := strings.ToLower(detail)
+	if strings.Contains(lower, "enable-auto-tool-choice") || strings.Contains(lower, "tool-call-parser") {
+		return true
+	}
+	if !strings.Contains(lower, "tool") {
+		return false
+	}
+	for _, phrase := range []string{"not support", "unsupported", "not implemented", "not available"} {
+		if strings.Contains(lower, phrase) {
+			return true
+		}
+	}
+	return false
+}
+
+// UseToolSupport shares support with the dialogue loops so both remember the
+// same models. Like UseToolAudit, call it before streaming starts.
+func (s *Streamer) UseToolSupport(support *ToolSupport) {
+	s.toolSupport = support
+}
+
+// streamMessagesWithToolFallback streams like streamMessages but, when
+// req.ToolFallback is set, omits tools for models known to reject them and
+// retries once without tools when the endpoint rejects them.
+func (s *Streamer) streamMessagesWithToolFallback(ctx context.Context, req ChatRequest, messages []chatCompletionMessage) (ChatMessage, error) {
+	if req.ToolFallback && s.toolSupport.Unsupported(req.Provider, req.Endpoint, req.Model) {
+		req.Tools, req.ToolDefs = nil, nil
+	}
+	msg, err := s.streamMessages(ctx, req, messages)
+	if req.ToolFallback && len(req.ToolDefs) > 0 && errors.Is(err, ErrToolsUnsupported) {
+		s.toolSupport.markUnsupported(req.Provider, req.Endpoint, req.Model)
+		req.Tools, req.ToolDefs = nil, nil
+		return s.streamMessages(ctx, req, messages)
+	}
+	return msg, err
+}
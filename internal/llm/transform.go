@@ -0,0 +1,105 @@
+package llm
+
+import (
+	"regexp"
+	"strings"
+)
+
+// AnswerTransform rewrites streamed answer text before it is emitted and
+// kept, e.g. to mask secrets. A new transform is created for every stream,
+// so it may carry state from one delta to the next; Flush returns any text it
+// still holds back once the stream ends.
+type AnswerTransform interface {
+	Transform(chunk string) string
+	Flush() string
+}
+
+// AnswerTransformFunc adapts a function that holds nothing back to an
+// AnswerTransform.
+type AnswerTransformFunc func(chunk string) string
+
+func (f AnswerTransformFunc) Transform(chunk string) string { return f(chunk) }
+
+func (AnswerTransformFunc) Flush() string { return "" }
+
+// ansiEscape matches CSI sequences (colors, cursor moves), OSC sequences
+// (titles, hyperlinks) and two-byte escapes.
+var ansiEscape = regexp.MustCompile(`\x1b(?:\[[0-?]*[ -/]*[@-~]|\][^\x07\x1b]*(?:\x07|\x1b\\)|[@-Z\\-_])`)
+
+// maxPendingEscape bounds how much text is held back waiting for an escape
+// sequence to complete; longer runs are emitted as they are.
+const maxPendingEscape = 256
+
+// StripANSI removes terminal escape sequences from text.
+func StripANSI(text string) string {
+	return ansiEscape.ReplaceAllString(text, "")
+}
+
+// NewANSIStripper returns a transform that strips terminal escape sequences
+// from a stream, holding back a sequence split across deltas until it is
+// complete. Text still held back when the stream ends is flushed with its
+// stray escape characters removed.
+func NewANSIStripper() AnswerTransform {
+	return &ansiStripper{}
+}
+
+type ansiStripper struct {
+	pending string
+}
+
+func (a *ansiStripper) Transform(chunk string) string {
+	text := StripANSI(a.pending + chunk)
+	a.pending = ""
+	if i := strings.LastIndexByte(text, '\x1b'); i >= 0 && len(text)-i < maxPendingEscape {
+		a.pending, text = text[i:], text[:i]
+	}
+	return text
+}
+
+func (a *ansiStripper) Flush() string {
+	rest := strings.ReplaceAll(StripANSI(a.pending), "\x1b", "")
+	a.pending = ""
+	return rest
+}
+
+// UseAnswerTransform applies the transforms made by newTransform to the
+// answer text of every later stream. Register transforms before streaming
+// starts; registration is not synchronized with running streams.
+func (s *Streamer) UseAnswerTransform(newTransform func() AnswerTransform) {
+	s.transforms = append(s.transforms, newTransform)
+}
+
+// answerTransform chains the registered transforms for one stream of req,
+// or returns nil when there are none.
+func (s *Streamer) answerTransform(req ChatRequest) AnswerTransform {
+	var chain []AnswerTransform
+	if req.StripANSI {
+		chain = append(chain, NewANSIStripper())
+	}
+	for _, newTransform := range s.transforms {
+		chain = append(chain, newTransform())
+	}
+	if len(chain) == 0 {
+		return nil
+	}
+	return chainedTransform(chain)
+}
+
+// chainedTransform feeds each transform's output, including what it flushes,
+// to the next one.
+type chainedTransform []AnswerTransform
+
+func (c chainedTransform) Transform(chunk string) string {
+	for _, transform := range c {
+		chunk = transform.Transform(chunk)
+	}
+	return chunk
+}
+
+func (c chainedTransform) Flush() string {
+	var rest string
+	for _, transform := range c {
+		rest = transform.Transform(rest) + transform.Flush()
+	}
+	return rest
+}
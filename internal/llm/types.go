@@ -3,6 +3,7 @@ package llm
 import (
 	"time"
 
+	"shell-werk/internal/shell"
 	"shell-werk/internal/tools"
 )
 
@@ -24,6 +25,14 @@ type ChatMessage struct {
 	Role      string     `json:"role"`
 	Content   string     `json:"content"`
 	ToolCalls []ToolCall `json:"tool_calls,omitempty"`
+	// ToolCallID links a tool message to the call it answers.
+	ToolCallID string `json:"tool_call_id,omitempty"`
+	// FinishReason is set when generation stopped for a reason other than completion.
+	FinishReason string `json:"finishReason,omitempty"`
+	// Usage holds the token counts of the completion that produced this message.
+	Usage *Usage `json:"usage,omitempty"`
+	// Citations lists the "[n]" markers of a final answer, when extraction is enabled.
+	Citations []Citation `json:"citations,omitempty"`
 }
 
 // ChatRequest carries the minimal inputs to produce a reply.
@@ -35,8 +44,67 @@ type ChatRequest struct {
 	Model     string                 `json:"model"`
 	Message   string                 `json:"message"`
 	History   []ChatMessage          `json:"history"`
+	// Tools names exactly the tools offered for this request, overriding the
+	// enabled flags of the registry.
 	Tools     []string               `json:"tools"`
 	ChatOnly  bool                   `json:"chatOnly"`
+	// Attachments are file paths whose text is inlined into the user message.
+	Attachments []string `json:"attachments,omitempty"`
+	Sampling  SamplingParams         `json:"sampling"`
+	// ParallelToolCalls asks OpenAI-compatible models to emit at most one tool
+	// call per turn when false. Nil leaves the provider default.
+	ParallelToolCalls *bool `json:"parallelToolCalls,omitempty"`
+	// SystemPrompt replaces the configured system prompt for this request only.
+	// Tool dialogues put it before the built-in prompt.
+	SystemPrompt string `json:"systemPrompt,omitempty"`
+	// ConversationID is echoed on every event of this request so a tabbed UI
+	// can route concurrent streams.
+	ConversationID string `json:"conversationId,omitempty"`
+	// IncludeShellContext adds the host OS, shell and working directory as a system message.
+	IncludeShellContext bool `json:"includeShellContext,omitempty"`
+	// IDPrefix namespaces the session and trace ids generated for this
+	// request, e.g. with the conversation id.
+	IDPrefix string `json:"idPrefix,omitempty"`
+	// StripANSI removes terminal escape sequences from streamed answers.
+	StripANSI bool `json:"-"`
+	// MaxToolIterations bounds the tool rounds of a dialogue; 0 uses the default.
+	MaxToolIterations int `json:"-"`
+	// MaxToolIterationsOverride replaces MaxToolIterations for this request,
+	// up to a fixed ceiling; 0 keeps the configured limit.
+	MaxToolIterationsOverride int `json:"maxToolIterations,omitempty"`
+	// FailFastOnToolError ends a dialogue with ErrToolFailed on the first
+	// failed tool instead of handing the error to the model.
+	FailFastOnToolError bool `json:"-"`
+	// LogLevel is resolved from the backend configuration; debug logs each
+	// outgoing completion body, with secrets redacted.
+	LogLevel LogLevel `json:"-"`
+	// ToolFallback retries without tools when the model rejects them.
+	ToolFallback bool `json:"-"`
+	// ExtractCitations links "[n]" markers in the final answer to tool results.
+	ExtractCitations bool `json:"-"`
+	// IDStyle is resolved from the backend configuration.
+	IDStyle IDStyle `json:"-"`
+	// StreamFallback retries without streaming when the endpoint rejects it.
+	StreamFallback bool `json:"-"`
+	// ParallelTools runs a turn's read-only tool calls concurrently; results keep the call order.
+	ParallelTools bool `json:"-"`
+	// RepetitionGuard stops streams that loop on the same phrase; nil disables it.
+	RepetitionGuard *RepetitionGuard `json:"-"`
+	// MinDeltaChars coalesces streamed answer chunks until this many characters accumulate.
+	MinDeltaChars int `json:"-"`
+	// HardMaxCompletionTokens stops a stream once its estimated length reaches
+	// this many tokens, for servers that ignore max_tokens.
+	HardMaxCompletionTokens int `json:"-"`
+	// RequestTimeout is passed to MakeClientWithTimeout for provider requests.
+	RequestTimeout time.Duration `json:"-"`
+	// RequestAttempts bounds retries of non-streaming completions; zero uses DefaultRequestAttempts.
+	RequestAttempts int `json:"-"`
+	// ToolRoot confines file access. Attachments are refused without one.
+	ToolRoot string `json:"-"`
+	// ToolShell sets the environment and working directory for tool commands.
+	ToolShell shell.Options `json:"-"`
+	// CustomTools are the user-defined tools from the configuration.
+	CustomTools []tools.CustomTool `json:"-"`
 	// Tool-specific configuration (currently used for web search API access).
 	WebSearchAPIKey     string `json:"webSearchApiKey"`
 	WebSearchEndpoint   string `json:"webSearchEndpoint"`
@@ -50,16 +118,47 @@ type ChatResponse struct {
 	Trace     []DialogueTrace `json:"trace"`
 }
 
+// DialogueResponse is the blocking result of a full tool dialogue.
+type DialogueResponse struct {
+	Message ChatMessage `json:"message"`
+	// Messages holds every turn the dialogue appended, including tool turns.
+	Messages  []ChatMessage   `json:"messages"`
+	LatencyMs int64           `json:"latencyMs"`
+	Trace     []DialogueTrace `json:"trace"`
+	// Usage sums the provider-reported token counts of every completion.
+	Usage *Usage `json:"usage,omitempty"`
+}
+
+// ResumeChatRequest continues a streamed answer that was interrupted.
+type ResumeChatRequest struct {
+	Request ChatRequest `json:"request"`
+	Partial string      `json:"partial"`
+}
+
 // ModelsRequest carries provider configuration to list available models.
 type ModelsRequest struct {
+	ID       string `json:"id,omitempty"`
 	Provider string `json:"provider"`
 	Endpoint string `json:"endpoint"`
 	APIKey   string `json:"apiKey"`
+	// RequestID lets the caller abort the listing via CancelRequest.
+	RequestID string `json:"requestId,omitempty"`
+	// DetailedLabels adds family, size and quantization labels for Ollama
+	// models at the cost of one extra request per model.
+	DetailedLabels bool `json:"detailedLabels,omitempty"`
 }
 
 // ModelsResponse wraps the provider model names.
 type ModelsResponse struct {
 	Models []string `json:"models"`
+	// Labels maps model names to display labels when DetailedLabels was requested.
+	Labels map[string]string `json:"labels,omitempty"`
+}
+
+// AllModels aggregates model listings across providers, keyed by provider config.
+type AllModels struct {
+	Models map[string][]string `json:"models"`
+	Errors map[string]string   `json:"errors"`
 }
 
 // DialogueTrace captures intermediate steps in the dialogue feedback loop so the
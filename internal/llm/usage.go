@@ -0,0 +1,84 @@
+package llm
+
+import (
+	"errors"
+	"strings"
+)
+
+// FinishReasonCancelled marks a stream that was stopped by the user.
+const FinishReasonCancelled = "cancelled"
+
+// FinishReasonLength marks a stream stopped by the client-side token cap.
+const FinishReasonLength = "length"
+
+// FinishReasonToolLimit marks a dialogue stopped by the tool iteration limit.
+const FinishReasonToolLimit = "tool_limit"
+
+// providerFinishReason maps an OpenAI-style finish_reason to the value kept
+// on ChatMessage: empty for a normal completion, otherwise the reason as sent,
+// e.g. "length" when the server's token limit truncated the answer.
+func providerFinishReason(reason string) string {
+	switch reason = strings.TrimSpace(reason); reason {
+	case "", "stop", "tool_calls", "function_call":
+		return ""
+	default:
+		return reason
+	}
+}
+
+var errTokenCapReached = errors.New("completion token cap reached")
+
+// Usage reports token counts for a request. Estimated is set when the counts
+// come from EstimateTokens rather than from the provider.
+type Usage struct {
+	PromptTokens     int  `json:"promptTokens"`
+	CompletionTokens int  `json:"completionTokens"`
+	Estimated        bool `json:"estimated"`
+}
+
+// estimateUsage approximates usage from the messages sent and the text received.
+func estimateUsage(messages []chatCompletionMessage, completion string) Usage {
+	usage := Usage{CompletionTokens: EstimateTokens(completion), Estimated: true}
+	for _, msg := range messages {
+		usage.PromptTokens += EstimateTokens(msg.Content)
+	}
+	return usage
+}
+
+// completionUsage is the usage block of OpenAI-compatible responses.
+type completionUsage struct {
+	PromptTokens     int `json:"prompt_tokens"`
+	CompletionTokens int `json:"completion_tokens"`
+}
+
+func (u *completionUsage) toUsage() *Usage {
+	if u == nil {
+		return nil
+	}
+	return &Usage{PromptTokens: u.PromptTokens, CompletionTokens: u.CompletionTokens}
+}
+
+// ollamaUsage maps Ollama's eval counters, which are only set on the final
+// response, to Usage.
+func ollamaUsage(promptEvalCount, evalCount int) *Usage {
+	if promptEvalCount == 0 && evalCount == 0 {
+		return nil
+	}
+	return &Usage{PromptTokens: promptEvalCount, CompletionTokens: evalCount}
+}
+
+// addUsage sums usage across completions; the total is estimated if any part is.
+func addUsage(total, next *Usage) *Usage {
+	if next == nil {
+		return total
+	}
+	if total == nil {
+		sum := *next
+		return &sum
+	}
+	return &Usage{
+		PromptTokens:     total.PromptTokens + next.PromptTokens,
+		CompletionTokens: total.CompletionTokens + next.CompletionTokens,
+		Estimated:        total.Estimated || next.Estimated,
+	}
+}
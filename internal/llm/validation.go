@@ -0,0 +1,109 @@
+package llm
+
+import (
+	"fmt"
+	"net"
+	"net/url"
+	"strings"
+)
+
+// ProviderValidation is the static check result for one provider config.
+type ProviderValidation struct {
+	ID        string   `json:"id"`
+	Provider  string   `json:"provider"`
+	Endpoint  string   `json:"endpoint"`
+	ValidURL  bool     `json:"validUrl"`
+	HasAPIKey bool     `json:"hasApiKey"`
+	Warnings  []string `json:"warnings,omitempty"`
+	Error     string   `json:"error,omitempty"`
+}
+
+// ValidateProviders checks each target's endpoint and credentials without
+// making network calls.
+func ValidateProviders(targets []ModelsRequest) []ProviderValidation {
+	results := make([]ProviderValidation, 0, len(targets))
+	for _, target := range targets {
+		results = append(results, validateProvider(target))
+	}
+	return results
+}
+
+func validateProvider(target ModelsRequest) ProviderValidation {
+	raw := strings.TrimSpace(target.Endpoint)
+	result := ProviderValidation{
+		ID:        modelsKey(target),
+		Provider:  strings.ToLower(strings.TrimSpace(target.Provider)),
+		HasAPIKey: strings.TrimSpace(target.APIKey) != "",
+	}
+
+	normalized, err := NormalizeBaseURL(target.Provider, raw)
+	if err != nil {
+		result.Error = err.Error()
+		return result
+	}
+	result.Endpoint = normalized
+	result.ValidURL = normalized != "" || result.Provider == "mock"
+
+	if raw != "" && !strings.Contains(raw, "://") {
+		result.Warnings = append(result.Warnings, fmt.Sprintf("no scheme given; using %s", normalized))
+	} else if raw != normalized && raw != "" {
+		result.Warnings = append(result.Warnings, fmt.Sprintf("endpoint will be saved as %s", normalized))
+	}
+	if !result.HasAPIKey && ProviderRequiresAuth(result.Provider) {
+		result.Warnings = append(result.Warnings, "an API key is required for this provider")
+	}
+	if result.HasAPIKey && isLoopbackEndpoint(normalized) {
+		result.Warnings = append(result.Warnings, "an API key is set for a local endpoint; local servers usually don't need one and some reject the auth header")
+	}
+	return result
+}
+
+// isLoopbackEndpoint reports whether a normalized endpoint points at this machine.
+func isLoopbackEndpoint(endpoint string) bool {
+	parsed, err := url.Parse(endpoint)
+	if err != nil {
+		return false
+	}
+	host := parsed.Hostname()
+	if strings.EqualFold(host, "localhost") {
+		return true
+	}
+	ip := net.ParseIP(host)
+	return ip != nil && ip.IsLoopback()
+}
+
+// ValidateModelOverride rejects a request model that is given but blank.
+// An empty model is fine: it falls back to the configured selection.
+func ValidateModelOverride(model string) error {
+	if model != "" && strings.TrimSpace(model) == "" {
+		return fmt.Errorf("%w: model must not be blank", ErrInvalidRequest)
+	}
+	return nil
+}
+
+// DetectDuplicateEndpoints groups the IDs of targets whose endpoints normalize
+// to the same base URL. Only groups with more than one member are returned,
+// in the order their first member appears.
+func DetectDuplicateEndpoints(targets []ModelsRequest) [][]string {
+	groups := map[string][]string{}
+	var order []string
+	for _, target := range targets {
+		normalized, err := NormalizeBaseURL(target.Provider, target.Endpoint)
+		if err != nil || normalized == "" {
+			continue
+		}
+		key := strings.ToLower(normalized)
+		if _, seen := groups[key]; !seen {
+			order = append(order, key)
+		}
+		groups[key] = append(groups[key], modelsKey(target))
+	}
+
+	duplicates := [][]string{}
+	for _, key := range order {
+		if len(groups[key]) > 1 {
+			duplicates = append(duplicates, groups[key])
+		}
+	}
+	return duplicates
+}
@@ -0,0 +1,61 @@
+package llm
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"strings"
+)
+
+// ProviderVersion asks the server for its version. Ollama serves
+// /api/version and vLLM serves /version; an empty string means the server
+// does not report one, which is always the case for hosted OpenAI.
+func ProviderVersion(ctx context.Context, target ModelsRequest, client *http.Client) (string, error) {
+	if client == nil {
+		client = MakeClient()
+	}
+
+	base := NormalizeBase(target.Endpoint)
+	var path string
+	switch strings.ToLower(target.Provider) {
+	case "ollama":
+		path = "/api/version"
+	case "vllm":
+		path = "/version"
+	case "mock", "openai":
+		return "", nil
+	default:
+		return "", fmt.Errorf("unsupported provider %q", target.Provider)
+	}
+
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, base+path, nil)
+	if err != nil {
+		return "", err
+	}
+	if strings.TrimSpace(target.APIKey) != "" {
+		req.Header.Set("Authorization", "Bearer "+target.APIKey)
+	}
+
+	resp, err := client.Do(req)
+	if err != nil {
+		return "", fmt.Errorf("version request to %s failed: %w", base+path, err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode == http.StatusNotFound {
+		return "", nil
+	}
+	if resp.StatusCode >= http.StatusBadRequest {
+		return "", fmt.Errorf("version request returned %w", statusError(resp))
+	}
+
+	var decoded struct {
+		Version string `json:"version"`
+	}
+	if err := json.NewDecoder(io.LimitReader(resp.Body, 4_096)).Decode(&decoded); err != nil {
+		return "", nil
+	}
+	return strings.TrimSpace(decoded.Version), nil
+}
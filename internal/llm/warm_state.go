@@ -0,0 +1,135 @@
+package llm
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"strings"
+	"time"
+)
+
+// Warm states reported by ModelWarmState.
+const (
+	WarmStateWarm    = "warm"
+	WarmStateCold    = "cold"
+	WarmStateUnknown = "unknown"
+)
+
+// ModelWarmInfo tells the model picker whether a model is already loaded.
+type ModelWarmInfo struct {
+	Model string `json:"model"`
+	State string `json:"state"`
+	// SizeVRAM is the memory the loaded model occupies on the GPU, in bytes.
+	SizeVRAM int64 `json:"sizeVram,omitempty"`
+	// ExpiresAt is when Ollama will unload the model if it stays idle.
+	ExpiresAt *time.Time `json:"expiresAt,omitempty"`
+}
+
+type ollamaPsResponse struct {
+	Models []struct {
+		Name      string    `json:"name"`
+		Size      int64     `json:"size"`
+		SizeVRAM  int64     `json:"size_vram"`
+		ExpiresAt time.Time `json:"expires_at"`
+	} `json:"models"`
+}
+
+// ModelWarmState reports which of models are loaded. Ollama lists its loaded
+// models at /api/ps; loaded models missing from models are appended. Other
+// providers cannot tell, so every model is reported as unknown.
+func ModelWarmState(ctx context.Context, target ModelsRequest, models []string, client *http.Client) ([]ModelWarmInfo, error) {
+	if !strings.EqualFold(strings.TrimSpace(target.Provider), "ollama") {
+		out := make([]ModelWarmInfo, 0, len(models))
+		for _, model := range models {
+			out = append(out, ModelWarmInfo{Model: model, State: WarmStateUnknown})
+		}
+		return out, nil
+	}
+	decoded, err := fetchOllamaPs(ctx, target, client)
+	if err != nil {
+		return nil, err
+	}
+
+	loaded := make(map[string]ModelWarmInfo, len(decoded.Models))
+	for _, running := range decoded.Models {
+		info := ModelWarmInfo{Model: running.Name, State: WarmStateWarm, SizeVRAM: running.SizeVRAM}
+		if !running.ExpiresAt.IsZero() {
+			expires := running.ExpiresAt
+			info.ExpiresAt = &expires
+		}
+		loaded[running.Name] = info
+	}
+
+	out := make([]ModelWarmInfo, 0, len(models)+len(loaded))
+	for _, model := range models {
+		if info, ok := loaded[model]; ok {
+			out = append(out, info)
+			delete(loaded, model)
+			continue
+		}
+		out = append(out, ModelWarmInfo{Model: model, State: WarmStateCold})
+	}
+	for _, running := range decoded.Models {
+		if info, ok := loaded[running.Name]; ok {
+			out = append(out, info)
+		}
+	}
+	return out, nil
+}
+
+// ProviderAcceleration reports where the models loaded by an Ollama server
+// run: "GPU", "CPU", or a split such as "25% CPU / 75% GPU" when a model
+// does not fit into VRAM. It returns "" for other providers and when no model
+// is loaded, since there is nothing to measure.
+func ProviderAcceleration(ctx context.Context, target ModelsRequest, client *http.Client) (string, error) {
+	if !strings.EqualFold(strings.TrimSpace(target.Provider), "ollama") {
+		return "", nil
+	}
+	decoded, err := fetchOllamaPs(ctx, target, client)
+	if err != nil {
+		return "", err
+	}
+
+	var size, vram int64
+	for _, running := range decoded.Models {
+		size += running.Size
+		vram += running.SizeVRAM
+	}
+	switch {
+	case size <= 0:
+		return "", nil
+	case vram >= size:
+		return "GPU", nil
+	case vram <= 0:
+		return "CPU", nil
+	}
+	gpu := int(vram * 100 / size)
+	return fmt.Sprintf("%d%% CPU / %d%% GPU", 100-gpu, gpu), nil
+}
+
+func fetchOllamaPs(ctx context.Context, target ModelsRequest, client *http.Client) (ollamaPsResponse, error) {
+	if client == nil {
+		client = MakeClient()
+	}
+
+	url := providerBase(target.Provider, target.Endpoint) + "/api/ps"
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, url, nil)
+	if err != nil {
+		return ollamaPsResponse{}, err
+	}
+	resp, err := client.Do(req)
+	if err != nil {
+		return ollamaPsResponse{}, fmt.Errorf("ollama ps request failed: %w", err)
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode >= http.StatusBadRequest {
+		return ollamaPsResponse{}, fmt.Errorf("ollama ps returned %w", statusError(resp))
+	}
+
+	var decoded ollamaPsResponse
+	if err := json.NewDecoder(resp.Body).Decode(&decoded); err != nil {
+		return ollamaPsResponse{}, fmt.Errorf("ollama ps decode failed: %w", err)
+	}
+	return decoded, nil
+}
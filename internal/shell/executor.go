@@ -5,20 +5,65 @@ import (
 	"context"
 	"errors"
 	"fmt"
+	"os"
 	"os/exec"
 	"runtime"
 	"strings"
 	"time"
 )
 
+// Options customizes the environment commands run in.
+type Options struct {
+	// Env adds or replaces environment variables for spawned commands.
+	Env map[string]string `json:"env,omitempty"`
+	// Dir is the working directory; empty means the current directory.
+	Dir string `json:"dir,omitempty"`
+	// SanitizeEnv inherits only safeEnvVars from the parent process.
+	SanitizeEnv bool `json:"sanitizeEnv,omitempty"`
+}
+
+// safeEnvVars are inherited when Options.SanitizeEnv is set.
+var safeEnvVars = []string{
+	"PATH", "HOME", "USER", "LANG", "LC_ALL", "TERM", "SHELL", "TMPDIR",
+	"SystemRoot", "ComSpec", "PATHEXT", "USERPROFILE", "TEMP", "TMP",
+}
+
 // Executor handles safe shell command execution.
-type Executor struct{}
+type Executor struct {
+	opts Options
+}
 
 // NewExecutor creates a new Executor.
 func NewExecutor() *Executor {
 	return &Executor{}
 }
 
+// NewExecutorWithOptions creates an Executor that applies opts to every command.
+func NewExecutorWithOptions(opts Options) *Executor {
+	return &Executor{opts: opts}
+}
+
+func (e *Executor) environment() []string {
+	if len(e.opts.Env) == 0 && !e.opts.SanitizeEnv {
+		return nil
+	}
+
+	var env []string
+	if e.opts.SanitizeEnv {
+		for _, name := range safeEnvVars {
+			if value, ok := os.LookupEnv(name); ok {
+				env = append(env, name+"="+value)
+			}
+		}
+	} else {
+		env = os.Environ()
+	}
+	for name, value := range e.opts.Env {
+		env = append(env, name+"="+value)
+	}
+	return env
+}
+
 // Validate checks if the command is safe to execute.
 func (e *Executor) Validate(command string, args []string) error {
 	trimmed := strings.TrimSpace(command)
@@ -63,6 +108,34 @@ func (e *Executor) Validate(command string, args []string) error {
 
 // Execute runs the command and returns the output.
 func (e *Executor) Execute(ctx context.Context, command string, args []string) (string, error) {
+	return e.ExecuteStreaming(ctx, command, args, nil)
+}
+
+// ExecuteStreaming runs the command like Execute and additionally passes each
+// stdout line, without its newline, to onLine as soon as it is written. A nil
+// onLine behaves exactly like Execute.
+func (e *Executor) ExecuteStreaming(ctx context.Context, command string, args []string, onLine func(line string)) (string, error) {
+	return e.execute(ctx, command, args, onLine, false)
+}
+
+// ExecuteDirectStreaming runs the command like ExecuteStreaming but never
+// through PowerShell, so each argument reaches the program verbatim and can
+// not be read as script code.
+func (e *Executor) ExecuteDirectStreaming(ctx context.Context, command string, args []string, onLine func(line string)) (string, error) {
+	return e.execute(ctx, command, args, onLine, true)
+}
+
+// commandFor builds the command for goos. Windows runs commands through
+// PowerShell, which joins the arguments into one script, unless direct is set.
+func commandFor(ctx context.Context, goos, command string, args []string, direct bool) *exec.Cmd {
+	if goos == "windows" && !direct {
+		psArgs := append([]string{"-Command", command}, args...)
+		return exec.CommandContext(ctx, "powershell", psArgs...)
+	}
+	return exec.CommandContext(ctx, command, args...)
+}
+
+func (e *Executor) execute(ctx context.Context, command string, args []string, onLine func(line string), direct bool) (string, error) {
 	if err := e.Validate(command, args); err != nil {
 		return "", err
 	}
@@ -70,19 +143,18 @@ func (e *Executor) Execute(ctx context.Context, command string, args []string) (
 	ctx, cancel := context.WithTimeout(ctx, 30*time.Second)
 	defer cancel()
 
-	var cmd *exec.Cmd
-	if runtime.GOOS == "windows" {
-		psArgs := append([]string{"-Command", command}, args...)
-		cmd = exec.CommandContext(ctx, "powershell", psArgs...)
-	} else {
-		cmd = exec.CommandContext(ctx, command, args...)
-	}
+	cmd := commandFor(ctx, runtime.GOOS, command, args, direct)
+	cmd.Dir = e.opts.Dir
+	cmd.Env = e.environment()
 
 	var stdout, stderr bytes.Buffer
-	cmd.Stdout = &stdout
+	lines := &lineWriter{out: &stdout, onLine: onLine}
+	cmd.Stdout = lines
 	cmd.Stderr = &stderr
 
-	if err := cmd.Run(); err != nil {
+	err := cmd.Run()
+	lines.flush()
+	if err != nil {
 		output := strings.TrimSpace(stdout.String())
 		if stderr.Len() > 0 {
 			if output != "" {
@@ -103,3 +175,35 @@ func (e *Executor) Execute(ctx context.Context, command string, args []string) (
 
 	return output, nil
 }
+
+// lineWriter copies writes to out and reports every completed line to onLine.
+type lineWriter struct {
+	out     *bytes.Buffer
+	onLine  func(line string)
+	pending []byte
+}
+
+func (w *lineWriter) Write(p []byte) (int, error) {
+	w.out.Write(p)
+	if w.onLine == nil {
+		return len(p), nil
+	}
+	w.pending = append(w.pending, p...)
+	for {
+		idx := bytes.IndexByte(w.pending, '\n')
+		if idx < 0 {
+			break
+		}
+		w.onLine(strings.TrimSuffix(string(w.pending[:idx]), "\r"))
+		w.pending = w.pending[idx+1:]
+	}
+	return len(p), nil
+}
+
+// flush reports a final line that was not terminated by a newline.
+func (w *lineWriter) flush() {
+	if w.onLine != nil && len(w.pending) > 0 {
+		w.onLine(strings.TrimSuffix(string(w.pending), "\r"))
+	}
+	w.pending = nil
+}
@@ -1,7 +1,9 @@
 package shell
 
 import (
+	"context"
 	"runtime"
+	"strings"
 	"testing"
 )
 
@@ -30,3 +32,38 @@ func TestValidateAllowsSafeCommands(t *testing.T) {
 		t.Fatalf("expected safe command to pass validation: %v", err)
 	}
 }
+
+func TestExecuteAppliesConfiguredEnvironment(t *testing.T) {
+	exec := NewExecutorWithOptions(Options{
+		Env:         map[string]string{"SHELLWERK_TOOL_VAR": "configured"},
+		Dir:         t.TempDir(),
+		SanitizeEnv: true,
+	})
+
+	command, args := "sh", []string{"-c", "echo $SHELLWERK_TOOL_VAR"}
+	if runtime.GOOS == "windows" {
+		command, args = "Write-Output", []string{"$env:SHELLWERK_TOOL_VAR"}
+	}
+
+	output, err := exec.Execute(context.Background(), command, args)
+	if err != nil {
+		t.Fatalf("unexpected error: %v (%s)", err, output)
+	}
+	if strings.TrimSpace(output) != "configured" {
+		t.Fatalf("expected configured env var in output, got %q", output)
+	}
+}
+
+func TestDirectCommandsBypassPowerShellOnWindows(t *testing.T) {
+	value := "x; Remove-Item -Recurse ~"
+
+	shelled := commandFor(context.Background(), "windows", "grep", []string{value}, false)
+	if shelled.Args[0] != "powershell" {
+		t.Fatalf("expected Windows commands to run through PowerShell, got %q", shelled.Args)
+	}
+
+	direct := commandFor(context.Background(), "windows", "grep", []string{value}, true)
+	if len(direct.Args) != 2 || direct.Args[0] != "grep" || direct.Args[1] != value {
+		t.Fatalf("expected the value as a single argument of grep, got %q", direct.Args)
+	}
+}
@@ -0,0 +1,90 @@
+package tools
+
+import (
+	"encoding/json"
+	"fmt"
+	"regexp"
+	"strings"
+)
+
+// CustomTool is a user-defined tool backed by a command template. Arguments
+// are substituted into {name} placeholders after the template is split into
+// words, and the command runs without a shell on every platform, so a value
+// can never add extra arguments to the command or run as code. Values
+// starting with "-" are rejected, since the command would read them as options,
+// unless AllowOptions is set.
+type CustomTool struct {
+	Name        string         `json:"name"`
+	Description string         `json:"description"`
+	Parameters  map[string]any `json:"parameters,omitempty"`
+	Command     string         `json:"command"`
+	// AllowOptions lets argument values start with "-".
+	AllowOptions bool `json:"allowOptions,omitempty"`
+}
+
+var placeholder = regexp.MustCompile(`\{([A-Za-z0-9_]+)\}`)
+
+// Metadata describes the tool for the registry and the LLM request.
+func (t CustomTool) Metadata() ToolMetadata {
+	params := t.Parameters
+	if params == nil {
+		params = map[string]any{"type": "object", "properties": map[string]any{}}
+	}
+	return ToolMetadata{
+		ID:          t.Name,
+		Name:        t.Name,
+		Description: t.Description,
+		UIVisible:   true,
+		Enabled:     true,
+		Custom:      true,
+		Definition: ToolDefinition{
+			Type: "function",
+			Function: ToolFunctionDef{
+				Name:        t.Name,
+				Description: t.Description,
+				Parameters:  params,
+			},
+		},
+	}
+}
+
+// Expand fills the command template with args and returns the command and
+// its arguments. Strings are used as-is and other values are JSON-encoded.
+func (t CustomTool) Expand(args map[string]any) (string, []string, error) {
+	words := strings.Fields(t.Command)
+	if len(words) == 0 {
+		return "", nil, fmt.Errorf("tool %s has no command", t.Name)
+	}
+
+	var missing, options []string
+	for i, word := range words {
+		words[i] = placeholder.ReplaceAllStringFunc(word, func(match string) string {
+			name := match[1 : len(match)-1]
+			value, ok := args[name]
+			if !ok {
+				missing = append(missing, name)
+				return ""
+			}
+			text, ok := value.(string)
+			if !ok {
+				encoded, err := json.Marshal(value)
+				if err != nil {
+					text = fmt.Sprint(value)
+				} else {
+					text = string(encoded)
+				}
+			}
+			if strings.HasPrefix(text, "-") && !t.AllowOptions {
+				options = append(options, name)
+			}
+			return text
+		})
+	}
+	if len(missing) > 0 {
+		return "", nil, fmt.Errorf("missing argument %s for tool %s", strings.Join(missing, ", "), t.Name)
+	}
+	if len(options) > 0 {
+		return "", nil, fmt.Errorf("argument %s for tool %s must not start with \"-\"", strings.Join(options, ", "), t.Name)
+	}
+	return words[0], words[1:], nil
+}
@@ -12,7 +12,9 @@ type ToolMetadata struct {
 	Description string         `json:"description"`
 	UIVisible   bool           `json:"uiVisible"`
 	Enabled     bool           `json:"enabled"`
-	Definition  ToolDefinition `json:"-"`
+	// Custom marks tools defined in the configuration rather than built in.
+	Custom     bool           `json:"custom,omitempty"`
+	Definition ToolDefinition `json:"-"`
 }
 
 // ToolFunctionDef describes the function signature for the LLM.
@@ -93,6 +95,36 @@ func (r *ToolRegistry) SetEnabled(id string, enabled bool) (ToolMetadata, error)
 	return tool, nil
 }
 
+// SetCustom replaces the user-defined tools, keeping the enabled state of
+// those that remain. Custom tools never replace a built-in tool.
+func (r *ToolRegistry) SetCustom(custom []ToolMetadata) {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+
+	enabled := map[string]bool{}
+	order := make([]string, 0, len(r.order)+len(custom))
+	for _, id := range r.order {
+		if tool := r.tools[id]; tool.Custom {
+			enabled[id] = tool.Enabled
+			delete(r.tools, id)
+			continue
+		}
+		order = append(order, id)
+	}
+	for _, tool := range custom {
+		if _, exists := r.tools[tool.ID]; exists || tool.ID == "" {
+			continue
+		}
+		tool.Custom = true
+		if was, ok := enabled[tool.ID]; ok {
+			tool.Enabled = was
+		}
+		r.tools[tool.ID] = tool
+		order = append(order, tool.ID)
+	}
+	r.order = order
+}
+
 // DefaultTools returns the built-in tool set and their UI visibility hints.
 func DefaultTools() []ToolMetadata {
 	return []ToolMetadata{
@@ -37,3 +37,33 @@ func TestToolRegistrySetEnabled(t *testing.T) {
 		t.Fatalf("expected error for unknown tool id")
 	}
 }
+
+func TestCustomToolExpandKeepsValuesAsSingleArguments(t *testing.T) {
+	tool := CustomTool{Name: "grep_logs", Command: "grep -n {pattern} {file}"}
+
+	cmd, args, err := tool.Expand(map[string]any{"pattern": "error; rm -rf /", "file": "app.log"})
+	if err != nil {
+		t.Fatalf("Expand returned error: %v", err)
+	}
+	if cmd != "grep" || len(args) != 3 || args[1] != "error; rm -rf /" || args[2] != "app.log" {
+		t.Fatalf("unexpected expansion: %s %q", cmd, args)
+	}
+
+	if _, _, err := tool.Expand(map[string]any{"pattern": "x"}); err == nil {
+		t.Fatalf("expected an error for a missing argument")
+	}
+}
+
+func TestCustomToolExpandRejectsOptionValues(t *testing.T) {
+	tool := CustomTool{Name: "cat_file", Command: "cat {file}"}
+
+	if _, _, err := tool.Expand(map[string]any{"file": "--output=/tmp/x"}); err == nil {
+		t.Fatalf("expected an error for a value that reads as an option")
+	}
+
+	tool.AllowOptions = true
+	_, args, err := tool.Expand(map[string]any{"file": "-n"})
+	if err != nil || len(args) != 1 || args[0] != "-n" {
+		t.Fatalf("expected the option to pass when allowed, got %q, %v", args, err)
+	}
+}
@@ -2,6 +2,7 @@ package main
 
 import (
 	"embed"
+	"log"
 
 	"github.com/wailsapp/wails/v2"
 	"github.com/wailsapp/wails/v2/pkg/options"
@@ -13,6 +14,11 @@ import (
 var assets embed.FS
 
 func main() {
+	// Provider logs carry their own timestamps; the prefix tells app output
+	// apart from the Wails runtime's.
+	log.SetFlags(0)
+	log.SetPrefix("shell-werk: ")
+
 	// Create an instance of the app structure
 	app := NewApp()
 
@@ -27,6 +33,7 @@ func main() {
 		Frameless:        true,
 		BackgroundColour: &options.RGBA{R: 27, G: 38, B: 54, A: 1},
 		OnStartup:        app.startup,
+		ErrorFormatter:   formatError,
 		Windows: &winopts.Options{
 			DisableFramelessWindowDecorations: true,
 		},
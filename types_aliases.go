@@ -1,6 +1,8 @@
 package main
 
 import (
+	"shell-werk/internal/config"
+	"shell-werk/internal/conversations"
 	"shell-werk/internal/llm"
 	"shell-werk/internal/shell"
 	"shell-werk/internal/tools"
@@ -10,15 +12,42 @@ type ToolMetadata = tools.ToolMetadata
 type ToolFunctionDef = tools.ToolFunctionDef
 type ToolDefinition = tools.ToolDefinition
 type SetToolEnabledRequest = tools.SetToolEnabledRequest
+type CustomTool = tools.CustomTool
 
 type ToolCall = llm.ToolCall
 type ToolCallFunction = llm.ToolCallFunction
 type ChatMessage = llm.ChatMessage
 type ChatRequest = llm.ChatRequest
 type ChatResponse = llm.ChatResponse
+type DialogueResponse = llm.DialogueResponse
+type ResumeChatRequest = llm.ResumeChatRequest
 type ModelsRequest = llm.ModelsRequest
 type ModelsResponse = llm.ModelsResponse
+type AllModels = llm.AllModels
 type DialogueTrace = llm.DialogueTrace
 type ContinuationDecisionRequest = llm.ContinuationDecisionRequest
+type FeatureFlags = llm.FeatureFlags
+type ConversationStats = llm.ConversationStats
+type ExchangeTrace = llm.ExchangeTrace
+type ProviderValidation = llm.ProviderValidation
+type SamplingPreview = llm.SamplingPreview
+type FitRequest = llm.FitRequest
+type FitResult = llm.FitResult
+type HealthStatus = llm.HealthStatus
+type ProviderDiagnostics = llm.ProviderDiagnostics
+type ValidationReport = llm.ValidationReport
+type CacheEntryMeta = llm.CacheEntryMeta
+type ModelWarmInfo = llm.ModelWarmInfo
+type ToolAuditEntry = llm.ToolAuditEntry
+type BatchResult = llm.BatchResult
+type Citation = llm.Citation
 
 type ShellExecutor = shell.Executor
+
+type LLMConfiguration = config.LLMConfiguration
+type ConfigStatus = config.Status
+type ProfileSummary = config.ProfileSummary
+type ConfigSaveResult = config.SaveResult
+
+type Conversation = conversations.Conversation
+type ConversationMeta = conversations.Meta